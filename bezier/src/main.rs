@@ -26,10 +26,12 @@
  *
  * KEYS
  * 1-2                 : fill styles
+ * 3-5                 : catmull-rom, centripetal catmull-rom, b-spline
  * f                   : freeze. loop on/off
  * Delete/Backspace    : clear display
  * s                   : save png
  */
+use edapx_curves::{curve_path, CurveType};
 use nannou::prelude::*;
 
 fn main() {
@@ -46,6 +48,7 @@ struct Model {
     y: Vec<f32>,
     filled: bool,
     freeze: bool,
+    curve: CurveType,
 }
 
 fn model(app: &App) -> Model {
@@ -79,6 +82,7 @@ fn model(app: &App) -> Model {
         y,
         filled: false,
         freeze: false,
+        curve: CurveType::CatmullRom,
     }
 }
 
@@ -90,33 +94,13 @@ fn view(app: &App, model: &Model, frame: Frame) {
     draw.background().color(WHITE);
     //}
 
-    let mut builder = nannou::geom::path::Builder::new();
-
-    // TODO implement the Catmull–Rom spline algo in lyon, see curveVertex() in Processing
-    // first control point
-    builder = builder.move_to(pt2(
-        model.x[model.form_resolution - 1] + model.center_x,
-        model.y[model.form_resolution - 1] + model.center_y,
-    ));
-    // only these points are drawn
-    for i in 0..model.form_resolution {
-        builder = builder.quadratic_bezier_to(
-            pt2(model.x[i] + model.center_x, model.y[i] + model.center_y),
-            pt2(model.x[i] + model.center_x, model.y[i] + model.center_y),
-        );
-    }
-    let path = builder
-        .quadratic_bezier_to(
-            pt2(model.x[0] + model.center_x, model.y[0] + model.center_y),
-            pt2(model.x[0] + model.center_x, model.y[0] + model.center_y),
-        )
-        // end control point
-        .move_to(pt2(
-            model.x[1] + model.center_x,
-            model.y[1] + model.center_y,
-        ))
-        .close()
-        .build();
+    let points: Vec<Point2> = (0..model.form_resolution)
+        .map(|i| pt2(model.x[i] + model.center_x, model.y[i] + model.center_y))
+        .collect();
+    let path = match curve_path(&points, model.curve, true) {
+        Some(path) => path,
+        None => return,
+    };
 
     if model.filled {
         let gray = random_f32();
@@ -147,6 +131,15 @@ fn key_released(app: &App, model: &mut Model, key: Key) {
         Key::Key2 => {
             model.filled = true;
         }
+        Key::Key3 => {
+            model.curve = CurveType::CatmullRom;
+        }
+        Key::Key4 => {
+            model.curve = CurveType::Centripetal;
+        }
+        Key::Key5 => {
+            model.curve = CurveType::BSpline;
+        }
         Key::F => {
             model.freeze = !model.freeze;
             if model.freeze {
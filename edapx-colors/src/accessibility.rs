@@ -0,0 +1,282 @@
+// Accessibility checks for the schemes: WCAG contrast ratios and color vision deficiency
+// simulation. The deficiencies are simulated with the matrices of Machado, Oliveira and Fernandes,
+// "A Physiologically-based Model for Simulation of Color Vision Deficiency" (2009), at full
+// severity, in linear RGB.
+use crate::oklab::{linear_to_srgb, srgb_to_linear, Oklab};
+use crate::{to_hex, Scheme};
+use nannou::color::{rgba, Rgba};
+use std::fmt;
+
+// WCAG 2.1 minimum contrast for graphical objects and large text. Normal text needs 4.5
+pub const MIN_CONTRAST: f32 = 3.0;
+// two colors closer than this in OKLab are hard to tell apart, especially from far away
+pub const MIN_DISTANCE: f32 = 0.05;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Deficiency {
+    // no red cones
+    Protanopia,
+    // no green cones, the most common
+    Deuteranopia,
+    // no blue cones
+    Tritanopia,
+}
+
+impl Deficiency {
+    pub fn all() -> &'static [Deficiency] {
+        &[
+            Deficiency::Protanopia,
+            Deficiency::Deuteranopia,
+            Deficiency::Tritanopia,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Deficiency::Protanopia => "protanopia",
+            Deficiency::Deuteranopia => "deuteranopia",
+            Deficiency::Tritanopia => "tritanopia",
+        }
+    }
+
+    fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            Deficiency::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Deficiency::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Deficiency::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+}
+
+// How the color looks to a person with the deficiency. The alpha is kept.
+pub fn simulate(color: Rgba, deficiency: Deficiency) -> Rgba {
+    let rgb = [
+        srgb_to_linear(color.red),
+        srgb_to_linear(color.green),
+        srgb_to_linear(color.blue),
+    ];
+    let m = deficiency.matrix();
+    let row = |r: [f32; 3]| {
+        let c = r[0] * rgb[0] + r[1] * rgb[1] + r[2] * rgb[2];
+        linear_to_srgb(c.clamp(0.0, 1.0))
+    };
+    rgba(row(m[0]), row(m[1]), row(m[2]), color.alpha)
+}
+
+// The WCAG relative luminance, 0 for black and 1 for white. The alpha is ignored.
+pub fn relative_luminance(color: Rgba) -> f32 {
+    0.2126 * srgb_to_linear(color.red)
+        + 0.7152 * srgb_to_linear(color.green)
+        + 0.0722 * srgb_to_linear(color.blue)
+}
+
+// The WCAG contrast ratio between the two colors, from 1 (same luminance) to 21 (black on
+// white). The foreground is blended over the background with its alpha, the background is
+// considered opaque, as it is when it clears the frame.
+pub fn contrast_ratio(foreground: Rgba, background: Rgba) -> f32 {
+    let a = foreground.alpha.clamp(0.0, 1.0);
+    let over = |f: f32, b: f32| f * a + b * (1.0 - a);
+    let blended = rgba(
+        over(foreground.red, background.red),
+        over(foreground.green, background.green),
+        over(foreground.blue, background.blue),
+        1.0,
+    );
+    let (l1, l2) = (relative_luminance(blended), relative_luminance(background));
+    (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+}
+
+// Two colors of a scheme that look different with normal vision but not with a deficiency.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Confusion {
+    pub deficiency: Deficiency,
+    // positions in the scheme
+    pub first: usize,
+    pub second: usize,
+    // the distance in OKLab between the simulated colors
+    pub distance: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub scheme: String,
+    pub colors: Vec<Rgba>,
+    // position in the scheme of the background color
+    pub background: usize,
+    // position and contrast ratio of the foreground colors
+    pub contrast: Vec<(usize, f32)>,
+    pub confusions: Vec<Confusion>,
+}
+
+impl Report {
+    // `background` is the position of the background color in the scheme, every other color of
+    // the scheme is a foreground color.
+    pub fn new(scheme: &Scheme, background: usize) -> Self {
+        let colors = &scheme.colors;
+        let n = colors.len();
+        let background = if n > 0 { background % n } else { 0 };
+        let contrast = (0..n)
+            .filter(|i| *i != background)
+            .map(|i| (i, contrast_ratio(colors[i], colors[background])))
+            .collect();
+
+        let mut confusions = Vec::new();
+        let lab: Vec<Oklab> = colors.iter().map(|c| Oklab::from_rgba(*c)).collect();
+        for deficiency in Deficiency::all() {
+            let simulated: Vec<Oklab> = colors
+                .iter()
+                .map(|c| Oklab::from_rgba(simulate(*c, *deficiency)))
+                .collect();
+            for first in 0..n {
+                for second in first + 1..n {
+                    let distance = simulated[first].distance_squared(&simulated[second]).sqrt();
+                    let normal = lab[first].distance_squared(&lab[second]).sqrt();
+                    if distance < MIN_DISTANCE && normal >= MIN_DISTANCE {
+                        confusions.push(Confusion {
+                            deficiency: *deficiency,
+                            first,
+                            second,
+                            distance,
+                        });
+                    }
+                }
+            }
+        }
+
+        Report {
+            scheme: scheme.name.clone(),
+            colors: colors.clone(),
+            background,
+            contrast,
+            confusions,
+        }
+    }
+
+    // positions of the foreground colors with a contrast lower than MIN_CONTRAST
+    pub fn low_contrast(&self) -> Vec<usize> {
+        self.contrast
+            .iter()
+            .filter(|(_, ratio)| *ratio < MIN_CONTRAST)
+            .map(|(i, _)| *i)
+            .collect()
+    }
+
+    // true if some colors become indistinguishable with a deficiency
+    pub fn is_flagged(&self) -> bool {
+        !self.confusions.is_empty()
+    }
+
+    pub fn passes(&self) -> bool {
+        !self.is_flagged() && self.low_contrast().is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hex = |i: usize| to_hex(self.colors[i]);
+        writeln!(f, "scheme \"{}\"", self.scheme)?;
+        if self.colors.is_empty() {
+            return writeln!(f, "  no colors");
+        }
+        writeln!(
+            f,
+            "  background {} {}",
+            self.background,
+            hex(self.background)
+        )?;
+        for (i, ratio) in self.contrast.iter() {
+            let warning = if *ratio < MIN_CONTRAST {
+                " low contrast"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "  color {} {} contrast {:.2}:1{}",
+                i,
+                hex(*i),
+                ratio,
+                warning
+            )?;
+        }
+        for c in self.confusions.iter() {
+            writeln!(
+                f,
+                "  {}: {} {} and {} {} are indistinguishable",
+                c.deficiency.name(),
+                c.first,
+                hex(c.first),
+                c.second,
+                hex(c.second)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast() {
+        let black = rgba(0.0, 0.0, 0.0, 1.0);
+        let white = rgba(1.0, 1.0, 1.0, 1.0);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-3);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-3);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 1e-3);
+        // a transparent color disappears into the background
+        let transparent = rgba(0.0, 0.0, 0.0, 0.0);
+        assert!((contrast_ratio(transparent, white) - 1.0).abs() < 1e-3);
+        // #767676 is the lightest gray with 4.5:1 on white
+        let gray = rgba(118.0 / 255.0, 118.0 / 255.0, 118.0 / 255.0, 1.0);
+        assert!((contrast_ratio(gray, white) - 4.54).abs() < 1e-2);
+    }
+
+    #[test]
+    fn simulation() {
+        // grays look the same with every deficiency
+        let gray = rgba(0.5, 0.5, 0.5, 0.7);
+        for deficiency in Deficiency::all() {
+            let s = simulate(gray, *deficiency);
+            assert!((s.red - 0.5).abs() < 1e-2);
+            assert!((s.green - 0.5).abs() < 1e-2);
+            assert!((s.blue - 0.5).abs() < 1e-2);
+            assert_eq!(s.alpha, 0.7);
+        }
+    }
+
+    #[test]
+    fn red_and_green_are_flagged() {
+        let red = rgba(0.8, 0.35, 0.0, 1.0);
+        let green = rgba(0.5, 0.5, 0.0, 1.0);
+        let black = rgba(0.0, 0.0, 0.0, 1.0);
+        let report = Report::new(&Scheme::new("red green", vec![red, green, black]), 2);
+        assert!(report.is_flagged());
+        assert!(report
+            .confusions
+            .iter()
+            .any(|c| c.deficiency == Deficiency::Deuteranopia && c.first == 0 && c.second == 1));
+        assert!(report.low_contrast().is_empty());
+        assert!(!report.passes());
+
+        let blue = rgba(0.0, 0.2, 0.9, 1.0);
+        let white = rgba(1.0, 1.0, 1.0, 1.0);
+        let report = Report::new(&Scheme::new("blue", vec![blue, white]), 1);
+        assert!(!report.is_flagged());
+        assert!(report.passes());
+        assert!(report.to_string().contains("contrast"));
+    }
+}
@@ -0,0 +1,225 @@
+// Adobe Swatch Exchange files. Every group of swatches becomes a scheme, the swatches outside
+// of a group are collected in a scheme called like the file.
+// The format is described in http://www.selapa.net/swatches/colors/fileformats.php#adobe_ase
+use crate::error::PaletteError;
+use crate::Scheme;
+use nannou::color::{rgba, Rgba};
+
+const GROUP_START: u16 = 0xc001;
+const GROUP_END: u16 = 0xc002;
+const COLOR_ENTRY: u16 = 0x0001;
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], PaletteError> {
+        if self.pos + n > self.bytes.len() {
+            return Err(PaletteError::Parse(
+                "unexpected end of the ASE file".to_string(),
+            ));
+        }
+        let slice = &self.bytes[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16, PaletteError> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, PaletteError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32, PaletteError> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    // length in UTF-16 code units, null terminator included
+    fn name(&mut self) -> Result<String, PaletteError> {
+        let len = self.u16()? as usize;
+        let mut units = Vec::with_capacity(len);
+        for _ in 0..len {
+            units.push(self.u16()?);
+        }
+        let name = String::from_utf16_lossy(&units);
+        Ok(name.trim_end_matches('\0').to_string())
+    }
+}
+
+pub fn parse(bytes: &[u8], name: &str) -> Result<Vec<Scheme>, PaletteError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.take(4)? != b"ASEF" {
+        return Err(PaletteError::Parse("missing ASEF signature".to_string()));
+    }
+    // version, 1.0
+    reader.u16()?;
+    reader.u16()?;
+    let n_blocks = reader.u32()?;
+
+    let mut ungrouped = Scheme::new(name, Vec::new());
+    let mut groups: Vec<Scheme> = Vec::new();
+    let mut in_group = false;
+    for _ in 0..n_blocks {
+        let block_type = reader.u16()?;
+        let length = reader.u32()? as usize;
+        let end = reader.pos + length;
+        match block_type {
+            GROUP_START => {
+                groups.push(Scheme::new(&reader.name()?, Vec::new()));
+                in_group = true;
+            }
+            GROUP_END => in_group = false,
+            COLOR_ENTRY => {
+                // the name of the swatch is not used
+                reader.name()?;
+                let color = read_color(&mut reader)?;
+                match groups.last_mut() {
+                    Some(group) if in_group => group.colors.push(color),
+                    _ => ungrouped.colors.push(color),
+                }
+            }
+            _ => (),
+        }
+        // skip what is left of the block, like the color type (global, spot, normal)
+        reader.pos = end;
+    }
+
+    let mut schemes = Vec::new();
+    if !ungrouped.colors.is_empty() {
+        schemes.push(ungrouped);
+    }
+    schemes.extend(groups.into_iter().filter(|g| !g.colors.is_empty()));
+    Ok(schemes)
+}
+
+fn read_color(reader: &mut Reader) -> Result<Rgba, PaletteError> {
+    let model = reader.take(4)?;
+    match model {
+        b"RGB " => Ok(rgba(reader.f32()?, reader.f32()?, reader.f32()?, 1.0)),
+        b"CMYK" => {
+            let (c, m, y, k) = (reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?);
+            Ok(rgba(
+                (1.0 - c) * (1.0 - k),
+                (1.0 - m) * (1.0 - k),
+                (1.0 - y) * (1.0 - k),
+                1.0,
+            ))
+        }
+        b"Gray" => {
+            let g = reader.f32()?;
+            Ok(rgba(g, g, g, 1.0))
+        }
+        b"LAB " => {
+            // L is between 0 and 1, a and b between -128 and 127
+            let (l, a, b) = (reader.f32()? * 100.0, reader.f32()?, reader.f32()?);
+            Ok(lab_to_rgba(l, a, b))
+        }
+        _ => Err(PaletteError::Parse(format!(
+            "unknown color model {}",
+            String::from_utf8_lossy(model)
+        ))),
+    }
+}
+
+// CIE Lab with the D50 white point, the one used by Adobe, to sRGB
+fn lab_to_rgba(l: f32, a: f32, b: f32) -> Rgba {
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+    let f_inv = |t: f32| {
+        if t > 6.0 / 29.0 {
+            t * t * t
+        } else {
+            3.0 * (6.0 / 29.0) * (6.0 / 29.0) * (t - 4.0 / 29.0)
+        }
+    };
+    let (x, y, z) = (0.9642 * f_inv(fx), f_inv(fy), 0.8249 * f_inv(fz));
+    // XYZ D50 to linear sRGB, Bradford adapted
+    let r = 3.1339 * x - 1.6169 * y - 0.4906 * z;
+    let g = -0.9788 * x + 1.9161 * y + 0.0335 * z;
+    let bl = 0.0719 * x - 0.2290 * y + 1.4052 * z;
+    let encode = |c: f32| {
+        let c = c.clamp(0.0, 1.0);
+        if c <= 0.003_130_8 {
+            12.92 * c
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    rgba(encode(r), encode(g), encode(bl), 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name_bytes(name: &str) -> Vec<u8> {
+        let units: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut bytes = (units.len() as u16).to_be_bytes().to_vec();
+        for u in units {
+            bytes.extend_from_slice(&u.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn block(block_type: u16, content: Vec<u8>) -> Vec<u8> {
+        let mut bytes = block_type.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&(content.len() as u32).to_be_bytes());
+        bytes.extend(content);
+        bytes
+    }
+
+    fn rgb_entry(r: f32, g: f32, b: f32) -> Vec<u8> {
+        let mut content = name_bytes("swatch");
+        content.extend_from_slice(b"RGB ");
+        for v in [r, g, b].iter() {
+            content.extend_from_slice(&v.to_be_bytes());
+        }
+        // normal color
+        content.extend_from_slice(&2u16.to_be_bytes());
+        block(COLOR_ENTRY, content)
+    }
+
+    #[test]
+    fn groups_become_schemes() {
+        let blocks = vec![
+            rgb_entry(1.0, 1.0, 1.0),
+            block(GROUP_START, name_bytes("warm")),
+            rgb_entry(1.0, 0.0, 0.0),
+            rgb_entry(1.0, 0.5, 0.0),
+            block(GROUP_END, Vec::new()),
+        ];
+        let mut bytes = b"ASEF".to_vec();
+        bytes.extend_from_slice(&1u16.to_be_bytes());
+        bytes.extend_from_slice(&0u16.to_be_bytes());
+        bytes.extend_from_slice(&(blocks.len() as u32).to_be_bytes());
+        for b in blocks {
+            bytes.extend(b);
+        }
+
+        let schemes = parse(&bytes, "file").unwrap();
+        assert_eq!(schemes.len(), 2);
+        assert_eq!(schemes[0].name, "file");
+        assert_eq!(schemes[0].colors.len(), 1);
+        assert_eq!(schemes[1].name, "warm");
+        assert_eq!(schemes[1].colors.len(), 2);
+        assert_eq!(schemes[1].colors[1].green, 0.5);
+
+        assert!(parse(b"ASEF", "file").is_err());
+        assert!(parse(b"GIMP Palette", "file").is_err());
+    }
+
+    #[test]
+    fn lab_white_and_black() {
+        let white = lab_to_rgba(100.0, 0.0, 0.0);
+        let black = lab_to_rgba(0.0, 0.0, 0.0);
+        assert!((white.red - 1.0).abs() < 0.01 && (white.blue - 1.0).abs() < 0.01);
+        assert!(black.red < 0.01 && black.green < 0.01);
+    }
+}
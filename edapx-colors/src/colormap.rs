@@ -0,0 +1,96 @@
+// Scientific colormaps, perceptually uniform and readable in grayscale (except turbo).
+// Every map is stored as 9 evenly spaced samples and interpolated in OKLab.
+use crate::gradient::Gradient;
+use crate::loader::parse_hex;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Colormap {
+    Viridis,
+    Magma,
+    Inferno,
+    Plasma,
+    Turbo,
+}
+
+impl Colormap {
+    pub fn all() -> &'static [Colormap] {
+        &[
+            Colormap::Viridis,
+            Colormap::Magma,
+            Colormap::Inferno,
+            Colormap::Plasma,
+            Colormap::Turbo,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colormap::Viridis => "viridis",
+            Colormap::Magma => "magma",
+            Colormap::Inferno => "inferno",
+            Colormap::Plasma => "plasma",
+            Colormap::Turbo => "turbo",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Colormap> {
+        Colormap::all()
+            .iter()
+            .find(|map| map.name() == name.to_lowercase())
+            .copied()
+    }
+
+    fn samples(&self) -> [&'static str; 9] {
+        match self {
+            Colormap::Viridis => [
+                "#440154", "#472d7b", "#3b528b", "#2c728e", "#21918c", "#28ae80", "#5ec962",
+                "#addc30", "#fde725",
+            ],
+            Colormap::Magma => [
+                "#000004", "#1c1044", "#4f127b", "#812581", "#b5367a", "#e55064", "#fb8761",
+                "#fec287", "#fcfdbf",
+            ],
+            Colormap::Inferno => [
+                "#000004", "#1f0c48", "#550f6d", "#88226a", "#ba3655", "#e35933", "#f98e09",
+                "#f9cb35", "#fcffa4",
+            ],
+            Colormap::Plasma => [
+                "#0d0887", "#4c02a1", "#7e03a8", "#a92395", "#cc4778", "#e66c5c", "#f89540",
+                "#fdc527", "#f0f921",
+            ],
+            Colormap::Turbo => [
+                "#30123b", "#4662d7", "#36aaf9", "#1ae4b6", "#72fe5e", "#c8ef34", "#faba39",
+                "#f66b19", "#7a0403",
+            ],
+        }
+    }
+
+    pub fn gradient(&self) -> Gradient {
+        let colors: Vec<_> = self
+            .samples()
+            .iter()
+            .map(|hex| parse_hex(hex).unwrap())
+            .collect();
+        Gradient::new(&colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colormaps() {
+        for map in Colormap::all().iter() {
+            assert_eq!(Colormap::from_name(map.name()), Some(*map));
+            let gradient = map.gradient();
+            assert_eq!(gradient.stops().len(), 9);
+        }
+        assert_eq!(Colormap::from_name("Magma"), Some(Colormap::Magma));
+        assert!(Colormap::from_name("jet").is_none());
+
+        let viridis = Colormap::Viridis.gradient();
+        let start = viridis.get(0.0);
+        assert!((start.red - 0x44 as f32 / 255.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,249 @@
+// An egui panel to preview and edit a palette. Enabled with the "egui" feature.
+//
+// let mut editor = PaletteEditor::new("palettes/edited.toml");
+// ...
+// egui::SidePanel::left("Colors").show(&ctx, |ui| {
+//     if editor.show(ui, &mut model.palette, &mut settings.scheme_id) {
+//         // a scheme has been selected or a color has changed
+//     }
+// });
+use crate::{Deficiency, Palette, Report, Scheme, MIN_CONTRAST};
+use egui::{Color32, Sense, Stroke, Ui, Vec2};
+use nannou::color::rgba;
+
+const SWATCH_SIZE: f32 = 18.0;
+
+pub struct PaletteEditor {
+    // where the palette is saved
+    pub path: String,
+    // the offset used by the sketch, the background of the contrast report is `get_fifth`
+    pub color_offset: usize,
+    // the color being edited in the selected scheme
+    selected_color: Option<usize>,
+    status: String,
+}
+
+impl PaletteEditor {
+    pub fn new(path: &str) -> Self {
+        PaletteEditor {
+            path: path.to_string(),
+            color_offset: 0,
+            selected_color: None,
+            status: String::new(),
+        }
+    }
+
+    // Shows a row of swatches for every scheme, clicking a row selects the scheme.
+    // The colors of the selected scheme can be edited, moved, added or removed.
+    // The vision buttons preview the palette with a color vision deficiency, see `Palette::vision`.
+    // It returns true when the selected scheme, the vision, a name or any color has changed.
+    pub fn show(&mut self, ui: &mut Ui, palette: &mut Palette, scheme_id: &mut usize) -> bool {
+        let mut changed = false;
+        if !palette.is_empty() {
+            *scheme_id %= palette.len();
+        }
+
+        for (id, scheme) in palette.schemes.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let selected = id == *scheme_id;
+                for (i, color) in scheme.colors.iter().enumerate() {
+                    let highlighted = selected && self.selected_color == Some(i);
+                    if swatch(ui, palette.preview(*color), highlighted).clicked() {
+                        if !selected {
+                            *scheme_id = id;
+                            changed = true;
+                        }
+                        self.selected_color = Some(i);
+                    }
+                }
+                if ui.selectable_label(selected, &scheme.name).clicked() && !selected {
+                    *scheme_id = id;
+                    self.selected_color = None;
+                    changed = true;
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("vision");
+            if ui
+                .selectable_label(palette.vision.is_none(), "normal")
+                .clicked()
+            {
+                palette.vision = None;
+                changed = true;
+            }
+            for deficiency in Deficiency::all() {
+                let selected = palette.vision == Some(*deficiency);
+                if ui.selectable_label(selected, deficiency.name()).clicked() {
+                    palette.vision = Some(*deficiency);
+                    changed = true;
+                }
+            }
+        });
+
+        if let Some(scheme) = palette.schemes.get_mut(*scheme_id) {
+            ui.separator();
+            changed |= self.edit_scheme(ui, scheme);
+        }
+        if let Some(report) = palette.report(*scheme_id, self.color_offset) {
+            ui.separator();
+            show_report(ui, &report);
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.path);
+            if ui.button("save").clicked() {
+                self.status = match palette.save(&self.path) {
+                    Ok(()) => format!("saved {}", self.path),
+                    Err(err) => err.to_string(),
+                };
+            }
+        });
+        if !self.status.is_empty() {
+            ui.label(&self.status);
+        }
+        changed
+    }
+
+    fn edit_scheme(&mut self, ui: &mut Ui, scheme: &mut Scheme) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("name");
+            if ui.text_edit_singleline(&mut scheme.name).changed() {
+                changed = true;
+            }
+        });
+
+        let n = scheme.colors.len();
+        let selected = match self.selected_color {
+            Some(i) if i < n => Some(i),
+            _ => None,
+        };
+        if let Some(i) = selected {
+            let color = &mut scheme.colors[i];
+            let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+            let mut srgba = [
+                byte(color.red),
+                byte(color.green),
+                byte(color.blue),
+                byte(color.alpha),
+            ];
+            ui.horizontal(|ui| {
+                ui.label(format!("color {}", i));
+                if ui
+                    .color_edit_button_srgba_unmultiplied(&mut srgba)
+                    .changed()
+                {
+                    *color = rgba(
+                        srgba[0] as f32 / 255.0,
+                        srgba[1] as f32 / 255.0,
+                        srgba[2] as f32 / 255.0,
+                        srgba[3] as f32 / 255.0,
+                    );
+                    changed = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("alpha");
+                if ui
+                    .add(egui::Slider::new(&mut color.alpha, 0.0..=1.0))
+                    .changed()
+                {
+                    changed = true;
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            if let Some(i) = selected {
+                if ui.small_button("<").clicked() && i > 0 {
+                    scheme.colors.swap(i, i - 1);
+                    self.selected_color = Some(i - 1);
+                    changed = true;
+                }
+                if ui.small_button(">").clicked() && i + 1 < n {
+                    scheme.colors.swap(i, i + 1);
+                    self.selected_color = Some(i + 1);
+                    changed = true;
+                }
+                if ui.small_button("remove").clicked() {
+                    scheme.colors.remove(i);
+                    self.selected_color = None;
+                    changed = true;
+                }
+            }
+            if ui.small_button("add").clicked() {
+                // a copy of the selected color, or of the last one
+                let color = selected
+                    .or_else(|| n.checked_sub(1))
+                    .map(|i| scheme.colors[i])
+                    .unwrap_or_else(|| rgba(1.0, 1.0, 1.0, 1.0));
+                scheme.colors.push(color);
+                self.selected_color = Some(scheme.colors.len() - 1);
+                changed = true;
+            }
+        });
+        changed
+    }
+}
+
+// The contrast of every color against the background and the colors that a person with a color
+// vision deficiency can not tell apart. Sketches can show it in their own panel.
+pub fn show_report(ui: &mut Ui, report: &Report) {
+    ui.label(format!("contrast with color {}", report.background));
+    ui.horizontal_wrapped(|ui| {
+        for (i, ratio) in report.contrast.iter() {
+            let text = format!("{}: {:.1}:1", i, ratio);
+            if *ratio < MIN_CONTRAST {
+                ui.colored_label(Color32::RED, text);
+            } else {
+                ui.label(text);
+            }
+        }
+    });
+    for confusion in report.confusions.iter() {
+        ui.colored_label(
+            Color32::RED,
+            format!(
+                "{}: {} and {} look the same",
+                confusion.deficiency.name(),
+                confusion.first,
+                confusion.second
+            ),
+        );
+    }
+}
+
+// A square with the color, on top of a checkerboard so that the alpha is visible.
+fn swatch(ui: &mut Ui, color: nannou::color::Rgba, highlighted: bool) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::click());
+    let painter = ui.painter();
+    let half = SWATCH_SIZE / 2.0;
+    for corner in [rect.left_top(), rect.center()].iter() {
+        painter.rect_filled(
+            egui::Rect::from_min_size(*corner, Vec2::splat(half)),
+            0.0,
+            Color32::GRAY,
+        );
+    }
+    let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    painter.rect_filled(
+        rect,
+        0.0,
+        Color32::from_rgba_unmultiplied(
+            byte(color.red),
+            byte(color.green),
+            byte(color.blue),
+            byte(color.alpha),
+        ),
+    );
+    let stroke = if highlighted {
+        Stroke::new(2.0, Color32::WHITE)
+    } else {
+        Stroke::new(1.0, Color32::DARK_GRAY)
+    };
+    painter.rect_stroke(rect, 0.0, stroke);
+    response.on_hover_text(crate::to_hex(color))
+}
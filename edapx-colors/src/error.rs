@@ -0,0 +1,53 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum PaletteError {
+    Io(io::Error),
+    // the extension of the file does not match any of the supported formats
+    UnsupportedFormat(String),
+    // the file has been read but its content is not valid, the string explains why
+    Parse(String),
+    // the image used to extract a scheme cannot be opened
+    Image(String),
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaletteError::Io(err) => write!(f, "cannot read the palette: {}", err),
+            PaletteError::UnsupportedFormat(ext) => {
+                write!(f, "unsupported palette format: {}", ext)
+            }
+            PaletteError::Parse(msg) => write!(f, "invalid palette: {}", msg),
+            PaletteError::Image(msg) => write!(f, "cannot open the image: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PaletteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PaletteError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PaletteError {
+    fn from(err: io::Error) -> Self {
+        PaletteError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for PaletteError {
+    fn from(err: serde_json::Error) -> Self {
+        PaletteError::Parse(err.to_string())
+    }
+}
+
+impl From<toml::de::Error> for PaletteError {
+    fn from(err: toml::de::Error) -> Self {
+        PaletteError::Parse(err.to_string())
+    }
+}
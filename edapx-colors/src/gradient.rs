@@ -0,0 +1,281 @@
+// Multi-stop gradients. Unlike nannou's Gradient<Hsl> the colors are interpolated in a perceptual
+// space (OKLab) or in linear RGB, so the midpoints are not muddy or shifted in hue.
+use crate::oklab::{linear_to_srgb, srgb_to_linear, Oklab};
+use nannou::color::{rgba, Rgba};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Interpolation {
+    Oklab,
+    LinearRgb,
+}
+
+// applied between every pair of stops
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    // holds every color, a gradient with hard edges
+    Step,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Easing::Step => 0.0,
+        }
+    }
+}
+
+// what happens outside of [0, 1]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Spread {
+    // the first and the last colors are used
+    Clamp,
+    // the gradient starts again from the beginning
+    Repeat,
+    // the gradient goes back and forth
+    Mirror,
+    // like Repeat, but the last color blends into the first one, without a seam
+    Cyclic,
+}
+
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    // positions between 0 and 1, sorted
+    stops: Vec<(f32, Rgba)>,
+    pub interpolation: Interpolation,
+    pub easing: Easing,
+    pub spread: Spread,
+}
+
+impl Gradient {
+    // The colors are evenly spaced, a scheme can be used directly:
+    // Gradient::new(palette.get_scheme(scheme_id))
+    pub fn new(colors: &[Rgba]) -> Self {
+        let n = colors.len();
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let position = if n > 1 {
+                    i as f32 / (n - 1) as f32
+                } else {
+                    0.0
+                };
+                (position, *c)
+            })
+            .collect();
+        Gradient::with_stops(stops)
+    }
+
+    // The colors are evenly spaced on a loop, after the last color the gradient goes back to the
+    // first one. Useful for animations, gradient.get(app.time * speed)
+    pub fn cyclic(colors: &[Rgba]) -> Self {
+        let n = colors.len();
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i as f32 / n as f32, *c))
+            .collect();
+        Gradient::with_stops(stops).spread(Spread::Cyclic)
+    }
+
+    // Stops at arbitrary positions, they are clamped to [0, 1] and sorted.
+    pub fn with_stops(mut stops: Vec<(f32, Rgba)>) -> Self {
+        for stop in stops.iter_mut() {
+            stop.0 = stop.0.clamp(0.0, 1.0);
+        }
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Gradient {
+            stops,
+            interpolation: Interpolation::Oklab,
+            easing: Easing::Linear,
+            spread: Spread::Clamp,
+        }
+    }
+
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn spread(mut self, spread: Spread) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn stops(&self) -> &[(f32, Rgba)] {
+        &self.stops
+    }
+
+    // The color at `t`. Values outside of [0, 1] are handled by the spread mode.
+    pub fn get(&self, t: f32) -> Rgba {
+        if self.stops.is_empty() {
+            return rgba(0.0, 0.0, 0.0, 0.0);
+        }
+        let t = match self.spread {
+            Spread::Clamp => t.clamp(0.0, 1.0),
+            Spread::Repeat | Spread::Cyclic => t.rem_euclid(1.0),
+            Spread::Mirror => {
+                let m = t.rem_euclid(2.0);
+                if m > 1.0 {
+                    2.0 - m
+                } else {
+                    m
+                }
+            }
+        };
+
+        let first = self.stops[0];
+        let last = self.stops[self.stops.len() - 1];
+        // between the last and the first stop, passing through 1 and 0
+        if self.spread == Spread::Cyclic && (t > last.0 || t < first.0) {
+            let span = 1.0 - last.0 + first.0;
+            let local = if span > 0.0 {
+                (t - last.0).rem_euclid(1.0) / span
+            } else {
+                0.0
+            };
+            return self.mix(last.1, first.1, local);
+        }
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+        let i = self.stops.iter().position(|s| s.0 > t).unwrap_or(0).max(1);
+        let (from, to) = (self.stops[i - 1], self.stops[i]);
+        let span = to.0 - from.0;
+        let local = if span > 0.0 { (t - from.0) / span } else { 1.0 };
+        self.mix(from.1, to.1, local)
+    }
+
+    // `n` colors sampled evenly from the gradient, the first and the last stop included.
+    pub fn colors(&self, n: usize) -> Vec<Rgba> {
+        (0..n)
+            .map(|i| {
+                let t = if n > 1 {
+                    i as f32 / (n - 1) as f32
+                } else {
+                    0.0
+                };
+                self.get(t)
+            })
+            .collect()
+    }
+
+    fn mix(&self, from: Rgba, to: Rgba, t: f32) -> Rgba {
+        let t = self.easing.apply(t);
+        let alpha = from.alpha + (to.alpha - from.alpha) * t;
+        match self.interpolation {
+            Interpolation::Oklab => Oklab::from_rgba(from)
+                .mix(&Oklab::from_rgba(to), t)
+                .to_rgba(alpha),
+            Interpolation::LinearRgb => {
+                let lerp = |a: f32, b: f32| {
+                    let (a, b) = (srgb_to_linear(a), srgb_to_linear(b));
+                    linear_to_srgb(a + (b - a) * t)
+                };
+                rgba(
+                    lerp(from.red, to.red),
+                    lerp(from.green, to.green),
+                    lerp(from.blue, to.blue),
+                    alpha,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Rgba, b: Rgba) -> bool {
+        (a.red - b.red).abs() < 1e-3
+            && (a.green - b.green).abs() < 1e-3
+            && (a.blue - b.blue).abs() < 1e-3
+            && (a.alpha - b.alpha).abs() < 1e-3
+    }
+
+    fn rgb() -> Vec<Rgba> {
+        vec![
+            rgba(1.0, 0.0, 0.0, 1.0),
+            rgba(0.0, 1.0, 0.0, 1.0),
+            rgba(0.0, 0.0, 1.0, 0.5),
+        ]
+    }
+
+    #[test]
+    fn stops_are_hit() {
+        let colors = rgb();
+        for interpolation in [Interpolation::Oklab, Interpolation::LinearRgb].iter() {
+            let gradient = Gradient::new(&colors).interpolation(*interpolation);
+            assert!(close(gradient.get(0.0), colors[0]));
+            assert!(close(gradient.get(0.5), colors[1]));
+            assert!(close(gradient.get(1.0), colors[2]));
+            assert!((gradient.get(0.75).alpha - 0.75).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn spread_modes() {
+        let colors = rgb();
+        let clamp = Gradient::new(&colors);
+        assert!(close(clamp.get(-1.0), colors[0]));
+        assert!(close(clamp.get(2.0), colors[2]));
+
+        let repeat = Gradient::new(&colors).spread(Spread::Repeat);
+        assert!(close(repeat.get(1.25), repeat.get(0.25)));
+
+        let mirror = Gradient::new(&colors).spread(Spread::Mirror);
+        assert!(close(mirror.get(1.25), mirror.get(0.75)));
+        assert!(close(mirror.get(-0.25), mirror.get(0.25)));
+
+        // the stops do not reach 1, the last color blends back into the first one
+        let cyclic =
+            Gradient::with_stops(vec![(0.0, colors[0]), (0.5, colors[1])]).spread(Spread::Cyclic);
+        assert!(close(cyclic.get(1.0), colors[0]));
+        assert!(close(cyclic.get(0.5), colors[1]));
+        assert!(close(cyclic.get(0.75), clamp_mix(colors[1], colors[0])));
+
+        let cyclic = Gradient::cyclic(&colors);
+        assert!(close(cyclic.get(0.0), colors[0]));
+        assert!(close(cyclic.get(1.0), colors[0]));
+        assert!(close(cyclic.get(2.0 / 3.0), colors[2]));
+        assert!(close(
+            cyclic.get(5.0 / 6.0),
+            clamp_mix(colors[2], colors[0])
+        ));
+    }
+
+    fn clamp_mix(a: Rgba, b: Rgba) -> Rgba {
+        Gradient::new(&[a, b]).get(0.5)
+    }
+
+    #[test]
+    fn easing_and_steps() {
+        let colors = rgb();
+        let step = Gradient::new(&colors).easing(Easing::Step);
+        assert!(close(step.get(0.3), colors[0]));
+        assert!(close(step.get(0.6), colors[1]));
+
+        let ease = Gradient::new(&colors).easing(Easing::EaseInOut);
+        assert!(close(ease.get(0.25), Gradient::new(&colors).get(0.25)));
+
+        assert_eq!(Gradient::new(&colors).colors(7).len(), 7);
+        assert_eq!(Gradient::new(&[]).get(0.5).alpha, 0.0);
+    }
+}
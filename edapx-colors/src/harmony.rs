@@ -0,0 +1,64 @@
+// Classic color harmonies. The hue is rotated in OKLCh, lightness and chroma of the base color
+// are kept, so all the colors of a harmony look equally bright.
+use crate::oklab::Oklch;
+use nannou::color::Rgba;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Harmony {
+    // the base color and the opposite hue
+    Complementary,
+    // three hues 120 degrees apart
+    Triadic,
+    // the base color and its neighbours, 30 degrees apart
+    Analogous,
+    // the base color and the two hues next to its complementary
+    SplitComplementary,
+}
+
+impl Harmony {
+    // hue rotations in degrees, the first one is the base color
+    fn angles(&self) -> &'static [f32] {
+        match self {
+            Harmony::Complementary => &[0.0, 180.0],
+            Harmony::Triadic => &[0.0, 120.0, 240.0],
+            Harmony::Analogous => &[-30.0, 0.0, 30.0],
+            Harmony::SplitComplementary => &[0.0, 150.0, 210.0],
+        }
+    }
+}
+
+pub fn harmony(base: Rgba, kind: Harmony) -> Vec<Rgba> {
+    let lch = Oklch::from_rgba(base);
+    kind.angles()
+        .iter()
+        .map(|angle| {
+            if *angle == 0.0 {
+                base
+            } else {
+                lch.rotate(angle.to_radians()).to_rgba(base.alpha)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nannou::color::rgba;
+
+    #[test]
+    fn harmonies() {
+        let base = rgba(0.6, 0.45, 0.4, 0.6);
+        assert_eq!(harmony(base, Harmony::Complementary).len(), 2);
+        assert_eq!(harmony(base, Harmony::Triadic).len(), 3);
+        assert_eq!(harmony(base, Harmony::Analogous)[1], base);
+        assert_eq!(harmony(base, Harmony::SplitComplementary)[0], base);
+
+        let complementary = harmony(base, Harmony::Complementary)[1];
+        let (h0, h1) = (Oklch::from_rgba(base).h, Oklch::from_rgba(complementary).h);
+        let diff = (h1 - h0).rem_euclid(std::f32::consts::TAU);
+        // gamut clipping can move the hue a little
+        assert!((diff - std::f32::consts::PI).abs() < 0.2);
+        assert_eq!(complementary.alpha, 0.6);
+    }
+}
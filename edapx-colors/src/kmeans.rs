@@ -0,0 +1,145 @@
+// Palette extraction from an image with k-means clustering in OKLab.
+use crate::oklab::{srgb_to_linear, Oklab};
+use nannou::color::Rgba;
+use nannou::image::RgbaImage;
+
+// the image is sampled, clustering all the pixels of a photo is slow and not more precise
+const MAX_SAMPLES: usize = 20_000;
+const MAX_ITERATIONS: usize = 32;
+
+// The `k` dominant colors of the image, sorted from the most to the least common.
+// Transparent pixels are ignored. The result can have less than `k` colors if the image
+// has less than `k` different colors.
+pub fn extract_palette(image: &RgbaImage, k: usize) -> Vec<Rgba> {
+    let n_pixels = (image.width() * image.height()) as usize;
+    let stride = (n_pixels / MAX_SAMPLES).max(1);
+    let samples: Vec<Oklab> = image
+        .pixels()
+        .step_by(stride)
+        .filter(|p| p[3] > 0)
+        .map(|p| {
+            let c = |v: u8| srgb_to_linear(v as f32 / 255.0);
+            Oklab::from_linear_rgb(c(p[0]), c(p[1]), c(p[2]))
+        })
+        .collect();
+    kmeans(&samples, k)
+        .into_iter()
+        .map(|center| center.to_rgba(1.0))
+        .collect()
+}
+
+// The centers of the clusters, sorted by size.
+pub fn kmeans(samples: &[Oklab], k: usize) -> Vec<Oklab> {
+    if samples.is_empty() || k == 0 {
+        return Vec::new();
+    }
+    // deterministic initialization, so the same image always gives the same palette. The first
+    // center is the first sample, every other center is the sample farthest from the centers
+    // already chosen.
+    let mut centers = vec![samples[0]];
+    while centers.len() < k {
+        let (farthest, distance) = samples
+            .iter()
+            .map(|s| {
+                let c = &centers[closest_center(s, &centers)];
+                (*s, s.distance_squared(c))
+            })
+            .fold(
+                (samples[0], 0.0),
+                |max, d| if d.1 > max.1 { d } else { max },
+            );
+        // all the samples are already centers
+        if distance <= 0.0 {
+            break;
+        }
+        centers.push(farthest);
+    }
+
+    let mut assignment = vec![0; samples.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (sample, cluster) in samples.iter().zip(assignment.iter_mut()) {
+            let closest = closest_center(sample, &centers);
+            if closest != *cluster {
+                *cluster = closest;
+                changed = true;
+            }
+        }
+
+        let mut sums = vec![(0.0, 0.0, 0.0, 0usize); centers.len()];
+        for (sample, cluster) in samples.iter().zip(assignment.iter()) {
+            let sum = &mut sums[*cluster];
+            sum.0 += sample.l;
+            sum.1 += sample.a;
+            sum.2 += sample.b;
+            sum.3 += 1;
+        }
+        for (center, (l, a, b, n)) in centers.iter_mut().zip(sums) {
+            // an empty cluster keeps its center
+            if n > 0 {
+                let n = n as f32;
+                *center = Oklab::new(l / n, a / n, b / n);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut sizes = vec![0usize; centers.len()];
+    for cluster in assignment.iter() {
+        sizes[*cluster] += 1;
+    }
+    let mut clusters: Vec<(usize, Oklab)> = sizes
+        .into_iter()
+        .zip(centers)
+        .filter(|(size, _)| *size > 0)
+        .collect();
+    clusters.sort_by_key(|(size, _)| std::cmp::Reverse(*size));
+    clusters.into_iter().map(|(_, center)| center).collect()
+}
+
+fn closest_center(sample: &Oklab, centers: &[Oklab]) -> usize {
+    let mut closest = 0;
+    let mut min_distance = f32::MAX;
+    for (i, center) in centers.iter().enumerate() {
+        let distance = sample.distance_squared(center);
+        if distance < min_distance {
+            min_distance = distance;
+            closest = i;
+        }
+    }
+    closest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nannou::image::Rgba as Pixel;
+
+    #[test]
+    fn two_colors_image() {
+        // 3/4 red, 1/4 blue
+        let image = RgbaImage::from_fn(8, 8, |x, _| {
+            if x < 6 {
+                Pixel([255, 0, 0, 255])
+            } else {
+                Pixel([0, 0, 255, 255])
+            }
+        });
+        let colors = extract_palette(&image, 2);
+        assert_eq!(colors.len(), 2);
+        assert!(colors[0].red > 0.99 && colors[0].blue < 0.01);
+        assert!(colors[1].blue > 0.99 && colors[1].red < 0.01);
+
+        // there are only 2 colors, the other clusters are empty
+        assert!(extract_palette(&image, 5).len() <= 2);
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(kmeans(&[], 3).is_empty());
+        let transparent = RgbaImage::from_pixel(4, 4, Pixel([255, 255, 255, 0]));
+        assert!(extract_palette(&transparent, 3).is_empty());
+    }
+}
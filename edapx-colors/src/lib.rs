@@ -1,140 +1,433 @@
 use nannou::color::rgba;
-#[warn(unused_assignments)]
 use nannou::color::Rgba;
+use std::path::Path;
 
-const COLOR_PER_SCHEME: usize = 5;
+mod accessibility;
+mod ase;
+mod colormap;
+#[cfg(feature = "egui")]
+mod editor;
+mod error;
+mod gradient;
+mod harmony;
+mod kmeans;
+mod loader;
+mod oklab;
 
+pub use accessibility::{
+    contrast_ratio, relative_luminance, simulate, Confusion, Deficiency, Report, MIN_CONTRAST,
+    MIN_DISTANCE,
+};
+pub use colormap::Colormap;
+#[cfg(feature = "egui")]
+pub use editor::{show_report, PaletteEditor};
+pub use error::PaletteError;
+pub use gradient::{Easing, Gradient, Interpolation, Spread};
+pub use harmony::{harmony, Harmony};
+pub use kmeans::{extract_palette, kmeans};
+pub use loader::{parse_hex, to_hex, PaletteFormat};
+pub use oklab::{lightness_ramp, linear_to_srgb, ramp, srgb_to_linear, Oklab, Oklch};
+
+// A named list of colors, of any length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scheme {
+    pub name: String,
+    pub colors: Vec<Rgba>,
+}
+
+impl Scheme {
+    pub fn new(name: &str, colors: Vec<Rgba>) -> Self {
+        Scheme {
+            name: name.to_string(),
+            colors,
+        }
+    }
+
+    // the base color followed by the colors of the harmony
+    pub fn from_harmony(name: &str, base: Rgba, kind: Harmony) -> Self {
+        Scheme::new(name, harmony(base, kind))
+    }
+
+    // `steps` colors between `from` and `to`, evenly spaced in OKLab
+    pub fn from_ramp(name: &str, from: Rgba, to: Rgba, steps: usize) -> Self {
+        Scheme::new(name, ramp(from, to, steps))
+    }
+
+    // The `k` dominant colors of an image, see `extract_palette`.
+    pub fn from_image<P: AsRef<Path>>(name: &str, path: P, k: usize) -> Result<Self, PaletteError> {
+        let image = nannou::image::open(path)
+            .map_err(|err| PaletteError::Image(err.to_string()))?
+            .to_rgba8();
+        Ok(Scheme::new(name, extract_palette(&image, k)))
+    }
+
+    // The scheme as seen with a color vision deficiency.
+    pub fn simulate(&self, deficiency: Deficiency) -> Self {
+        let colors = self
+            .colors
+            .iter()
+            .map(|c| simulate(*c, deficiency))
+            .collect();
+        Scheme::new(&self.name, colors)
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+}
+
+// A collection of schemes. `Palette::new` contains the built-in schemes, more schemes can be
+// loaded from JSON, TOML, GIMP (.gpl), Adobe (.ase) files or from a list of hex colors.
+#[derive(Debug, Clone)]
 pub struct Palette {
-    pub colors: [Rgba; 30],
-    pub len: usize,
+    pub schemes: Vec<Scheme>,
+    // Preview of a color vision deficiency, when set `get_color` and `get_gradient` return the
+    // simulated colors. The schemes are not changed.
+    pub vision: Option<Deficiency>,
+    // Multiplies the alpha of the colors returned by `get_color` and `get_gradient`, the alpha
+    // of each color in the schemes is kept.
+    pub opacity: f32,
 }
 
 impl Palette {
     pub fn new() -> Self {
-        let colors: [Rgba; 30] = [
-            // // LimePalette
-            // rgba(0.733, 1.0, 0.31, 1.0),    //lime
-            // rgba(1.0, 0.353, 0.208, 1.0),   //red
-            // rgba(0.086, 0.29, 0.8, 1.0),    //blu
-            // rgba(0.698, 0.188, 0.075, 1.0), //red hard
-            // rgba(0.098, 0.0, 0.749, 1.0),   //blu hard
-            // // greenAndRosePal
-            // rgba(1.0, 0.592, 0.706, 1.0),   //rose
-            // rgba(0.898, 0.341, 0.878, 1.0), //lilla
-            // rgba(0.902, 0.494, 0.063, 1.0), // orangeexpected unit type `()`
-
-            // // primoPal
-            // rgba(0.055, 0.8, 0.812, 1.0),   // azul
-            // rgba(0.702, 0.839, 0.38, 1.0),  // verde vomito
-            // rgba(0.945, 0.757, 0.137, 1.0), //giallo
-            // rgba(0.902, 0.259, 0.141, 1.0), //ocra CHANGE
-            // rgba(0.525, 0.094, 0.949, 1.0), //viola
-            // // bigBubblePal
-            // rgba(0.965, 0.914, 0.396, 1.0), //yellow
-            // rgba(0.333, 1.0, 0.235, 1.0),   //green
-            // rgba(0.078, 0.643, 0.8, 1.0),   //azul
-            // rgba(0.706, 0.0, 0.514, 1.0),   //violetwetr
-            // rgba(0.0, 0.0, 0.0, 1.0),       //black
-            // //Primo2
-            // rgba(0.687, 0.592, 0.231, 1.0), //oliva
-            // rgba(0.098, 0.980, 0.898, 1.0), // azzurro
-            // rgba(0.980, 0.788, 0.0, 1.0),   //giallo
-            // rgba(0.96, 0.478, 0.913, 1.0),  //lilla
-            // rgba(0.678, 0.0, 0.607, 1.0),   // viola
-            // //Nes Variation 2
-            // rgba(0.921568627, 0.960784314, 0.337254902, 1.0), // giallo limone
-            // rgba(0.121568627, 0.760784314, 0.42745098, 1.0),  // verde chiaro
-            // rgba(1.0, 0.054901961, 0.152941176, 1.0),         // rosso carminio
-            // rgba(0.745098039, 0.8, 0.0, 1.0),                 // verde oliva chiaro
-            // rgba(0.0, 0.521568627, 0.980392157, 1.0),         // azzurro
-
-
-
-
-            // LimePalette
-            rgba(0.733, 1.0, 0.31, 0.6),    //lime
-            rgba(1.0, 0.353, 0.208, 0.6),   //red
-            rgba(0.086, 0.29, 0.8, 0.6),    //blu
-            rgba(0.698, 0.188, 0.075, 0.6), //red hard
-            rgba(0.098, 0.0, 0.749, 0.6),   //blu hard
-            // greenAndRosePal
-            rgba(1.0, 0.592, 0.706, 0.6),   //rose
-            rgba(0.898, 0.341, 0.878, 0.6), //lilla
-            rgba(0.902, 0.494, 0.063, 0.6), // orange
-            rgba(0.0, 0.498, 0.353, 0.6),   //green
-            rgba(0.098, 0.0, 0.749, 0.6),   //blu
-            // nes variation
-            // rgba(0.055, 0.8, 0.812, 0.6),   //azul
-            // rgba(0.965, 0.914, 0.396, 0.6), //yellow
-            // rgba(0.902, 0.659, 0.141, 0.6), //ocra
-            // rgba(0.953, 0.447, 0.035, 0.6), //arancione
-            // rgba(1.0, 0.267, 0.016, 0.6),   // red
-
-            // primoPal
-            rgba(0.055, 0.8, 0.812, 0.6),   // azul
-            rgba(0.702, 0.839, 0.38, 0.6),  // verde vomito
-            rgba(0.945, 0.757, 0.137, 0.6), //giallo
-            rgba(0.902, 0.259, 0.141, 0.6), //ocra CHANGE
-            rgba(0.525, 0.094, 0.949, 0.6), //viola
-            // bigBubblePal
-            rgba(0.965, 0.914, 0.396, 0.6), //yellow
-            rgba(0.333, 1.0, 0.235, 0.6),   //green
-            rgba(0.078, 0.643, 0.8, 0.6),   //azul
-            rgba(0.706, 0.0, 0.514, 0.6),   //violetwetr
-            rgba(0.0, 0.0, 0.0, 0.6),       //black
-            //Primo2
-            rgba(0.687, 0.592, 0.231, 0.6), //oliva
-            rgba(0.098, 0.980, 0.898, 0.6), // azzurro
-            rgba(0.980, 0.788, 0.0, 0.6),   //giallo
-            rgba(0.96, 0.478, 0.913, 0.6),  //lilla
-            rgba(0.678, 0.0, 0.607, 0.6),   // viola
-            //Nes Variation 2
-            rgba(0.921568627, 0.960784314, 0.337254902, 0.6), // giallo limone
-            rgba(0.121568627, 0.760784314, 0.42745098, 0.6),  // verde chiaro
-            rgba(1.0, 0.054901961, 0.152941176, 0.6),         // rosso carminio
-            rgba(0.745098039, 0.8, 0.0, 0.6),                 // verde oliva chiaro
-            rgba(0.0, 0.521568627, 0.980392157, 0.6),         // azzurro
-
-
-        ];
-
-        let len = colors.len();
         Palette {
-            colors: colors,
-            len,
+            schemes: default_schemes(),
+            vision: None,
+            opacity: 1.0,
         }
     }
 
-    pub fn set_alpha(&mut self, a: f32){
-        for c in self.colors.iter_mut(){
-            c.alpha = a;
+    pub fn empty() -> Self {
+        Palette {
+            schemes: Vec::new(),
+            vision: None,
+            opacity: 1.0,
         }
     }
 
+    // The format is deduced from the extension of the file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PaletteError> {
+        let path = path.as_ref();
+        let format = PaletteFormat::from_path(path)
+            .ok_or_else(|| PaletteError::UnsupportedFormat(path.display().to_string()))?;
+        let bytes = std::fs::read(path)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Palette::from_bytes(&bytes, format, &name)
+    }
+
+    // `name` is given to the schemes that have no name in the file.
+    pub fn from_bytes(
+        bytes: &[u8],
+        format: PaletteFormat,
+        name: &str,
+    ) -> Result<Self, PaletteError> {
+        Ok(Palette {
+            schemes: loader::parse(bytes, format, name)?,
+            vision: None,
+            opacity: 1.0,
+        })
+    }
+
+    // Writes all the schemes to a JSON, TOML or hex list file, depending on the extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), PaletteError> {
+        let path = path.as_ref();
+        let format = PaletteFormat::from_path(path)
+            .ok_or_else(|| PaletteError::UnsupportedFormat(path.display().to_string()))?;
+        let text = loader::write(&self.schemes, format)?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    // Adds the schemes of a file to the palette.
+    pub fn load_schemes<P: AsRef<Path>>(&mut self, path: P) -> Result<(), PaletteError> {
+        let other = Palette::load(path)?;
+        self.schemes.extend(other.schemes);
+        Ok(())
+    }
+
+    pub fn add_scheme(&mut self, scheme: Scheme) {
+        self.schemes.push(scheme);
+    }
+
+    // A gradient through all the colors of a scheme, see `get_scheme`.
+    pub fn get_gradient(&self, scheme_id: usize) -> Gradient {
+        let colors: Vec<Rgba> = self
+            .get_scheme(scheme_id)
+            .iter()
+            .map(|c| self.preview(*c))
+            .collect();
+        Gradient::new(&colors)
+    }
+
+    // number of schemes
+    pub fn len(&self) -> usize {
+        self.schemes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schemes.is_empty()
+    }
+
+    pub fn scheme(&self, id: usize) -> Option<&Scheme> {
+        self.schemes.get(id)
+    }
+
+    pub fn scheme_by_name(&self, name: &str) -> Option<&Scheme> {
+        self.schemes.iter().find(|scheme| scheme.name == name)
+    }
+
+    pub fn scheme_id(&self, name: &str) -> Option<usize> {
+        self.schemes.iter().position(|scheme| scheme.name == name)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Scheme> {
+        self.schemes.iter()
+    }
+
+    // The colors of a scheme. The id wraps around, so any value coming from a slider is valid.
+    // An empty palette returns no colors. The colors are returned as they are, without `vision`.
     pub fn get_scheme(&self, id: usize) -> &[Rgba] {
-        let mut index = id;
-        if index >= (self.len / COLOR_PER_SCHEME) {
-            index = 0;
+        if self.schemes.is_empty() {
+            return &[];
         }
-        index = id * COLOR_PER_SCHEME;
-        let to = index + COLOR_PER_SCHEME - 1;
-        &self.colors[index..=to]
+        &self.schemes[id % self.schemes.len()].colors
+    }
+
+    // The position wraps around the scheme, transparent black if the scheme is empty.
+    pub fn get_color(&self, scheme_id: usize, position: usize) -> Rgba {
+        let scheme = self.get_scheme(scheme_id);
+        if scheme.is_empty() {
+            return rgba(0.0, 0.0, 0.0, 0.0);
+        }
+        self.preview(scheme[position % scheme.len()])
+    }
+
+    // The color as seen with the deficiency in `vision`, with `opacity` applied.
+    pub fn preview(&self, color: Rgba) -> Rgba {
+        let mut color = match self.vision {
+            Some(deficiency) => simulate(color, deficiency),
+            None => color,
+        };
+        color.alpha *= self.opacity;
+        color
+    }
+
+    // Contrast and color vision report of a scheme, the background is `get_fifth` and the
+    // other colors are the foreground.
+    pub fn report(&self, scheme_id: usize, offset: usize) -> Option<Report> {
+        if self.schemes.is_empty() {
+            return None;
+        }
+        let scheme = &self.schemes[scheme_id % self.schemes.len()];
+        Some(Report::new(scheme, 4 + offset))
+    }
+
+    // The reports of all the schemes.
+    pub fn reports(&self, offset: usize) -> Vec<Report> {
+        self.schemes
+            .iter()
+            .map(|scheme| Report::new(scheme, 4 + offset))
+            .collect()
     }
 
     pub fn get_first(&self, scheme_id: usize, offset: usize) -> Rgba {
-        return self.get_scheme(scheme_id)[(0 + offset) % COLOR_PER_SCHEME];
+        self.get_color(scheme_id, offset)
     }
     pub fn get_second(&self, scheme_id: usize, offset: usize) -> Rgba {
-        return self.get_scheme(scheme_id)[(1 + offset) % COLOR_PER_SCHEME];
+        self.get_color(scheme_id, 1 + offset)
     }
     pub fn get_third(&self, scheme_id: usize, offset: usize) -> Rgba {
-        return self.get_scheme(scheme_id)[(2 + offset) % COLOR_PER_SCHEME];
+        self.get_color(scheme_id, 2 + offset)
     }
     pub fn get_fourth(&self, scheme_id: usize, offset: usize) -> Rgba {
-        return self.get_scheme(scheme_id)[(3 + offset) % COLOR_PER_SCHEME];
+        self.get_color(scheme_id, 3 + offset)
     }
     pub fn get_fifth(&self, scheme_id: usize, offset: usize) -> Rgba {
-        return self.get_scheme(scheme_id)[(4 + offset) % COLOR_PER_SCHEME];
+        self.get_color(scheme_id, 4 + offset)
+    }
+}
+
+impl<'a> IntoIterator for &'a Palette {
+    type Item = &'a Scheme;
+    type IntoIter = std::slice::Iter<'a, Scheme>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.schemes.iter()
     }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new()
+    }
+}
+
+// The built-in schemes. Older schemes are in palettes/archive.toml, add them with
+// `palette.load_schemes("edapx-colors/palettes/archive.toml")`.
+fn default_schemes() -> Vec<Scheme> {
+    vec![
+        Scheme::new(
+            "lime",
+            vec![
+                rgba(0.733, 1.0, 0.31, 0.6),    //lime
+                rgba(1.0, 0.353, 0.208, 0.6),   //red
+                rgba(0.086, 0.29, 0.8, 0.6),    //blu
+                rgba(0.698, 0.188, 0.075, 0.6), //red hard
+                rgba(0.098, 0.0, 0.749, 0.6),   //blu hard
+            ],
+        ),
+        Scheme::new(
+            "green and rose",
+            vec![
+                rgba(1.0, 0.592, 0.706, 0.6),   //rose
+                rgba(0.898, 0.341, 0.878, 0.6), //lilla
+                rgba(0.902, 0.494, 0.063, 0.6), // orange
+                rgba(0.0, 0.498, 0.353, 0.6),   //green
+                rgba(0.098, 0.0, 0.749, 0.6),   //blu
+            ],
+        ),
+        Scheme::new(
+            "primo",
+            vec![
+                rgba(0.055, 0.8, 0.812, 0.6),   // azul
+                rgba(0.702, 0.839, 0.38, 0.6),  // verde vomito
+                rgba(0.945, 0.757, 0.137, 0.6), //giallo
+                rgba(0.902, 0.259, 0.141, 0.6), //ocra CHANGE
+                rgba(0.525, 0.094, 0.949, 0.6), //viola
+            ],
+        ),
+        Scheme::new(
+            "big bubble",
+            vec![
+                rgba(0.965, 0.914, 0.396, 0.6), //yellow
+                rgba(0.333, 1.0, 0.235, 0.6),   //green
+                rgba(0.078, 0.643, 0.8, 0.6),   //azul
+                rgba(0.706, 0.0, 0.514, 0.6),   //violetwetr
+                rgba(0.0, 0.0, 0.0, 0.6),       //black
+            ],
+        ),
+        Scheme::new(
+            "primo 2",
+            vec![
+                rgba(0.687, 0.592, 0.231, 0.6), //oliva
+                rgba(0.098, 0.980, 0.898, 0.6), // azzurro
+                rgba(0.980, 0.788, 0.0, 0.6),   //giallo
+                rgba(0.96, 0.478, 0.913, 0.6),  //lilla
+                rgba(0.678, 0.0, 0.607, 0.6),   // viola
+            ],
+        ),
+        Scheme::new(
+            "nes variation 2",
+            vec![
+                rgba(0.92156863, 0.9607843, 0.3372549, 0.6), // giallo limone
+                rgba(0.12156863, 0.7607843, 0.42745098, 0.6), // verde chiaro
+                rgba(1.0, 0.05490196, 0.15294118, 0.6),      // rosso carminio
+                rgba(0.74509805, 0.8, 0.0, 0.6),             // verde oliva chiaro
+                rgba(0.0, 0.52156866, 0.98039216, 0.6),      // azzurro
+            ],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn scheme_id_wraps_around() {
+        let palette = Palette::new();
+        let n = palette.len();
+        assert_eq!(
+            palette.get_scheme(n - 1),
+            &palette.schemes[n - 1].colors[..]
+        );
+        assert_eq!(palette.get_scheme(n), palette.get_scheme(0));
+        assert_eq!(palette.get_scheme(n + 1), palette.get_scheme(1));
+        assert_eq!(palette.get_scheme(usize::MAX).len(), 5);
+    }
+
+    #[test]
+    fn lookup() {
+        let palette = Palette::new();
+        assert!(palette.scheme(palette.len() - 1).is_some());
+        assert!(palette.scheme(palette.len()).is_none());
+        assert_eq!(palette.scheme_by_name("primo").unwrap().name, "primo");
+        assert_eq!(palette.scheme_id("primo"), Some(2));
+        assert!(palette.scheme_by_name("does not exist").is_none());
+        assert_eq!(palette.iter().count(), palette.len());
+        assert_eq!((&palette).into_iter().count(), palette.len());
+    }
+
+    #[test]
+    fn schemes_of_any_size() {
+        let mut palette = Palette::empty();
+        assert!(palette.get_scheme(3).is_empty());
+        assert_eq!(palette.get_first(3, 0).alpha, 0.0);
+
+        let red = rgba(1.0, 0.0, 0.0, 1.0);
+        let blue = rgba(0.0, 0.0, 1.0, 1.0);
+        palette.add_scheme(Scheme::new("two", vec![red, blue]));
+        palette.add_scheme(Scheme::new("empty", Vec::new()));
+        assert_eq!(palette.get_first(0, 0), red);
+        assert_eq!(palette.get_second(0, 0), blue);
+        assert_eq!(palette.get_third(0, 0), red);
+        assert_eq!(palette.get_fifth(0, 1), blue);
+        assert_eq!(palette.get_first(1, 0).alpha, 0.0);
+        assert_eq!(palette.get_first(2, 0), red);
+    }
+
+    #[test]
+    fn vision_preview_and_reports() {
+        let mut palette = Palette::new();
+        let color = palette.get_first(0, 0);
+        palette.vision = Some(Deficiency::Protanopia);
+        assert_eq!(
+            palette.get_first(0, 0),
+            simulate(color, Deficiency::Protanopia)
+        );
+        // the schemes are not changed
+        assert_eq!(palette.get_scheme(0)[0], color);
+
+        assert_eq!(palette.reports(0).len(), palette.len());
+        let report = palette.report(0, 0).unwrap();
+        assert_eq!(report.background, 4);
+        assert_eq!(report.contrast.len(), 4);
+        assert!(Palette::empty().report(0, 0).is_none());
+    }
+
+    #[test]
+    fn opacity_keeps_the_alpha_of_each_color() {
+        let mut palette = Palette::empty();
+        let red = rgba(1.0, 0.0, 0.0, 1.0);
+        let glass = rgba(0.0, 0.0, 1.0, 0.5);
+        palette.add_scheme(Scheme::new("two", vec![red, glass]));
+        palette.opacity = 0.5;
+        assert_eq!(palette.get_first(0, 0).alpha, 0.5);
+        assert_eq!(palette.get_second(0, 0).alpha, 0.25);
+        palette.opacity = 1.0;
+        assert_eq!(palette.get_second(0, 0), glass);
+        assert_eq!(palette.get_scheme(0), &[red, glass][..]);
+    }
+    #[test]
+    fn archive_adds_the_older_schemes() {
+        let mut palette = Palette::new();
+        let built_in = palette.len();
+        palette
+            .load_schemes(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/palettes/archive.toml"
+            ))
+            .unwrap();
+        assert_eq!(palette.len(), built_in + 3);
+        assert_eq!(palette.schemes[built_in].name, "nes variation");
+        assert_eq!(palette.schemes[built_in + 2].colors.len(), 3);
+    }
 }
@@ -0,0 +1,373 @@
+use crate::ase;
+use crate::error::PaletteError;
+use crate::Scheme;
+use nannou::color::{rgba, Rgba};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PaletteFormat {
+    Json,
+    Toml,
+    // GIMP palette, .gpl
+    Gpl,
+    // Adobe Swatch Exchange, .ase
+    Ase,
+    // plain list of hex colors, .hex or .txt
+    Hex,
+}
+
+impl PaletteFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "json" => Some(PaletteFormat::Json),
+            "toml" => Some(PaletteFormat::Toml),
+            "gpl" => Some(PaletteFormat::Gpl),
+            "ase" => Some(PaletteFormat::Ase),
+            "hex" | "txt" => Some(PaletteFormat::Hex),
+            _ => None,
+        }
+    }
+}
+
+// The schemes contained in a file. `name` is used for the schemes that have no name in the file,
+// usually it is the name of the file.
+pub fn parse(bytes: &[u8], format: PaletteFormat, name: &str) -> Result<Vec<Scheme>, PaletteError> {
+    match format {
+        PaletteFormat::Ase => ase::parse(bytes, name),
+        _ => {
+            let text =
+                std::str::from_utf8(bytes).map_err(|err| PaletteError::Parse(err.to_string()))?;
+            match format {
+                PaletteFormat::Json => parse_json(text),
+                PaletteFormat::Toml => parse_toml(text),
+                PaletteFormat::Gpl => parse_gpl(text, name),
+                _ => parse_hex_list(text, name),
+            }
+        }
+    }
+}
+
+// JSON and TOML files share the same structure:
+//
+// [[schemes]]
+// name = "lime"
+// colors = ["#bbff4f", "ff5a35", [0.086, 0.29, 0.8], [0.698, 0.188, 0.075, 0.6]]
+//
+// a color is either an hex string or a list of 3 or 4 components between 0 and 1.
+#[derive(Deserialize, Serialize)]
+struct PaletteFile {
+    schemes: Vec<SchemeFile>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SchemeFile {
+    name: String,
+    colors: Vec<ColorFile>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum ColorFile {
+    Hex(String),
+    Components(Vec<f32>),
+}
+
+impl ColorFile {
+    fn to_rgba(&self) -> Result<Rgba, PaletteError> {
+        match self {
+            ColorFile::Hex(hex) => parse_hex(hex)
+                .ok_or_else(|| PaletteError::Parse(format!("invalid hex color {}", hex))),
+            ColorFile::Components(c) => match c.len() {
+                3 => Ok(rgba(c[0], c[1], c[2], 1.0)),
+                4 => Ok(rgba(c[0], c[1], c[2], c[3])),
+                n => Err(PaletteError::Parse(format!(
+                    "a color needs 3 or 4 components, found {}",
+                    n
+                ))),
+            },
+        }
+    }
+}
+
+fn from_palette_file(file: PaletteFile) -> Result<Vec<Scheme>, PaletteError> {
+    file.schemes
+        .into_iter()
+        .map(|scheme| {
+            let colors = scheme
+                .colors
+                .iter()
+                .map(|c| c.to_rgba())
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Scheme::new(&scheme.name, colors))
+        })
+        .collect()
+}
+
+pub fn parse_json(text: &str) -> Result<Vec<Scheme>, PaletteError> {
+    from_palette_file(serde_json::from_str(text)?)
+}
+
+pub fn parse_toml(text: &str) -> Result<Vec<Scheme>, PaletteError> {
+    from_palette_file(toml::from_str(text)?)
+}
+
+fn to_palette_file(schemes: &[Scheme]) -> PaletteFile {
+    PaletteFile {
+        schemes: schemes
+            .iter()
+            .map(|scheme| SchemeFile {
+                name: scheme.name.clone(),
+                colors: scheme
+                    .colors
+                    .iter()
+                    .map(|c| ColorFile::Hex(to_hex(*c)))
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+// The schemes written in a format that `parse` can read back. Only JSON, TOML and hex lists
+// can hold more than one scheme and the alpha of the colors.
+pub fn write(schemes: &[Scheme], format: PaletteFormat) -> Result<String, PaletteError> {
+    match format {
+        PaletteFormat::Json => serde_json::to_string_pretty(&to_palette_file(schemes))
+            .map_err(|err| PaletteError::Parse(err.to_string())),
+        PaletteFormat::Toml => toml::to_string(&to_palette_file(schemes))
+            .map_err(|err| PaletteError::Parse(err.to_string())),
+        PaletteFormat::Hex => {
+            let mut text = String::new();
+            for scheme in schemes {
+                text.push_str(&format!("{}:\n", scheme.name));
+                let colors: Vec<String> = scheme.colors.iter().map(|c| to_hex(*c)).collect();
+                text.push_str(&colors.join(" "));
+                text.push('\n');
+            }
+            Ok(text)
+        }
+        _ => Err(PaletteError::UnsupportedFormat(format!("{:?}", format))),
+    }
+}
+
+// GIMP palette, one scheme per file:
+//
+// GIMP Palette
+// Name: lime
+// Columns: 5
+// #
+// 187 255  79	lime
+// 255  90  53	red
+pub fn parse_gpl(text: &str, name: &str) -> Result<Vec<Scheme>, PaletteError> {
+    let mut lines = text.lines();
+    match lines.next() {
+        Some(header) if header.trim() == "GIMP Palette" => (),
+        _ => {
+            return Err(PaletteError::Parse(
+                "missing GIMP Palette header".to_string(),
+            ))
+        }
+    }
+    let mut scheme_name = name.to_string();
+    let mut colors = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+            continue;
+        }
+        if let Some(n) = line.strip_prefix("Name:") {
+            scheme_name = n.trim().to_string();
+            continue;
+        }
+        let components: Vec<&str> = line.split_whitespace().take(3).collect();
+        let values = components
+            .iter()
+            .map(|c| c.parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| PaletteError::Parse(format!("invalid GIMP color {}", line)))?;
+        if values.len() != 3 {
+            return Err(PaletteError::Parse(format!("invalid GIMP color {}", line)));
+        }
+        colors.push(rgba(
+            values[0] as f32 / 255.0,
+            values[1] as f32 / 255.0,
+            values[2] as f32 / 255.0,
+            1.0,
+        ));
+    }
+    Ok(vec![Scheme::new(&scheme_name, colors)])
+}
+
+// A list of hex colors, separated by spaces, commas or new lines. A line ending with ':' starts
+// a new scheme with that name, an empty line starts a new unnamed scheme. Lines starting with
+// ';' or '//' are comments.
+//
+// lime:
+// #bbff4f #ff5a35 #164acc
+pub fn parse_hex_list(text: &str, name: &str) -> Result<Vec<Scheme>, PaletteError> {
+    let mut schemes: Vec<Scheme> = Vec::new();
+    let mut current = Scheme::new(name, Vec::new());
+    let mut n_unnamed = 1;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with(';') || line.starts_with("//") {
+            continue;
+        }
+        if line.is_empty() || line.ends_with(':') {
+            if !current.colors.is_empty() {
+                schemes.push(current);
+                n_unnamed += 1;
+            }
+            let scheme_name = if line.is_empty() {
+                format!("{} {}", name, n_unnamed)
+            } else {
+                line.trim_end_matches(':').trim().to_string()
+            };
+            current = Scheme::new(&scheme_name, Vec::new());
+            continue;
+        }
+        for hex in line.split(|c: char| c.is_whitespace() || c == ',') {
+            if hex.is_empty() {
+                continue;
+            }
+            let color = parse_hex(hex)
+                .ok_or_else(|| PaletteError::Parse(format!("invalid hex color {}", hex)))?;
+            current.colors.push(color);
+        }
+    }
+    if !current.colors.is_empty() {
+        schemes.push(current);
+    }
+    Ok(schemes)
+}
+
+// "#rgb", "#rrggbb" or "#rrggbbaa", the '#' is optional
+pub fn parse_hex(hex: &str) -> Option<Rgba> {
+    let hex = hex.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let (r, g, b, a) = match hex.len() {
+        3 => {
+            let short = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+            (short(0)?, short(1)?, short(2)?, 255)
+        }
+        6 => (component(0)?, component(2)?, component(4)?, 255),
+        8 => (component(0)?, component(2)?, component(4)?, component(6)?),
+        _ => return None,
+    };
+    Some(rgba(
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0,
+        a as f32 / 255.0,
+    ))
+}
+
+// "#rrggbb", or "#rrggbbaa" when the color is not opaque
+pub fn to_hex(color: Rgba) -> String {
+    let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let rgb = format!(
+        "#{:02x}{:02x}{:02x}",
+        byte(color.red),
+        byte(color.green),
+        byte(color.blue)
+    );
+    if byte(color.alpha) == 255 {
+        rgb
+    } else {
+        format!("{}{:02x}", rgb, byte(color.alpha))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color(color: Rgba, expected: (f32, f32, f32, f32)) {
+        let eps = 1.0 / 255.0;
+        assert!((color.red - expected.0).abs() < eps);
+        assert!((color.green - expected.1).abs() < eps);
+        assert!((color.blue - expected.2).abs() < eps);
+        assert!((color.alpha - expected.3).abs() < eps);
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_color(parse_hex("#ff0000").unwrap(), (1.0, 0.0, 0.0, 1.0));
+        assert_color(parse_hex("00ff0080").unwrap(), (0.0, 1.0, 0.0, 0.5));
+        assert_color(parse_hex("#fff").unwrap(), (1.0, 1.0, 1.0, 1.0));
+        assert!(parse_hex("#ff00").is_none());
+        assert!(parse_hex("#gg0000").is_none());
+    }
+
+    #[test]
+    fn toml_and_json() {
+        let toml = r##"
+            [[schemes]]
+            name = "lime"
+            colors = ["#bbff4f", [0.086, 0.29, 0.8], [1.0, 0.0, 0.0, 0.6]]
+        "##;
+        let schemes = parse_toml(toml).unwrap();
+        assert_eq!(schemes.len(), 1);
+        assert_eq!(schemes[0].name, "lime");
+        assert_eq!(schemes[0].colors.len(), 3);
+        assert_color(schemes[0].colors[2], (1.0, 0.0, 0.0, 0.6));
+
+        let json = r##"{"schemes": [
+            {"name": "a", "colors": ["#000000"]},
+            {"name": "b", "colors": ["#ffffff", "#ff0000"]}
+        ]}"##;
+        let schemes = parse_json(json).unwrap();
+        assert_eq!(schemes.len(), 2);
+        assert_eq!(schemes[1].name, "b");
+        assert_eq!(schemes[1].colors.len(), 2);
+
+        let wrong = r#"{"schemes": [{"name": "a", "colors": [[1.0, 0.0]]}]}"#;
+        assert!(parse_json(wrong).is_err());
+    }
+
+    #[test]
+    fn write_and_read_back() {
+        let schemes = vec![
+            Scheme::new(
+                "a",
+                vec![rgba(1.0, 0.0, 0.0, 1.0), rgba(0.0, 0.0, 1.0, 0.6)],
+            ),
+            Scheme::new("b", vec![rgba(0.2, 0.4, 0.6, 1.0)]),
+        ];
+        for format in [PaletteFormat::Json, PaletteFormat::Toml, PaletteFormat::Hex].iter() {
+            let text = write(&schemes, *format).unwrap();
+            let back = parse(text.as_bytes(), *format, "file").unwrap();
+            assert_eq!(back.len(), 2);
+            assert_eq!(back[0].name, "a");
+            assert_eq!(back[1].name, "b");
+            assert_color(back[0].colors[1], (0.0, 0.0, 1.0, 0.6));
+        }
+        assert!(write(&schemes, PaletteFormat::Ase).is_err());
+        assert_eq!(to_hex(rgba(1.0, 0.0, 0.0, 1.0)), "#ff0000");
+    }
+
+    #[test]
+    fn gimp_palette() {
+        let gpl = "GIMP Palette\nName: test\nColumns: 2\n#\n255   0   0\tred\n  0 255   0\n";
+        let schemes = parse_gpl(gpl, "file").unwrap();
+        assert_eq!(schemes[0].name, "test");
+        assert_eq!(schemes[0].colors.len(), 2);
+        assert_color(schemes[0].colors[1], (0.0, 1.0, 0.0, 1.0));
+        assert!(parse_gpl("255 0 0", "file").is_err());
+    }
+
+    #[test]
+    fn hex_list() {
+        let list = "// comment\nfirst:\n#ff0000, #00ff00\n#0000ff\n\n#ffffff #000000\n";
+        let schemes = parse_hex_list(list, "list").unwrap();
+        assert_eq!(schemes.len(), 2);
+        assert_eq!(schemes[0].name, "first");
+        assert_eq!(schemes[0].colors.len(), 3);
+        assert_eq!(schemes[1].name, "list 2");
+        assert_eq!(schemes[1].colors.len(), 2);
+    }
+}
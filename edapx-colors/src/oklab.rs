@@ -0,0 +1,222 @@
+// OKLab and OKLCh, perceptual color spaces by Björn Ottosson, see
+// https://bottosson.github.io/posts/oklab/
+// Equal distances in OKLab look like equal color differences, interpolating in it does not give
+// the muddy or too bright midpoints of RGB and HSL.
+use nannou::color::{rgba, Rgba};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+// The polar form of OKLab, the hue is in radians.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl Oklab {
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        Oklab { l, a, b }
+    }
+
+    pub fn from_linear_rgb(r: f32, g: f32, b: f32) -> Self {
+        let l = 0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b;
+        let m = 0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b;
+        let s = 0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b;
+        let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+        Oklab {
+            l: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        }
+    }
+
+    pub fn to_linear_rgb(&self) -> (f32, f32, f32) {
+        let l = self.l + 0.396_337_78 * self.a + 0.215_803_76 * self.b;
+        let m = self.l - 0.105_561_346 * self.a - 0.063_854_17 * self.b;
+        let s = self.l - 0.089_484_18 * self.a - 1.291_485_5 * self.b;
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+        (
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        )
+    }
+
+    pub fn from_rgba(color: Rgba) -> Self {
+        Oklab::from_linear_rgb(
+            srgb_to_linear(color.red),
+            srgb_to_linear(color.green),
+            srgb_to_linear(color.blue),
+        )
+    }
+
+    // Colors outside of the sRGB gamut are clipped.
+    pub fn to_rgba(&self, alpha: f32) -> Rgba {
+        let (r, g, b) = self.to_linear_rgb();
+        let encode = |c: f32| linear_to_srgb(c.clamp(0.0, 1.0));
+        rgba(encode(r), encode(g), encode(b), alpha)
+    }
+
+    pub fn mix(&self, other: &Oklab, t: f32) -> Oklab {
+        Oklab {
+            l: self.l + (other.l - self.l) * t,
+            a: self.a + (other.a - self.a) * t,
+            b: self.b + (other.b - self.b) * t,
+        }
+    }
+
+    pub fn distance_squared(&self, other: &Oklab) -> f32 {
+        let (dl, da, db) = (self.l - other.l, self.a - other.a, self.b - other.b);
+        dl * dl + da * da + db * db
+    }
+}
+
+impl Oklch {
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        Oklch { l, c, h }
+    }
+
+    pub fn from_rgba(color: Rgba) -> Self {
+        Oklch::from(Oklab::from_rgba(color))
+    }
+
+    pub fn to_rgba(&self, alpha: f32) -> Rgba {
+        Oklab::from(*self).to_rgba(alpha)
+    }
+
+    // rotates the hue, the angle is in radians
+    pub fn rotate(&self, angle: f32) -> Oklch {
+        Oklch {
+            h: (self.h + angle).rem_euclid(std::f32::consts::TAU),
+            ..*self
+        }
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(lab: Oklab) -> Self {
+        Oklch {
+            l: lab.l,
+            c: (lab.a * lab.a + lab.b * lab.b).sqrt(),
+            h: lab.b.atan2(lab.a).rem_euclid(std::f32::consts::TAU),
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(lch: Oklch) -> Self {
+        Oklab {
+            l: lch.l,
+            a: lch.c * lch.h.cos(),
+            b: lch.c * lch.h.sin(),
+        }
+    }
+}
+
+// `steps` colors from `from` to `to`, both included, evenly spaced in OKLab.
+pub fn ramp(from: Rgba, to: Rgba, steps: usize) -> Vec<Rgba> {
+    let (start, end) = (Oklab::from_rgba(from), Oklab::from_rgba(to));
+    (0..steps)
+        .map(|i| {
+            let t = if steps > 1 {
+                i as f32 / (steps - 1) as f32
+            } else {
+                0.0
+            };
+            let alpha = from.alpha + (to.alpha - from.alpha) * t;
+            start.mix(&end, t).to_rgba(alpha)
+        })
+        .collect()
+}
+
+// `steps` shades of the same hue and chroma, from dark to light, evenly spaced in OKLCh lightness
+// between `min_lightness` and `max_lightness` (0 black, 1 white).
+pub fn lightness_ramp(
+    base: Rgba,
+    steps: usize,
+    min_lightness: f32,
+    max_lightness: f32,
+) -> Vec<Rgba> {
+    let lch = Oklch::from_rgba(base);
+    (0..steps)
+        .map(|i| {
+            let t = if steps > 1 {
+                i as f32 / (steps - 1) as f32
+            } else {
+                0.5
+            };
+            let l = min_lightness + (max_lightness - min_lightness) * t;
+            Oklch { l, ..lch }.to_rgba(base.alpha)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn white_and_black() {
+        let white = Oklab::from_rgba(rgba(1.0, 1.0, 1.0, 1.0));
+        assert!((white.l - 1.0).abs() < 1e-3);
+        assert!(white.a.abs() < 1e-3 && white.b.abs() < 1e-3);
+        let black = Oklab::from_rgba(rgba(0.0, 0.0, 0.0, 1.0));
+        assert!(black.l.abs() < 1e-3);
+    }
+
+    #[test]
+    fn round_trip() {
+        let colors = [
+            rgba(0.733, 1.0, 0.31, 1.0),
+            rgba(0.098, 0.0, 0.749, 0.5),
+            rgba(0.9, 0.1, 0.2, 1.0),
+        ];
+        for c in colors.iter() {
+            let back = Oklch::from_rgba(*c).to_rgba(c.alpha);
+            assert!((back.red - c.red).abs() < 1e-3);
+            assert!((back.green - c.green).abs() < 1e-3);
+            assert!((back.blue - c.blue).abs() < 1e-3);
+            assert_eq!(back.alpha, c.alpha);
+        }
+    }
+
+    #[test]
+    fn ramps() {
+        let black = rgba(0.0, 0.0, 0.0, 1.0);
+        let white = rgba(1.0, 1.0, 1.0, 1.0);
+        let colors = ramp(black, white, 5);
+        assert_eq!(colors.len(), 5);
+        assert_eq!(colors[0].red, 0.0);
+        assert!((colors[4].red - 1.0).abs() < 1e-3);
+        // evenly spaced lightness
+        let l: Vec<f32> = colors.iter().map(|c| Oklab::from_rgba(*c).l).collect();
+        assert!((l[2] - 0.5).abs() < 1e-2);
+
+        let shades = lightness_ramp(rgba(0.086, 0.29, 0.8, 1.0), 4, 0.2, 0.8);
+        for pair in shades.windows(2) {
+            assert!(Oklab::from_rgba(pair[0]).l < Oklab::from_rgba(pair[1]).l);
+        }
+    }
+}
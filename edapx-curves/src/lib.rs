@@ -0,0 +1,225 @@
+use nannou::geom::path::{Builder, Path};
+use nannou::prelude::*;
+
+// Curves passing through (or, for the B-spline, close to) a list of points, like curveVertex() in
+// Processing. Every curve is converted to cubic Bézier segments, so it can be drawn with lyon.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CurveType {
+    // uniform Catmull–Rom, the one of curveVertex()
+    CatmullRom,
+    // centripetal Catmull–Rom, no cusps and no self intersections inside a segment
+    Centripetal,
+    // uniform cubic B-spline, smoother but it does not pass through the points
+    BSpline,
+}
+
+impl CurveType {
+    // the exponent used to parametrize the Catmull–Rom splines, 0 uniform, 0.5 centripetal
+    fn alpha(&self) -> f32 {
+        match self {
+            CurveType::Centripetal => 0.5,
+            _ => 0.0,
+        }
+    }
+}
+
+// The cubic Bézier segments, as [from, ctrl1, ctrl2, to], of the curve through the points.
+// When the curve is open, the missing points at the ends are mirrored, so that the curve starts on
+// the first point and ends on the last one. When it is closed the last point is connected to the first.
+pub fn curve_segments(points: &[Point2], curve: CurveType, closed: bool) -> Vec<[Point2; 4]> {
+    let n = points.len();
+    let mut segments = Vec::new();
+    if n < 2 {
+        return segments;
+    }
+    let point = |i: isize| -> Point2 {
+        if closed {
+            points[i.rem_euclid(n as isize) as usize]
+        } else if i < 0 {
+            points[0] * 2.0 - points[1]
+        } else if i >= n as isize {
+            points[n - 1] * 2.0 - points[n - 2]
+        } else {
+            points[i as usize]
+        }
+    };
+    let n_segments = if closed { n } else { n - 1 };
+    for i in 0..n_segments as isize {
+        let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+        let segment = match curve {
+            CurveType::BSpline => [
+                (p0 + p1 * 4.0 + p2) / 6.0,
+                (p1 * 2.0 + p2) / 3.0,
+                (p1 + p2 * 2.0) / 3.0,
+                (p1 + p2 * 4.0 + p3) / 6.0,
+            ],
+            _ => catmull_rom_segment(p0, p1, p2, p3, curve.alpha()),
+        };
+        segments.push(segment);
+    }
+    segments
+}
+
+// Catmull–Rom segment between p1 and p2 with the knots spaced by distance^alpha.
+// See "On the Parameterization of Catmull-Rom Curves", Yuksel, Schaefer, Keyser.
+fn catmull_rom_segment(p0: Point2, p1: Point2, p2: Point2, p3: Point2, alpha: f32) -> [Point2; 4] {
+    let d1 = p0.distance(p1).powf(alpha);
+    let d2 = p1.distance(p2).powf(alpha);
+    let d3 = p2.distance(p3).powf(alpha);
+
+    // when two points coincide the tangent is undefined, the control point stays on the curve
+    let ctrl1 = if d1 > f32::EPSILON {
+        (p2 * d1 * d1 - p0 * d2 * d2 + p1 * (2.0 * d1 * d1 + 3.0 * d1 * d2 + d2 * d2))
+            / (3.0 * d1 * (d1 + d2))
+    } else {
+        p1
+    };
+    let ctrl2 = if d3 > f32::EPSILON {
+        (p1 * d3 * d3 - p3 * d2 * d2 + p2 * (2.0 * d3 * d3 + 3.0 * d3 * d2 + d2 * d2))
+            / (3.0 * d3 * (d3 + d2))
+    } else {
+        p2
+    };
+    [p1, ctrl1, ctrl2, p2]
+}
+
+// A lyon path with the curve through the points, to be used with draw.path().
+// It returns None when there are less than 2 points.
+pub fn curve_path(points: &[Point2], curve: CurveType, closed: bool) -> Option<Path> {
+    let segments = curve_segments(points, curve, closed);
+    let first = segments.first()?;
+    let mut builder = Builder::new().begin(first[0]);
+    for [_, ctrl1, ctrl2, to] in segments.iter() {
+        builder = builder.cubic_bezier_to(*ctrl1, *ctrl2, *to);
+    }
+    if closed {
+        builder = builder.close();
+    } else {
+        // lyon wants every sub path to be ended before building
+        builder.inner_mut().end(false);
+    }
+    Some(builder.build())
+}
+
+// The curve sampled in `resolution` points per segment, to be used with draw.polyline()
+// when every vertex needs its own color or when the points are needed.
+pub fn curve_points(
+    points: &[Point2],
+    curve: CurveType,
+    closed: bool,
+    resolution: usize,
+) -> Vec<Point2> {
+    let resolution = resolution.max(1);
+    let segments = curve_segments(points, curve, closed);
+    let mut curve_points = Vec::with_capacity(segments.len() * resolution + 1);
+    for (index, segment) in segments.iter().enumerate() {
+        // the first point of a segment is the last point of the previous one
+        let first = if index == 0 { 0 } else { 1 };
+        for i in first..=resolution {
+            curve_points.push(bezier_point(segment, i as f32 / resolution as f32));
+        }
+    }
+    curve_points
+}
+
+pub fn bezier_point(segment: &[Point2; 4], t: f32) -> Point2 {
+    let u = 1.0 - t;
+    segment[0] * (u * u * u)
+        + segment[1] * (3.0 * u * u * t)
+        + segment[2] * (3.0 * u * t * t)
+        + segment[3] * (t * t * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<Point2> {
+        vec![
+            pt2(0.0, 0.0),
+            pt2(100.0, 0.0),
+            pt2(100.0, 100.0),
+            pt2(0.0, 100.0),
+        ]
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_the_points() {
+        for curve in [CurveType::CatmullRom, CurveType::Centripetal].iter() {
+            let points = square();
+            let segments = curve_segments(&points, *curve, false);
+            assert_eq!(segments.len(), points.len() - 1);
+            for (i, segment) in segments.iter().enumerate() {
+                assert_eq!(segment[0], points[i]);
+                assert_eq!(segment[3], points[i + 1]);
+            }
+        }
+    }
+
+    #[test]
+    fn closed_curves_wrap_around() {
+        let points = square();
+        let segments = curve_segments(&points, CurveType::CatmullRom, true);
+        assert_eq!(segments.len(), points.len());
+        assert_eq!(segments.last().unwrap()[3], points[0]);
+    }
+
+    #[test]
+    fn uniform_catmull_rom_tangents() {
+        let points = square();
+        let segments = curve_segments(&points, CurveType::CatmullRom, true);
+        // ctrl1 = p1 + (p2 - p0) / 6
+        let expected = points[1] + (points[2] - points[0]) / 6.0;
+        assert!(segments[1][1].distance(expected) < 1e-4);
+    }
+
+    #[test]
+    fn open_b_spline_ends_on_the_points() {
+        let points = square();
+        let segments = curve_segments(&points, CurveType::BSpline, false);
+        assert!(segments[0][0].distance(points[0]) < 1e-4);
+        assert!(segments.last().unwrap()[3].distance(points[3]) < 1e-4);
+        // the segments are connected
+        for pair in segments.windows(2) {
+            assert!(pair[0][3].distance(pair[1][0]) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn centripetal_handles_coincident_points() {
+        let points = vec![pt2(0.0, 0.0), pt2(0.0, 0.0), pt2(50.0, 10.0)];
+        let segments = curve_segments(&points, CurveType::Centripetal, false);
+        for segment in segments.iter() {
+            for p in segment.iter() {
+                assert!(p.x.is_finite() && p.y.is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn not_enough_points() {
+        assert!(curve_path(&[], CurveType::CatmullRom, false).is_none());
+        assert!(curve_path(&[pt2(1.0, 1.0)], CurveType::BSpline, true).is_none());
+        assert!(curve_points(&[pt2(1.0, 1.0)], CurveType::BSpline, true, 8).is_empty());
+    }
+
+    #[test]
+    fn open_and_closed_paths() {
+        let points = square();
+        for closed in [false, true].iter() {
+            let path = curve_path(&points, CurveType::CatmullRom, *closed).unwrap();
+            let segments = points.len() - 1 + *closed as usize;
+            // a begin, the cubic segments and an end
+            assert_eq!(path.iter().count(), segments + 2);
+        }
+    }
+
+    #[test]
+    fn sampled_points() {
+        let points = square();
+        let sampled = curve_points(&points, CurveType::CatmullRom, false, 10);
+        assert_eq!(sampled.len(), 3 * 10 + 1);
+        assert_eq!(sampled[0], points[0]);
+        assert!(sampled.last().unwrap().distance(points[3]) < 1e-4);
+    }
+}
@@ -0,0 +1,464 @@
+// A chain of screen space passes. The sketch draws into the multisampled texture of the chain,
+// the drawing is resolved into an intermediate texture and every enabled pass reads the result of
+// the previous one, ping-ponging between two intermediate textures. The last enabled pass writes
+// to the output: the frame of the window or a texture, like the one of the `Capturer`.
+
+use crate::effects::{color_grading_uniforms, COLOR_GRADING_SOURCE};
+use crate::post_processing_effect::{
+    bind_group, bind_group_layout, pipeline_layout, render_pipeline, shader_module, uniform_buffer,
+    vertex_buffer, write_uniforms, VERTICES,
+};
+use crate::{fragment_shader, Effect, UniformError, UniformValue, Uniforms};
+use nannou::image::RgbaImage;
+use nannou::prelude::*;
+use nannou::wgpu::Device;
+
+// the format of the drawing and of the intermediate textures
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+// Where the last pass writes. The pipelines have to know the format and the sample count of the
+// destination when they are created.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Output {
+    pub format: wgpu::TextureFormat,
+    pub sample_count: u32,
+}
+
+impl Output {
+    // the frame of the window
+    pub fn window(window: &Window) -> Self {
+        Output {
+            format: Frame::TEXTURE_FORMAT,
+            sample_count: window.msaa_samples(),
+        }
+    }
+
+    // a texture, like the one of the `Capturer`
+    pub fn texture(texture: &wgpu::Texture) -> Self {
+        Output {
+            format: texture.format(),
+            sample_count: texture.sample_count(),
+        }
+    }
+}
+
+// A fragment shader of the chain. It receives the texture coordinates at location 0 and reads the
+// result of the previous pass with the generated `source` functions, see `fragment_shader`.
+pub struct Pass {
+    pub name: String,
+    // a disabled pass is skipped, the next one reads the result of the previous one
+    pub enabled: bool,
+    pub uniforms: Uniforms,
+    uniform_buffer: wgpu::Buffer,
+    // reading the first or the second intermediate texture
+    bind_groups: [wgpu::BindGroup; 2],
+    // writing to an intermediate texture or to the output
+    pipeline: wgpu::RenderPipeline,
+    output_pipeline: wgpu::RenderPipeline,
+    extra: Option<Extra>,
+}
+
+// The texture a pass reads at binding 3, besides the result of the previous pass.
+enum Extra {
+    // a look up table, like the one of the color grading
+    Texture(wgpu::Texture),
+    // the result of the pass in the previous frame, written every time the pass runs
+    History(wgpu::Texture),
+}
+
+impl Extra {
+    fn texture(&self) -> &wgpu::Texture {
+        match self {
+            Extra::Texture(texture) | Extra::History(texture) => texture,
+        }
+    }
+}
+
+impl Pass {
+    fn history(&self) -> Option<&wgpu::Texture> {
+        match &self.extra {
+            Some(Extra::History(texture)) => Some(texture),
+            _ => None,
+        }
+    }
+
+    fn encode(
+        &self,
+        input: usize,
+        last: bool,
+        dst_texture: &wgpu::TextureViewHandle,
+        vertex_buffer: &wgpu::Buffer,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let pipeline = if last {
+            &self.output_pipeline
+        } else {
+            &self.pipeline
+        };
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(dst_texture, |color| color)
+            .begin(encoder);
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.set_bind_group(0, &self.bind_groups[input], &[]);
+        let vertex_range = 0..VERTICES.len() as u32;
+        let instance_range = 0..1;
+        render_pass.draw(vertex_range, instance_range);
+    }
+}
+
+pub struct EffectChain {
+    // The texture that we will draw to.
+    pub texture: wgpu::Texture,
+    // Create a `Draw` instance for drawing to our texture.
+    pub draw: nannou::Draw,
+    // The type used to render the `Draw` vertices to our texture.
+    pub renderer: nannou::draw::Renderer,
+    // resolves the drawing into the first intermediate texture
+    resolver: wgpu::TextureReshaper,
+    shared: Shared,
+    vertex_buffer: wgpu::Buffer,
+    passes: Vec<Pass>,
+    // writes to the output when every pass is disabled or when the last one keeps its history
+    copy: Pass,
+}
+
+// What the passes of a chain have in common.
+struct Shared {
+    targets: [wgpu::Texture; 2],
+    target_views: [wgpu::TextureView; 2],
+    output: Output,
+    sampler: wgpu::Sampler,
+    sampler_filtering: bool,
+    vs_mod: wgpu::ShaderModule,
+}
+
+impl EffectChain {
+    pub fn new(
+        texture_size: [u32; 2],
+        src_sample_count: u32,
+        device: &Device,
+        output: Output,
+    ) -> Self {
+        let texture = wgpu::TextureBuilder::new()
+            .size(texture_size)
+            .usage(wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING)
+            .sample_count(src_sample_count)
+            .format(TEXTURE_FORMAT)
+            .build(device);
+
+        let draw = nannou::Draw::new();
+        let descriptor = texture.descriptor();
+        let renderer =
+            nannou::draw::RendererBuilder::new().build_from_texture_descriptor(device, descriptor);
+
+        // the intermediate textures are not multisampled, the passes read them like images. They
+        // are copied into the history of the feedback passes.
+        let target = || {
+            wgpu::TextureBuilder::new()
+                .size(texture_size)
+                .usage(
+                    wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING
+                        | wgpu::TextureUsages::COPY_SRC,
+                )
+                .sample_count(1)
+                .format(TEXTURE_FORMAT)
+                .build(device)
+        };
+        let (first, second) = (target(), target());
+
+        let resolver = wgpu::TextureReshaper::new(
+            device,
+            &texture.view().build(),
+            src_sample_count,
+            texture.sample_type(),
+            1,
+            TEXTURE_FORMAT,
+        );
+
+        let sampler_desc = wgpu::SamplerBuilder::new().into_descriptor();
+        let shared = Shared {
+            target_views: [first.view().build(), second.view().build()],
+            targets: [first, second],
+            output,
+            sampler_filtering: wgpu::sampler_filtering(&sampler_desc),
+            sampler: device.create_sampler(&sampler_desc),
+            vs_mod: device.create_shader_module(&crate::vertex_shader()),
+        };
+        let copy = shared.new_pass(
+            device,
+            "copy",
+            include_str!("shaders/copy.wgsl"),
+            Uniforms::new(),
+            None,
+        );
+
+        EffectChain {
+            texture,
+            draw,
+            renderer,
+            resolver,
+            shared,
+            vertex_buffer: vertex_buffer(device),
+            passes: Vec::new(),
+            copy,
+        }
+    }
+
+    // Adds a pass at the end of the chain, enabled.
+    pub fn add_pass(&mut self, device: &Device, name: &str, fs_source: &str, uniforms: Uniforms) {
+        let pass = self
+            .shared
+            .new_pass(device, name, fs_source, uniforms, None);
+        self.passes.push(pass);
+    }
+
+    // Adds one of the built-in effects with its default parameters, named after the effect.
+    pub fn add_effect(&mut self, device: &Device, effect: Effect) {
+        let extra = if effect.feedback() {
+            Some(Extra::History(self.shared.history(device)))
+        } else {
+            None
+        };
+        let pass = self.shared.new_pass(
+            device,
+            effect.name(),
+            effect.source(),
+            effect.uniforms(),
+            extra,
+        );
+        self.passes.push(pass);
+    }
+
+    // Adds the "color grading" pass with a look up table, an image like the one of `neutral_lut`:
+    // `chain.add_color_grading(&window, &image::open(path)?.to_rgba8())`.
+    pub fn add_color_grading(&mut self, window: &Window, lut: &RgbaImage) {
+        let device = window.device();
+        // not srgb, the values of the table are used as they are
+        let texture = wgpu::TextureBuilder::new()
+            .size([lut.width(), lut.height()])
+            .usage(wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST)
+            .format(wgpu::TextureFormat::Rgba8Unorm)
+            .build(device);
+        let ce_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("look up table"),
+        };
+        let mut encoder = device.create_command_encoder(&ce_desc);
+        texture.upload_data(device, &mut encoder, lut.as_raw());
+        window.queue().submit(Some(encoder.finish()));
+
+        let pass = self.shared.new_pass(
+            device,
+            "color grading",
+            COLOR_GRADING_SOURCE,
+            color_grading_uniforms(),
+            Some(Extra::Texture(texture)),
+        );
+        self.passes.push(pass);
+    }
+
+    pub fn passes(&self) -> &[Pass] {
+        &self.passes
+    }
+
+    // to enable, disable or reorder the passes
+    pub fn passes_mut(&mut self) -> &mut Vec<Pass> {
+        &mut self.passes
+    }
+
+    pub fn pass_mut(&mut self, name: &str) -> Option<&mut Pass> {
+        self.passes.iter_mut().find(|pass| pass.name == name)
+    }
+
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        if let Some(pass) = self.pass_mut(name) {
+            pass.enabled = enabled;
+        }
+    }
+
+    // Renders the drawing to the texture.
+    pub fn update(&mut self, window: &Window, device: &Device) {
+        let ce_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("texture renderer"),
+        };
+        let mut encoder = device.create_command_encoder(&ce_desc);
+        self.renderer
+            .render_to_texture(device, &mut encoder, &self.draw, &self.texture);
+        window.queue().submit(Some(encoder.finish()));
+    }
+
+    // writes the time and the parameters of every pass
+    pub fn update_buffer(&mut self, window: &Window, val: f32) {
+        for pass in self.passes.iter_mut() {
+            pass.uniforms.set_time(val);
+            write_uniforms(window, &pass.uniform_buffer, &pass.uniforms);
+        }
+    }
+
+    // sets a parameter of a pass, `chain.set("vignette", "radius", 0.8)`
+    pub fn set<V: Into<UniformValue>>(
+        &mut self,
+        pass: &str,
+        name: &str,
+        value: V,
+    ) -> Result<(), UniformError> {
+        match self.pass_mut(pass) {
+            Some(pass) => pass.uniforms.set(name, value),
+            None => Err(UniformError::Unknown(pass.to_string())),
+        }
+    }
+
+    // A checkbox for every pass, with the sliders of its parameters. Returns true if something
+    // changed.
+    #[cfg(feature = "egui")]
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        for (i, pass) in self.passes.iter_mut().enumerate() {
+            changed |= ui.checkbox(&mut pass.enabled, &pass.name).changed();
+            if pass.enabled {
+                let uniforms = &mut pass.uniforms;
+                ui.indent(i, |ui| {
+                    changed |= uniforms.show(ui);
+                });
+            }
+        }
+        changed
+    }
+
+    // Resolves the drawing and runs the enabled passes, the last one writes to `dst_texture`,
+    // that has to match the `Output` of the chain.
+    pub fn encode_render_pass(
+        &self,
+        dst_texture: &wgpu::TextureViewHandle,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let targets = &self.shared.targets;
+        let target_views = &self.shared.target_views;
+        self.resolver.encode_render_pass(&target_views[0], encoder);
+
+        let enabled: Vec<&Pass> = self.passes.iter().filter(|pass| pass.enabled).collect();
+        let mut input = 0;
+        let mut written = false;
+        for (i, pass) in enabled.iter().enumerate() {
+            let history = pass.history();
+            // a pass keeping its history writes to an intermediate texture, that can be copied
+            if i + 1 == enabled.len() && history.is_none() {
+                pass.encode(input, true, dst_texture, &self.vertex_buffer, encoder);
+                written = true;
+            } else {
+                let output = 1 - input;
+                pass.encode(
+                    input,
+                    false,
+                    &target_views[output],
+                    &self.vertex_buffer,
+                    encoder,
+                );
+                if let Some(history) = history {
+                    encoder.copy_texture_to_texture(
+                        targets[output].as_image_copy(),
+                        history.as_image_copy(),
+                        history.extent(),
+                    );
+                }
+                input = output;
+            }
+        }
+        if !written {
+            self.copy
+                .encode(input, true, dst_texture, &self.vertex_buffer, encoder);
+        }
+    }
+
+    // Draw into the given `Frame`, the output of the chain has to be `Output::window`.
+    pub fn view(&self, frame: Frame) {
+        let mut encoder = frame.command_encoder();
+        self.encode_render_pass(frame.texture_view(), &mut encoder);
+    }
+
+    // Writes the result into `texture`, for example the texture of the `Capturer`. The output of
+    // the chain has to be `Output::texture(texture)`.
+    pub fn render_to(&self, window: &Window, device: &Device, texture: &wgpu::Texture) {
+        let ce_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("effect chain"),
+        };
+        let mut encoder = device.create_command_encoder(&ce_desc);
+        self.encode_render_pass(&texture.view().build(), &mut encoder);
+        window.queue().submit(Some(encoder.finish()));
+    }
+}
+
+impl Shared {
+    fn new_pass(
+        &self,
+        device: &Device,
+        name: &str,
+        fs_source: &str,
+        uniforms: Uniforms,
+        extra: Option<Extra>,
+    ) -> Pass {
+        let extra_view = extra.as_ref().map(|extra| extra.texture().view().build());
+        let layout = bind_group_layout(
+            device,
+            1,
+            self.targets[0].sample_type(),
+            self.sampler_filtering,
+            extra.as_ref().map(|extra| extra.texture().sample_type()),
+        );
+        let pipeline_layout = pipeline_layout(device, &layout);
+        // the intermediate textures are not multisampled
+        let fs_mod = shader_module(device, fragment_shader(fs_source, 1, &uniforms));
+        let uniform_buffer = uniform_buffer(device, &uniforms);
+        let reading = |input: &wgpu::TextureView| {
+            bind_group(
+                device,
+                &layout,
+                input,
+                &self.sampler,
+                &uniform_buffer,
+                extra_view.as_deref(),
+            )
+        };
+        let bind_groups = [
+            reading(&self.target_views[0]),
+            reading(&self.target_views[1]),
+        ];
+        let pipeline = render_pipeline(
+            device,
+            &pipeline_layout,
+            &self.vs_mod,
+            &fs_mod,
+            1,
+            TEXTURE_FORMAT,
+        );
+        let output_pipeline = render_pipeline(
+            device,
+            &pipeline_layout,
+            &self.vs_mod,
+            &fs_mod,
+            self.output.sample_count,
+            self.output.format,
+        );
+        Pass {
+            name: name.to_string(),
+            enabled: true,
+            uniforms,
+            uniform_buffer,
+            bind_groups,
+            pipeline,
+            output_pipeline,
+            extra,
+        }
+    }
+
+    // the result of a feedback pass in the previous frame, black at the beginning
+    fn history(&self, device: &Device) -> wgpu::Texture {
+        wgpu::TextureBuilder::new()
+            .size(self.targets[0].size())
+            .usage(wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST)
+            .sample_count(1)
+            .format(TEXTURE_FORMAT)
+            .build(device)
+    }
+}
@@ -0,0 +1,379 @@
+// The effects computed on the CPU, on images in memory. They follow the shaders step by step, the
+// results differ by the precision of the GPU, so they work as a fallback when rendering without a
+// window and as the reference in tests:
+//
+// let image = cpu::from_rgba8(&nannou::image::open(path)?.to_rgba8());
+// let result = cpu::apply(Effect::Vignette, &Effect::Vignette.uniforms(), &image).unwrap();
+// let pixels = cpu::to_rgba8(&result);
+//
+// The pixels are read like in the shaders: `source` clamps the coordinates, `source_uv` filters
+// linearly with the texture coordinates of the centers of the pixels, (x + 0.5) / width.
+use crate::{Effect, UniformValue, Uniforms};
+use nannou::image::{ImageBuffer, Rgba, RgbaImage};
+
+// the channels between 0.0 and 1.0, more in the bright parts like in the textures of the effects
+pub type Image = ImageBuffer<Rgba<f32>, Vec<f32>>;
+
+pub fn from_rgba8(image: &RgbaImage) -> Image {
+    Image::from_fn(image.width(), image.height(), |x, y| {
+        let p = image.get_pixel(x, y).0;
+        Rgba([
+            p[0] as f32 / 255.0,
+            p[1] as f32 / 255.0,
+            p[2] as f32 / 255.0,
+            p[3] as f32 / 255.0,
+        ])
+    })
+}
+
+pub fn to_rgba8(image: &Image) -> RgbaImage {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+        let p = image.get_pixel(x, y).0;
+        Rgba([channel(p[0]), channel(p[1]), channel(p[2]), channel(p[3])])
+    })
+}
+
+// The biggest difference of a channel between two images of the same size, to compare a result
+// with a reference.
+pub fn max_difference(a: &Image, b: &Image) -> f32 {
+    assert_eq!(
+        a.dimensions(),
+        b.dimensions(),
+        "the images have different sizes"
+    );
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f32::max)
+}
+
+// The built-in effect with the parameters of `uniforms`, time included. None for the effects
+// that exist only on the GPU.
+pub fn apply(effect: Effect, uniforms: &Uniforms, image: &Image) -> Option<Image> {
+    let f = |name| float(uniforms, name);
+    let result = match effect {
+        Effect::Bloom => bloom(image, f("threshold"), f("intensity"), f("radius")),
+        Effect::FilmGrain => film_grain(image, f("amount"), f("size"), f("time")),
+        Effect::Vignette => vignette(
+            image,
+            f("radius"),
+            f("softness"),
+            f("strength"),
+            vec4(uniforms, "color"),
+        ),
+        _ => return None,
+    };
+    Some(result)
+}
+
+// The displacement of screeneffect/src/shaders/noise.wgsl: waves move the pixels horizontally and
+// a grid bent by the noise tints the channels.
+pub fn noise_displacement(image: &Image, time: f32) -> Image {
+    let (w, h) = image.dimensions();
+    map_uv(image, |uv| {
+        let waved_x = uv[0] + ((time + uv[1]) * 19.0).sin() * 0.02;
+        let coords = [(w as f32 * waved_x) as i32, (h as f32 * uv[1]) as i32];
+        let mut color = source(image, coords);
+
+        let thick = 0.3;
+        let st = [uv[1], uv[1] * (uv[0] * 0.4)];
+        let st = vector_field(st, time);
+        let cell = 0.3;
+        let x = plot(st[0] % cell, cell, thick);
+        let y = plot(st[1] % cell, cell, thick);
+
+        color[0] *= y;
+        color[1] = (1.0 - color[1]) * smoothstep(1.3, 0.01, x + y);
+        color[2] = (1.0 - color[2]) * smoothstep(1.9, 0.01, x + y);
+        color
+    })
+}
+
+// Gaussian blur, a sigma of half the radius in pixels. Not a shader of the library, the bloom
+// blurs inside its own pass.
+pub fn blur(image: &Image, radius: f32) -> Image {
+    let sigma = (radius / 2.0).max(0.01);
+    let r = radius.ceil() as i32;
+    let weights: Vec<f32> = (-r..=r)
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = weights.iter().sum();
+    // a horizontal pass and a vertical one
+    let pass = |image: &Image, dx: i32, dy: i32| {
+        Image::from_fn(image.width(), image.height(), |x, y| {
+            let mut sum = [0.0; 4];
+            for (i, weight) in (-r..=r).zip(weights.iter()) {
+                let c = source(image, [x as i32 + i * dx, y as i32 + i * dy]);
+                for k in 0..4 {
+                    sum[k] += c[k] * weight / total;
+                }
+            }
+            Rgba(sum)
+        })
+    };
+    pass(&pass(image, 1, 0), 0, 1)
+}
+
+// the gaussian bloom of shaders/effects/bloom.wgsl
+pub fn bloom(image: &Image, threshold: f32, intensity: f32, radius: f32) -> Image {
+    let texel = texel(image);
+    let spacing = radius / 4.0;
+    let bright = |uv: [f32; 2]| {
+        let c = source_uv(image, uv);
+        [
+            (c[0] - threshold).max(0.0),
+            (c[1] - threshold).max(0.0),
+            (c[2] - threshold).max(0.0),
+        ]
+    };
+    map_uv(image, |uv| {
+        let mut sum = [0.0; 3];
+        let mut total = 0.0;
+        for y in -4..=4 {
+            for x in -4..=4 {
+                let d = [x as f32 / 4.0, y as f32 / 4.0];
+                let weight = (-2.0 * (d[0] * d[0] + d[1] * d[1])).exp();
+                let b = bright([
+                    uv[0] + x as f32 * spacing * texel[0],
+                    uv[1] + y as f32 * spacing * texel[1],
+                ]);
+                for k in 0..3 {
+                    sum[k] += b[k] * weight;
+                }
+                total += weight;
+            }
+        }
+        let c = source_uv(image, uv);
+        [
+            c[0] + sum[0] / total * intensity,
+            c[1] + sum[1] / total * intensity,
+            c[2] + sum[2] / total * intensity,
+            c[3],
+        ]
+    })
+}
+
+// the grain of shaders/effects/film_grain.wgsl
+pub fn film_grain(image: &Image, amount: f32, size: f32, time: f32) -> Image {
+    let (w, h) = image.dimensions();
+    let frame = (time * 24.0).floor() % 64.0;
+    map_uv(image, |uv| {
+        let c = source_uv(image, uv);
+        let cell = [
+            (uv[0] * w as f32 / size).floor(),
+            (uv[1] * h as f32 / size).floor(),
+        ];
+        let n = rand([cell[0] + frame * 17.0, cell[1] + frame * 31.0]) - 0.5;
+        let luma = c[0] * 0.2126 + c[1] * 0.7152 + c[2] * 0.0722;
+        let amount = amount * (1.0 - 0.5 * luma.clamp(0.0, 1.0));
+        [
+            c[0] + n * amount,
+            c[1] + n * amount,
+            c[2] + n * amount,
+            c[3],
+        ]
+    })
+}
+
+// the vignette of shaders/effects/vignette.wgsl
+pub fn vignette(
+    image: &Image,
+    radius: f32,
+    softness: f32,
+    strength: f32,
+    color: [f32; 4],
+) -> Image {
+    let (w, h) = image.dimensions();
+    let aspect = w as f32 / h as f32;
+    map_uv(image, |uv| {
+        let d = [(uv[0] - 0.5) * aspect * 2.0, (uv[1] - 0.5) * 2.0];
+        let length = (d[0] * d[0] + d[1] * d[1]).sqrt();
+        let fade = smoothstep(radius - softness, radius, length);
+        let a = fade * strength * color[3];
+        let c = source_uv(image, uv);
+        [
+            mix(c[0], color[0], a),
+            mix(c[1], color[1], a),
+            mix(c[2], color[2], a),
+            c[3],
+        ]
+    })
+}
+
+// runs `f` with the texture coordinates of every pixel
+fn map_uv<F: Fn([f32; 2]) -> [f32; 4]>(image: &Image, f: F) -> Image {
+    let (w, h) = image.dimensions();
+    Image::from_fn(w, h, |x, y| {
+        let uv = [(x as f32 + 0.5) / w as f32, (y as f32 + 0.5) / h as f32];
+        Rgba(f(uv))
+    })
+}
+
+fn texel(image: &Image) -> [f32; 2] {
+    [1.0 / image.width() as f32, 1.0 / image.height() as f32]
+}
+
+// `source` of the shaders, the coordinates clamped to the image
+fn source(image: &Image, coords: [i32; 2]) -> [f32; 4] {
+    let x = coords[0].max(0).min(image.width() as i32 - 1);
+    let y = coords[1].max(0).min(image.height() as i32 - 1);
+    image.get_pixel(x as u32, y as u32).0
+}
+
+// `source_uv` of the shaders, linear filtering and the borders clamped like the sampler
+fn source_uv(image: &Image, uv: [f32; 2]) -> [f32; 4] {
+    let x = uv[0] * image.width() as f32 - 0.5;
+    let y = uv[1] * image.height() as f32 - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let a = source(image, [x0, y0]);
+    let b = source(image, [x0 + 1, y0]);
+    let c = source(image, [x0, y0 + 1]);
+    let d = source(image, [x0 + 1, y0 + 1]);
+    let mut color = [0.0; 4];
+    for k in 0..4 {
+        color[k] = mix(mix(a[k], b[k], fx), mix(c[k], d[k], fx), fy);
+    }
+    color
+}
+
+fn float(uniforms: &Uniforms, name: &str) -> f32 {
+    match uniforms.get(name) {
+        Some(UniformValue::Float(v)) => v,
+        Some(UniformValue::Int(v)) => v as f32,
+        _ => panic!("the effect has no float parameter {}", name),
+    }
+}
+
+fn vec4(uniforms: &Uniforms, name: &str) -> [f32; 4] {
+    match uniforms.get(name) {
+        Some(UniformValue::Vec4(v)) | Some(UniformValue::Color(v)) => v,
+        _ => panic!("the effect has no vec4 parameter {}", name),
+    }
+}
+
+fn mix(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+// like in WGSL, the edges can be reversed
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+// like in WGSL, always positive
+fn fract(x: f32) -> f32 {
+    x - x.floor()
+}
+
+fn rand(st: [f32; 2]) -> f32 {
+    fract((st[0] * 12.9898 + st[1] * 78.233).sin() * 43758.547)
+}
+
+fn noise(st: [f32; 2]) -> f32 {
+    let i = [st[0].floor(), st[1].floor()];
+    let f = [fract(st[0]), fract(st[1])];
+    let a = rand(i);
+    let b = rand([i[0] + 1.0, i[1]]);
+    let c = rand([i[0], i[1] + 1.0]);
+    let d = rand([i[0] + 1.0, i[1] + 1.0]);
+    let u = [
+        f[0] * f[0] * (3.0 - 2.0 * f[0]),
+        f[1] * f[1] * (3.0 - 2.0 * f[1]),
+    ];
+    mix(a, b, u[0]) + (c - a) * u[1] * (1.0 - u[0]) + (d - b) * u[0] * u[1]
+}
+
+fn vector_field(uv: [f32; 2], time: f32) -> [f32; 2] {
+    let n = noise([uv[0] * 3.0, uv[1] * 3.0]);
+    let mut res = [uv[0], uv[1] - time * 0.05];
+    res = [
+        res[0] + (res[1] * 40.0).sin() * 0.02,
+        res[1] + (res[0] * 40.0).sin() * 0.02,
+    ];
+    [res[0] + n, res[1] + n]
+}
+
+fn plot(val: f32, c: f32, t: f32) -> f32 {
+    let l = smoothstep(c, c - t, val);
+    let r = smoothstep(c, c - t / 5.0, val);
+    r - l
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray(w: u32, h: u32, v: f32) -> Image {
+        Image::from_pixel(w, h, Rgba([v, v, v, 1.0]))
+    }
+
+    #[test]
+    fn rgba8_round_trip() {
+        let image = RgbaImage::from_fn(4, 3, |x, y| Rgba([x as u8 * 60, y as u8 * 100, 7, 255]));
+        assert_eq!(to_rgba8(&from_rgba8(&image)), image);
+    }
+
+    #[test]
+    fn blur_keeps_a_flat_image() {
+        let image = gray(16, 8, 0.4);
+        assert!(max_difference(&blur(&image, 5.0), &image) < 1e-5);
+    }
+
+    #[test]
+    fn bloom_spreads_the_light() {
+        let mut image = gray(32, 32, 0.0);
+        image.put_pixel(16, 16, Rgba([4.0, 4.0, 4.0, 1.0]));
+        // nothing is above the threshold
+        let dark = gray(32, 32, 0.5);
+        assert!(max_difference(&bloom(&dark, 0.7, 0.8, 12.0), &dark) < 1e-6);
+
+        let result = bloom(&image, 0.7, 0.8, 12.0);
+        assert!(result.get_pixel(19, 16).0[0] > 0.0);
+        assert!(result.get_pixel(16, 16).0[0] >= 4.0);
+        assert_eq!(result.get_pixel(0, 0).0[0], 0.0);
+    }
+
+    #[test]
+    fn grain_around_the_color() {
+        let image = gray(64, 64, 0.5);
+        assert!(max_difference(&film_grain(&image, 0.0, 1.5, 3.0), &image) < 1e-6);
+
+        let result = film_grain(&image, 0.2, 1.0, 3.0);
+        assert!(max_difference(&result, &image) <= 0.1);
+        let mean = result.pixels().map(|p| p.0[0]).sum::<f32>() / (64.0 * 64.0);
+        assert!((mean - 0.5).abs() < 0.01, "mean {}", mean);
+        // a new pattern every 24th of a second
+        assert!(max_difference(&result, &film_grain(&image, 0.2, 1.0, 3.1)) > 0.0);
+    }
+
+    #[test]
+    fn vignette_darkens_the_corners() {
+        let image = gray(40, 20, 1.0);
+        let uniforms = Effect::Vignette.uniforms();
+        let result = apply(Effect::Vignette, &uniforms, &image).unwrap();
+        assert_eq!(result.get_pixel(20, 10).0, [1.0, 1.0, 1.0, 1.0]);
+        let corner = result.get_pixel(0, 0).0;
+        assert!(corner[0] < 0.3, "corner {:?}", corner);
+        assert_eq!(corner[3], 1.0);
+    }
+
+    #[test]
+    fn only_some_effects_on_the_cpu() {
+        let image = gray(4, 4, 0.5);
+        let uniforms = Effect::Halftone.uniforms();
+        assert!(apply(Effect::Halftone, &uniforms, &image).is_none());
+    }
+
+    #[test]
+    fn noise_displacement_is_deterministic() {
+        let image = gray(24, 16, 0.5);
+        let a = noise_displacement(&image, 1.0);
+        assert_eq!(a.dimensions(), (24, 16));
+        assert_eq!(max_difference(&a, &noise_displacement(&image, 1.0)), 0.0);
+        assert!(max_difference(&a, &noise_displacement(&image, 2.0)) > 0.0);
+    }
+}
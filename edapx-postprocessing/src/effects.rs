@@ -0,0 +1,190 @@
+// The built-in effects, each one is a shader with its default parameters:
+//
+// chain.add_effect(device, Effect::Vignette);
+// chain.set("vignette", "radius", 1.2)?;
+//
+// The effects that do not read the previous frame work with `PostProcessingEffect` too:
+//
+// PostProcessingEffect::new(size, samples, device, output, Effect::Halftone.source(), Effect::Halftone.uniforms())
+//
+// The color grading needs a look up table, see `EffectChain::add_color_grading`.
+use crate::Uniforms;
+use nannou::image::{Rgba, RgbaImage};
+use nannou::prelude::*;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Effect {
+    Bloom,
+    KawaseBloom,
+    FilmGrain,
+    ChromaticAberration,
+    Vignette,
+    Halftone,
+    Posterize,
+    Trails,
+    Feedback,
+    Kaleidoscope,
+}
+
+pub const EFFECTS: [Effect; 10] = [
+    Effect::Bloom,
+    Effect::KawaseBloom,
+    Effect::FilmGrain,
+    Effect::ChromaticAberration,
+    Effect::Vignette,
+    Effect::Halftone,
+    Effect::Posterize,
+    Effect::Trails,
+    Effect::Feedback,
+    Effect::Kaleidoscope,
+];
+
+impl Effect {
+    // the name of the pass in the chain
+    pub fn name(self) -> &'static str {
+        match self {
+            Effect::Bloom => "bloom",
+            Effect::KawaseBloom => "kawase bloom",
+            Effect::FilmGrain => "film grain",
+            Effect::ChromaticAberration => "chromatic aberration",
+            Effect::Vignette => "vignette",
+            Effect::Halftone => "halftone",
+            Effect::Posterize => "posterize",
+            Effect::Trails => "trails",
+            Effect::Feedback => "feedback",
+            Effect::Kaleidoscope => "kaleidoscope",
+        }
+    }
+
+    pub fn source(self) -> &'static str {
+        match self {
+            Effect::Bloom => include_str!("shaders/effects/bloom.wgsl"),
+            Effect::KawaseBloom => include_str!("shaders/effects/kawase_bloom.wgsl"),
+            Effect::FilmGrain => include_str!("shaders/effects/film_grain.wgsl"),
+            Effect::ChromaticAberration => {
+                include_str!("shaders/effects/chromatic_aberration.wgsl")
+            }
+            Effect::Vignette => include_str!("shaders/effects/vignette.wgsl"),
+            Effect::Halftone => include_str!("shaders/effects/halftone.wgsl"),
+            Effect::Posterize => include_str!("shaders/effects/posterize.wgsl"),
+            Effect::Trails => include_str!("shaders/effects/trails.wgsl"),
+            Effect::Feedback => include_str!("shaders/effects/feedback.wgsl"),
+            Effect::Kaleidoscope => include_str!("shaders/effects/kaleidoscope.wgsl"),
+        }
+    }
+
+    // the default parameters, sizes are in pixels
+    pub fn uniforms(self) -> Uniforms {
+        match self {
+            Effect::Bloom | Effect::KawaseBloom => Uniforms::new()
+                .float("threshold", 0.7, 0.0..=1.0)
+                .float("intensity", 0.8, 0.0..=3.0)
+                .float("radius", 12.0, 1.0..=48.0),
+            Effect::FilmGrain => {
+                Uniforms::new()
+                    .float("amount", 0.08, 0.0..=0.5)
+                    .float("size", 1.5, 1.0..=4.0)
+            }
+            Effect::ChromaticAberration => Uniforms::new().float("amount", 0.01, 0.0..=0.1).vec2(
+                "center",
+                [0.5, 0.5],
+                0.0..=1.0,
+            ),
+            Effect::Vignette => Uniforms::new()
+                .float("radius", 1.1, 0.0..=2.0)
+                .float("softness", 0.6, 0.01..=2.0)
+                .float("strength", 0.8, 0.0..=1.0)
+                .color("color", rgba(0.0, 0.0, 0.0, 1.0)),
+            Effect::Halftone => Uniforms::new()
+                .float("cell", 8.0, 2.0..=40.0)
+                .float("angle", 0.26, 0.0..=FRAC_PI_2)
+                .float("smoothness", 1.0, 0.1..=4.0),
+            Effect::Posterize => {
+                Uniforms::new()
+                    .int("levels", 6, 2..=32)
+                    .float("dither", 1.0, 0.0..=1.0)
+            }
+            Effect::Trails => {
+                Uniforms::new()
+                    .float("decay", 0.92, 0.0..=0.99)
+                    .float("zoom", 1.0, 0.9..=1.1)
+            }
+            // the transformation is applied every frame, the drawing has to be transparent
+            Effect::Feedback => Uniforms::new()
+                .float("decay", 0.9, 0.0..=1.0)
+                .vec2("offset", [0.0, 0.0], -10.0..=10.0)
+                .float("scale", 1.0, 0.9..=1.1)
+                .float("rotation", 0.0, -0.1..=0.1)
+                .float("hue_shift", 0.0, -0.2..=0.2)
+                .color("background", rgba(0.0, 0.0, 0.0, 1.0)),
+            Effect::Kaleidoscope => Uniforms::new()
+                .int("segments", 6, 2..=24)
+                .float("angle", 0.0, 0.0..=TAU)
+                .vec2("center", [0.5, 0.5], 0.0..=1.0),
+        }
+    }
+
+    // reads its own result of the previous frame, only in a chain
+    pub fn feedback(self) -> bool {
+        self == Effect::Trails || self == Effect::Feedback
+    }
+}
+
+pub(crate) const COLOR_GRADING_SOURCE: &str = include_str!("shaders/effects/color_grading.wgsl");
+
+pub(crate) fn color_grading_uniforms() -> Uniforms {
+    Uniforms::new().float("strength", 1.0, 0.0..=1.0)
+}
+
+// A look up table that does not change the colors, `size` values for every channel. Edit it in an
+// image editor to make a grading: load a screenshot and this table, apply the same adjustments to
+// both and save the table.
+pub fn neutral_lut(size: u32) -> RgbaImage {
+    assert!(
+        size >= 2,
+        "a look up table needs at least 2 values per channel"
+    );
+    let value = |v: u32| (v * 255 / (size - 1)) as u8;
+    RgbaImage::from_fn(size * size, size, |x, y| {
+        Rgba([value(x % size), value(y), value(x / size), 255])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shaders_use_declared_uniforms() {
+        for effect in EFFECTS.iter() {
+            let uniforms = effect.uniforms();
+            let source = effect.source();
+            assert!(source.contains("fn main("), "{} has no main", effect.name());
+            for (i, _) in source.match_indices("uniforms.") {
+                let name: String = source[i + "uniforms.".len()..]
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                assert!(
+                    uniforms.get(&name).is_some(),
+                    "{} reads the undeclared uniform {}",
+                    effect.name(),
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn neutral_lut_layout() {
+        let lut = neutral_lut(16);
+        assert_eq!(lut.dimensions(), (256, 16));
+        // red grows in a square, green downwards and blue from a square to the next one
+        assert_eq!(lut.get_pixel(0, 0), &Rgba([0, 0, 0, 255]));
+        assert_eq!(lut.get_pixel(15, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(lut.get_pixel(0, 15), &Rgba([0, 255, 0, 255]));
+        assert_eq!(lut.get_pixel(16 * 15, 0), &Rgba([0, 0, 255, 255]));
+        assert_eq!(lut.get_pixel(16 * 5 + 3, 7), &Rgba([51, 119, 85, 255]));
+    }
+}
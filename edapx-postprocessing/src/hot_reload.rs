@@ -0,0 +1,186 @@
+// Shaders loaded from disk, see `PostProcessingEffect::from_path`. The file is polled at every
+// update, reading its modification time is cheap and needs no watcher thread. A new shader is
+// validated before it reaches wgpu, wgpu panics on a shader that does not compile, so the last
+// good pipeline keeps running and the error is shown in an overlay.
+use nannou::prelude::*;
+use std::cell::RefCell;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub(crate) struct ShaderFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ShaderFile {
+    // the file and its content
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<(Self, String)> {
+        let mut file = ShaderFile {
+            path: path.as_ref().to_path_buf(),
+            modified: None,
+        };
+        let source = file.read()?;
+        Ok((file, source))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // the new content if the file changed since the last read
+    pub fn changed(&mut self) -> Option<io::Result<String>> {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok();
+        // an editor can remove the file for a moment while saving
+        if modified.is_none() || modified == self.modified {
+            return None;
+        }
+        Some(self.read())
+    }
+
+    fn read(&mut self) -> io::Result<String> {
+        self.modified = std::fs::metadata(&self.path)?.modified().ok();
+        std::fs::read_to_string(&self.path)
+    }
+}
+
+// Parses and validates a whole shader, the generated header included. The shader also has to
+// fit the pipeline, that wgpu would reject with a panic: a fragment `main` and only the bindings
+// of the layout, from 0 to `last_binding` in group 0.
+pub(crate) fn validate(source: &str, last_binding: u32) -> Result<(), String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::empty(),
+    )
+    .validate(&module)
+    .map_err(|e| e.to_string())?;
+
+    let has_main = module
+        .entry_points
+        .iter()
+        .any(|entry| entry.name == "main" && entry.stage == naga::ShaderStage::Fragment);
+    if !has_main {
+        return Err("the shader has no fragment entry point called main".to_string());
+    }
+    for (_, global) in module.global_variables.iter() {
+        if let Some(ref binding) = global.binding {
+            if binding.group != 0 || binding.binding > last_binding {
+                return Err(format!(
+                    "{} is bound to group {} binding {}, only group 0 bindings 0 to {} exist",
+                    global.name.as_deref().unwrap_or("a variable"),
+                    binding.group,
+                    binding.binding,
+                    last_binding
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Draws an error on top of the frame. The renderer is created with the first error.
+#[derive(Default)]
+pub(crate) struct ErrorOverlay {
+    renderer: RefCell<Option<nannou::draw::Renderer>>,
+}
+
+impl ErrorOverlay {
+    pub fn view(&self, frame: &Frame, error: &str) {
+        let [w, h] = frame.texture_size();
+        let device = frame.device_queue_pair().device();
+        let mut renderer = self.renderer.borrow_mut();
+        let renderer = renderer.get_or_insert_with(|| {
+            nannou::draw::RendererBuilder::new().build(
+                device,
+                [w, h],
+                1.0,
+                frame.texture_msaa_samples(),
+                Frame::TEXTURE_FORMAT,
+            )
+        });
+
+        // the coordinates are in pixels, the origin in the middle of the frame
+        let draw = nannou::Draw::new();
+        let area = Rect::from_w_h(w as f32, h as f32).pad(20.0);
+        draw.rect()
+            .xy(area.xy())
+            .wh(area.wh())
+            .color(rgba(0.0, 0.0, 0.0, 0.8));
+        draw.text(error)
+            .xy(area.xy())
+            .wh(area.pad(10.0).wh())
+            .font_size(14)
+            .left_justify()
+            .align_text_top()
+            .color(rgb(1.0, 0.3, 0.3));
+        renderer.render_to_frame(device, &draw, 1.0, frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effects::{color_grading_uniforms, COLOR_GRADING_SOURCE};
+    use crate::{fragment_shader, Uniforms, EFFECTS, SAMPLE_COUNTS};
+
+    #[test]
+    fn changed_after_a_write() {
+        let path = std::env::temp_dir().join("edapx_postprocessing_hot_reload.wgsl");
+        std::fs::write(&path, "// first").unwrap();
+        let (mut file, source) = ShaderFile::open(&path).unwrap();
+        assert_eq!(source, "// first");
+        assert!(file.changed().is_none());
+
+        // the modification time has to move forward
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        std::fs::write(&path, "// second").unwrap();
+        assert_eq!(file.changed().unwrap().unwrap(), "// second");
+        assert!(file.changed().is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let source = fragment_shader("fn main( {", 1, &Uniforms::new());
+        assert!(validate(&source, 2).is_err());
+    }
+
+    #[test]
+    fn shaders_have_to_fit_the_pipeline() {
+        let output = "[[location(0)]] vec4<f32> {\n    return vec4<f32>(1.0);\n}\n";
+        let renamed = format!("[[stage(fragment)]]\nfn fragment() -> {}", output);
+        let source = fragment_shader(&renamed, 1, &Uniforms::new());
+        assert!(validate(&source, 2).unwrap_err().contains("main"));
+
+        let extra = format!(
+            "[[group(0), binding(3)]]\nvar lut: texture_2d<f32>;\n\n[[stage(fragment)]]\nfn main() -> {}",
+            output
+        );
+        let source = fragment_shader(&extra, 1, &Uniforms::new());
+        assert!(validate(&source, 2).unwrap_err().contains("binding 3"));
+        assert_eq!(validate(&source, 3), Ok(()));
+
+        let other_group = extra.replace("group(0), binding(3)", "group(1), binding(0)");
+        let source = fragment_shader(&other_group, 1, &Uniforms::new());
+        assert!(validate(&source, 3).is_err());
+    }
+
+    #[test]
+    fn effects_are_valid() {
+        for effect in EFFECTS.iter() {
+            for &samples in SAMPLE_COUNTS.iter() {
+                let source = fragment_shader(effect.source(), samples, &effect.uniforms());
+                // the effects reading their previous frame have it at binding 3
+                let last_binding = if effect.feedback() { 3 } else { 2 };
+                if let Err(e) = validate(&source, last_binding) {
+                    panic!("{} with {} samples: {}", effect.name(), samples, e);
+                }
+            }
+        }
+        let source = fragment_shader(COLOR_GRADING_SOURCE, 1, &color_grading_uniforms());
+        assert_eq!(validate(&source, 3), Ok(()));
+    }
+}
@@ -0,0 +1,52 @@
+// Screen space effects: the sketch draws into the texture of the effect, the texture is then
+// rendered to the frame through a fragment shader. `PostProcessingEffect` has a single shader that
+// reads the drawing, `EffectChain` runs a list of passes one after the other.
+//
+// let effect = PostProcessingEffect::new(
+//     texture_size,
+//     window.msaa_samples(),
+//     window.device(),
+//     Output::window(&window),
+//     include_str!("shaders/cell_noise.wgsl"),
+//     Uniforms::new().float("scale", 3.0, 1.0..=10.0),
+// );
+// effect.set("scale", 4.0)?;
+//
+// `PostProcessingEffect::from_path` reads the shader from a file and reloads it when the file
+// changes, a shader that does not compile is shown in an overlay while the last good one runs.
+//
+// The fragment shader receives the texture coordinates at location 0, the bindings of the source
+// and of the uniforms are generated, see `fragment_shader` and `Uniforms`.
+//
+// let mut chain = EffectChain::new(texture_size, sample_count, device, Output::window(&window));
+// chain.add_pass(device, "cell noise", include_str!("shaders/cell_noise.wgsl"), uniforms);
+// chain.add_effect(device, Effect::Vignette);
+// chain.set_enabled("vignette", false);
+// chain.show(ui); // with the "egui" feature
+//
+// The built-in effects are added in a line, with sensible parameters, see `Effect`:
+//
+// chain.add_effect(device, Effect::Bloom);
+// chain.add_color_grading(&window, &neutral_lut(16));
+//
+// The `cpu` module computes some of the effects on images in memory, without a GPU.
+use nannou::wgpu;
+
+mod chain;
+pub mod cpu;
+mod effects;
+mod hot_reload;
+mod post_processing_effect;
+mod uniforms;
+mod wgsl;
+
+pub use chain::{EffectChain, Output, Pass};
+pub use effects::{neutral_lut, Effect, EFFECTS};
+pub use post_processing_effect::PostProcessingEffect;
+pub use uniforms::{Uniform, UniformError, UniformValue, Uniforms};
+pub use wgsl::{fragment_shader, SAMPLE_COUNTS};
+
+// The vertex shader of a quad covering the screen, the one used by every effect.
+pub fn vertex_shader() -> wgpu::ShaderModuleDescriptor<'static> {
+    wgpu::include_wgsl!("shaders/vs.wgsl")
+}
@@ -0,0 +1,393 @@
+// copied from https://github.com/nannou-org/nannou/blob/master/examples/draw/draw_capture_hi_res.rs
+
+use crate::hot_reload::{validate, ErrorOverlay, ShaderFile};
+use crate::{fragment_shader, Output, UniformError, UniformValue, Uniforms};
+use nannou::prelude::*;
+use nannou::wgpu::util::DeviceExt;
+use nannou::wgpu::Device;
+use std::io;
+use std::path::Path;
+
+// the source texture, its sampler and the uniforms, this effect has no texture at binding 3
+const LAST_BINDING: u32 = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub(crate) struct Vertex {
+    pub position: [f32; 2],
+}
+
+// TODO: those vertices can be emitted in the vertex shader.
+pub(crate) const VERTICES: [Vertex; 4] = [
+    Vertex {
+        position: [-1.0, 1.0],
+    },
+    Vertex {
+        position: [-1.0, -1.0],
+    },
+    Vertex {
+        position: [1.0, 1.0],
+    },
+    Vertex {
+        position: [1.0, -1.0],
+    },
+];
+
+pub struct PostProcessingEffect {
+    // The texture that we will draw to.
+    pub texture: wgpu::Texture,
+    // Create a `Draw` instance for drawing to our texture.
+    pub draw: nannou::Draw,
+    // The type used to render the `Draw` vertices to our texture.
+    pub renderer: nannou::draw::Renderer,
+    bind_group: wgpu::BindGroup,
+    render_pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    vertex_buffer: wgpu::Buffer,
+    // the parameters of the shader, written to the uniform buffer by `update_buffer`
+    pub uniforms: Uniforms,
+    // to rebuild the pipeline when the shader is loaded from disk
+    vs_mod: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    src_sample_count: u32,
+    output: Output,
+    shader_file: Option<ShaderFile>,
+    error: Option<String>,
+    overlay: ErrorOverlay,
+}
+
+impl PostProcessingEffect {
+    // `fs_source` is the WGSL of the fragment shader without the bindings, they are generated
+    // for the sample count of the source, see `fragment_shader`. The source can have any sample
+    // count, the output is usually `Output::window`.
+    pub fn new(
+        texture_size: [u32; 2],
+        src_sample_count: u32,
+        device: &Device,
+        output: Output,
+        fs_source: &str,
+        uniforms: Uniforms,
+    ) -> Self {
+        // Create our custom texture.
+        let texture = wgpu::TextureBuilder::new()
+            .size(texture_size)
+            // Our texture will be used as the RENDER_ATTACHMENT for our `Draw` render pass.
+            .usage(wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING)
+            // Use nannou's default multisampling sample count.
+            .sample_count(src_sample_count)
+            // Use a spacious 16-bit linear sRGBA format suitable for high quality drawing.
+            .format(wgpu::TextureFormat::Rgba16Float)
+            // Build it!
+            .build(device);
+
+        // Create our `Draw` instance and a renderer for it.
+        let draw = nannou::Draw::new();
+        let descriptor = texture.descriptor();
+        let renderer =
+            nannou::draw::RendererBuilder::new().build_from_texture_descriptor(device, descriptor);
+
+        // Create the texture view
+        let src_texture = texture.view().build();
+        let src_sample_type = texture.sample_type();
+
+        let vs_mod = device.create_shader_module(&crate::vertex_shader());
+        let fs_mod = shader_module(
+            device,
+            fragment_shader(fs_source, src_sample_count, &uniforms),
+        );
+
+        // Create the sampler for sampling the source texture.
+        let sampler_desc = wgpu::SamplerBuilder::new().into_descriptor();
+        let sampler_filtering = wgpu::sampler_filtering(&sampler_desc);
+        let sampler = device.create_sampler(&sampler_desc);
+
+        // Create the render pipeline.
+        let bind_group_layout = bind_group_layout(
+            device,
+            src_sample_count,
+            src_sample_type,
+            sampler_filtering,
+            None,
+        );
+        let pipeline_layout = pipeline_layout(device, &bind_group_layout);
+        let render_pipeline = render_pipeline(
+            device,
+            &pipeline_layout,
+            &vs_mod,
+            &fs_mod,
+            output.sample_count,
+            output.format,
+        );
+
+        // create the buffer containing the uniforms
+        let uniform_buffer = uniform_buffer(device, &uniforms);
+
+        // Create the bind group.
+        let bind_group = bind_group(
+            device,
+            &bind_group_layout,
+            &src_texture,
+            &sampler,
+            &uniform_buffer,
+            None,
+        );
+
+        // Create the vertex buffer. TODO, remove this and emit vertices in the vertex shader
+        let vertex_buffer = vertex_buffer(device);
+
+        PostProcessingEffect {
+            texture,
+            draw,
+            renderer,
+            bind_group,
+            render_pipeline,
+            uniform_buffer,
+            vertex_buffer,
+            uniforms,
+            vs_mod,
+            pipeline_layout,
+            src_sample_count,
+            output,
+            shader_file: None,
+            error: None,
+            overlay: ErrorOverlay::default(),
+        }
+    }
+
+    // Like `new`, with the fragment shader read from `path`. The file is read again when it
+    // changes, see `update`. If it does not compile the drawing is shown as it is, with the error.
+    pub fn from_path<P: AsRef<Path>>(
+        texture_size: [u32; 2],
+        src_sample_count: u32,
+        device: &Device,
+        output: Output,
+        path: P,
+        uniforms: Uniforms,
+    ) -> io::Result<Self> {
+        let (file, source) = ShaderFile::open(path)?;
+        let error = validate(
+            &fragment_shader(&source, src_sample_count, &uniforms),
+            LAST_BINDING,
+        )
+        .err();
+        let fs_source = match error {
+            Some(_) => include_str!("shaders/copy.wgsl"),
+            None => &source,
+        };
+        let mut effect = Self::new(
+            texture_size,
+            src_sample_count,
+            device,
+            output,
+            fs_source,
+            uniforms,
+        );
+        effect.error = error.map(|e| format!("{}\n{}", file.path().display(), e));
+        effect.shader_file = Some(file);
+        Ok(effect)
+    }
+
+    // the compile error of the shader loaded from disk, the last good one is running
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    // Rebuilds the pipeline if the shader file changed.
+    fn reload(&mut self, device: &Device) {
+        let file = match self.shader_file.as_mut() {
+            Some(file) => file,
+            None => return,
+        };
+        let path = file.path().display().to_string();
+        let source = match file.changed() {
+            None => return,
+            Some(Ok(source)) => source,
+            Some(Err(e)) => {
+                self.error = Some(format!("{}\n{}", path, e));
+                return;
+            }
+        };
+        let source = fragment_shader(&source, self.src_sample_count, &self.uniforms);
+        match validate(&source, LAST_BINDING) {
+            Ok(()) => {
+                let fs_mod = shader_module(device, source);
+                self.render_pipeline = render_pipeline(
+                    device,
+                    &self.pipeline_layout,
+                    &self.vs_mod,
+                    &fs_mod,
+                    self.output.sample_count,
+                    self.output.format,
+                );
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("{}\n{}", path, e)),
+        }
+    }
+
+    // receive an encoder and submit a render pass command for writing the src texture to the dst texture.
+    pub fn encode_render_pass(
+        &self,
+        dst_texture: &wgpu::TextureViewHandle,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(dst_texture, |color| color)
+            .begin(encoder);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        let vertex_range = 0..VERTICES.len() as u32;
+        let instance_range = 0..1;
+        render_pass.draw(vertex_range, instance_range);
+    }
+
+    // Renders the drawing to the texture, after reloading the shader if it was loaded from disk.
+    pub fn update(&mut self, window: &Window, device: &Device) {
+        self.reload(device);
+        let ce_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("texture renderer"),
+        };
+        let mut encoder = device.create_command_encoder(&ce_desc);
+        self.renderer
+            .render_to_texture(device, &mut encoder, &self.draw, &self.texture);
+
+        // Submit the commands for our drawing .
+        window.queue().submit(Some(encoder.finish()));
+    }
+
+    // writes the time and the parameters
+    pub fn update_buffer(&mut self, window: &Window, val: f32) {
+        self.uniforms.set_time(val);
+        write_uniforms(window, &self.uniform_buffer, &self.uniforms);
+    }
+
+    pub fn set<V: Into<UniformValue>>(&mut self, name: &str, value: V) -> Result<(), UniformError> {
+        self.uniforms.set(name, value)
+    }
+
+    // Draw into the given `Frame`, with the compile error on top if there is one.
+    pub fn view(&self, frame: Frame) {
+        // Sample the texture and write it to the frame.
+        {
+            let mut encoder = frame.command_encoder();
+            self.encode_render_pass(frame.texture_view(), &mut encoder);
+        }
+        if let Some(error) = &self.error {
+            self.overlay.view(&frame, error);
+        }
+    }
+}
+
+pub(crate) fn shader_module(device: &wgpu::Device, source: String) -> wgpu::ShaderModule {
+    device.create_shader_module(&wgpu::ShaderModuleDescriptor {
+        label: Some("fragment shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    })
+}
+
+pub(crate) fn uniform_buffer(device: &wgpu::Device, uniforms: &Uniforms) -> wgpu::Buffer {
+    device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: &uniforms.to_bytes(),
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    })
+}
+
+pub(crate) fn write_uniforms(window: &Window, uniform_buffer: &wgpu::Buffer, uniforms: &Uniforms) {
+    window
+        .queue()
+        .write_buffer(uniform_buffer, 0, &uniforms.to_bytes());
+}
+
+pub(crate) fn vertex_buffer(device: &wgpu::Device) -> wgpu::Buffer {
+    let vertices_bytes = vertices_as_bytes(&VERTICES[..]);
+    device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: vertices_bytes,
+        usage: wgpu::BufferUsages::VERTEX,
+    })
+}
+
+pub(crate) fn pipeline_layout(
+    device: &wgpu::Device,
+    bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::PipelineLayout {
+    let desc = wgpu::PipelineLayoutDescriptor {
+        label: Some("nannou_Effect"),
+        bind_group_layouts: &[bind_group_layout],
+        push_constant_ranges: &[],
+    };
+    device.create_pipeline_layout(&desc)
+}
+
+pub(crate) fn render_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    vs_mod: &wgpu::ShaderModule,
+    fs_mod: &wgpu::ShaderModule,
+    dst_sample_count: u32,
+    dst_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    wgpu::RenderPipelineBuilder::from_layout(layout, vs_mod)
+        .fragment_shader(fs_mod)
+        .color_format(dst_format)
+        .color_blend(wgpu::BlendComponent::REPLACE)
+        .alpha_blend(wgpu::BlendComponent::REPLACE)
+        .add_vertex_buffer::<Vertex>(&wgpu::vertex_attr_array![0 => Float32x2])
+        .primitive_topology(wgpu::PrimitiveTopology::TriangleStrip)
+        .sample_count(dst_sample_count)
+        .build(device)
+}
+
+fn vertices_as_bytes(data: &[Vertex]) -> &[u8] {
+    unsafe { wgpu::bytes::from_slice(data) }
+}
+
+pub(crate) fn bind_group_layout(
+    device: &wgpu::Device,
+    src_sample_count: u32,
+    src_sample_type: wgpu::TextureSampleType,
+    sampler_filtering: bool,
+    extra_sample_type: Option<wgpu::TextureSampleType>,
+) -> wgpu::BindGroupLayout {
+    let mut builder = wgpu::BindGroupLayoutBuilder::new()
+        .texture(
+            wgpu::ShaderStages::FRAGMENT,
+            src_sample_count > 1,
+            wgpu::TextureViewDimension::D2,
+            src_sample_type,
+        )
+        .sampler(wgpu::ShaderStages::FRAGMENT, sampler_filtering);
+
+    builder = builder.uniform_buffer(wgpu::ShaderStages::FRAGMENT, false);
+    // a texture at binding 3, like the look up table of the color grading
+    if let Some(sample_type) = extra_sample_type {
+        builder = builder.texture(
+            wgpu::ShaderStages::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+            sample_type,
+        );
+    }
+    builder.build(device)
+}
+pub(crate) fn bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    texture: &wgpu::TextureViewHandle,
+    sampler: &wgpu::Sampler,
+    uniform_buffer: &wgpu::Buffer,
+    extra: Option<&wgpu::TextureViewHandle>,
+) -> wgpu::BindGroup {
+    let mut builder = wgpu::BindGroupBuilder::new()
+        .texture_view(texture)
+        .sampler(sampler);
+
+    // the whole buffer, its size depends on the parameters of the shader
+    builder = builder.buffer_bytes(uniform_buffer, 0, None);
+    if let Some(view) = extra {
+        builder = builder.texture_view(view);
+    }
+    builder.build(device, layout)
+}
@@ -0,0 +1,11 @@
+// The drawing as it is, used by the chain when every pass is disabled.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    return FragmentOutput(source_uv(tex_coords));
+}
@@ -0,0 +1,32 @@
+// Gaussian bloom: the parts of the image brighter than the threshold are blurred on a grid of
+// 9x9 taps spread over the radius and added to the image.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+// the part of the color above the threshold
+fn bright(uv: vec2<f32>) -> vec3<f32> {
+    let c = source_uv(uv).rgb;
+    return max(c - vec3<f32>(uniforms.threshold), vec3<f32>(0.0, 0.0, 0.0));
+}
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let texel = 1.0 / vec2<f32>(source_size());
+    let spacing = uniforms.radius / 4.0;
+    var sum: vec3<f32> = vec3<f32>(0.0, 0.0, 0.0);
+    var total: f32 = 0.0;
+    for (var y: i32 = -4; y <= 4; y = y + 1) {
+        for (var x: i32 = -4; x <= 4; x = x + 1) {
+            let d = vec2<f32>(f32(x), f32(y)) / 4.0;
+            // a sigma of half the radius
+            let weight = exp(-2.0 * dot(d, d));
+            sum = sum + bright(tex_coords + vec2<f32>(f32(x), f32(y)) * spacing * texel) * weight;
+            total = total + weight;
+        }
+    }
+    let color = source_uv(tex_coords);
+    return FragmentOutput(vec4<f32>(color.rgb + sum / total * uniforms.intensity, color.a));
+}
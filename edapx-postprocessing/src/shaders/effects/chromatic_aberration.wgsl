@@ -0,0 +1,16 @@
+// Chromatic aberration: red and blue are shifted away from the center, in opposite directions,
+// more on the borders.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let shift = (tex_coords - uniforms.center) * uniforms.amount;
+    let color = source_uv(tex_coords);
+    let r = source_uv(tex_coords + shift).r;
+    let b = source_uv(tex_coords - shift).b;
+    return FragmentOutput(vec4<f32>(r, color.g, b, color.a));
+}
@@ -0,0 +1,31 @@
+// Color grading with a look up table. The table is a strip of `n` squares of `n` x `n` pixels,
+// red grows to the right in a square, green to the bottom and blue from a square to the next one,
+// see `neutral_lut`.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+[[group(0), binding(3)]]
+var lut: texture_2d<f32>;
+
+fn grade(c: vec3<f32>) -> vec3<f32> {
+    let n = f32(textureDimensions(lut).y);
+    let size = vec2<f32>(n * n, n);
+    let v = clamp(c, vec3<f32>(0.0, 0.0, 0.0), vec3<f32>(1.0, 1.0, 1.0)) * (n - 1.0);
+    // the two squares around blue, red and green at the center of the pixels
+    let slice = floor(v.b);
+    let next = min(slice + 1.0, n - 1.0);
+    let rg = v.rg + vec2<f32>(0.5, 0.5);
+    let low = textureSample(lut, tex_sampler, (vec2<f32>(slice * n, 0.0) + rg) / size).rgb;
+    let high = textureSample(lut, tex_sampler, (vec2<f32>(next * n, 0.0) + rg) / size).rgb;
+    return mix(low, high, vec3<f32>(v.b - slice));
+}
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let color = source_uv(tex_coords);
+    let rgb = mix(color.rgb, grade(color.rgb), vec3<f32>(uniforms.strength));
+    return FragmentOutput(vec4<f32>(rgb, color.a));
+}
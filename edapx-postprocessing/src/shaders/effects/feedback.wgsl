@@ -0,0 +1,43 @@
+// Feedback: the result of the previous frame is drawn back under the drawing. It is moved by
+// `offset` pixels, scaled and rotated around the center, shifted in hue and it fades to the
+// background by `decay`. The drawing has to be cleared with a transparent color: a decay of 0.0
+// leaves only the background, like clearing every frame, 1.0 keeps everything that was drawn.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+[[group(0), binding(3)]]
+var history: texture_2d<f32>;
+
+// a rotation around the gray axis
+fn hue_shift(c: vec3<f32>, angle: f32) -> vec3<f32> {
+    let k = vec3<f32>(0.57735, 0.57735, 0.57735);
+    let cos_a = cos(angle);
+    return c * cos_a + cross(k, c) * sin(angle) + k * dot(k, c) * (1.0 - cos_a);
+}
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let size = vec2<f32>(source_size());
+    let aspect = vec2<f32>(size.x / size.y, 1.0);
+
+    // where the pixel was in the previous frame, the transformation inverted
+    let p = (tex_coords - vec2<f32>(0.5, 0.5) - uniforms.offset / size) * aspect;
+    let c = cos(-uniforms.rotation);
+    let s = sin(-uniforms.rotation);
+    let rotated = vec2<f32>(c * p.x - s * p.y, s * p.x + c * p.y) / uniforms.scale;
+    let previous_uv = rotated / aspect + vec2<f32>(0.5, 0.5);
+    let sampled = textureSample(history, tex_sampler, previous_uv);
+    // outside of the previous frame there is only the background
+    let inside = step(0.0, previous_uv.x) * step(previous_uv.x, 1.0)
+        * step(0.0, previous_uv.y) * step(previous_uv.y, 1.0);
+    let previous = mix(uniforms.background, sampled, vec4<f32>(inside));
+
+    let shifted = vec4<f32>(hue_shift(previous.rgb, uniforms.hue_shift), previous.a);
+    let faded = mix(uniforms.background, shifted, vec4<f32>(uniforms.decay));
+    // the drawing over what remains of the previous frames
+    let color = source_uv(tex_coords);
+    return FragmentOutput(color + faded * (1.0 - color.a));
+}
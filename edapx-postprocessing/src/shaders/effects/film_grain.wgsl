@@ -0,0 +1,24 @@
+// Film grain: noise added to the image, a new pattern 24 times per second.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let color = source_uv(tex_coords);
+    // grains of `size` pixels
+    let cell = floor(tex_coords * vec2<f32>(source_size()) / uniforms.size);
+    // the frames repeat after a while, the hash loses precision with big numbers
+    let frame = floor(uniforms.time * 24.0) % 64.0;
+    let n = hash(cell + vec2<f32>(frame * 17.0, frame * 31.0)) - 0.5;
+    // less grain in the highlights, like on film
+    let luma = dot(color.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let amount = uniforms.amount * (1.0 - 0.5 * clamp(luma, 0.0, 1.0));
+    return FragmentOutput(vec4<f32>(color.rgb + vec3<f32>(n * amount), color.a));
+}
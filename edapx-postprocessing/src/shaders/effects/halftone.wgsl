@@ -0,0 +1,34 @@
+// Halftone: a grid of dots for each channel, every grid rotated by a different angle. The bigger
+// the value of the channel at the center of a cell, the bigger the dot.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+fn rotate(p: vec2<f32>, angle: f32) -> vec2<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec2<f32>(c * p.x - s * p.y, s * p.x + c * p.y);
+}
+
+// how much the pixel is covered by the dot of its cell, `channel` selects the channel
+fn halftone(px: vec2<f32>, angle: f32, channel: vec3<f32>) -> f32 {
+    let p = rotate(px, angle) / uniforms.cell;
+    let center = floor(p) + vec2<f32>(0.5, 0.5);
+    let value = dot(source(vec2<i32>(rotate(center * uniforms.cell, -angle))).rgb, channel);
+    // a full channel covers the whole cell
+    let radius = sqrt(clamp(value, 0.0, 1.0)) * 0.7071;
+    let aa = uniforms.smoothness / uniforms.cell;
+    return 1.0 - smoothStep(radius - aa, radius + aa, length(p - center));
+}
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let px = tex_coords * vec2<f32>(source_size());
+    let a = uniforms.angle;
+    let r = halftone(px, a, vec3<f32>(1.0, 0.0, 0.0));
+    let g = halftone(px, a + 0.5236, vec3<f32>(0.0, 1.0, 0.0));
+    let b = halftone(px, a + 1.0472, vec3<f32>(0.0, 0.0, 1.0));
+    return FragmentOutput(vec4<f32>(r, g, b, source_uv(tex_coords).a));
+}
@@ -0,0 +1,20 @@
+// Kaleidoscope: the image around the center is cut in `segments` wedges, every wedge mirrors the
+// first one. `angle` rotates the wedge that is mirrored.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let size = vec2<f32>(source_size());
+    let aspect = vec2<f32>(size.x / size.y, 1.0);
+    let p = (tex_coords - uniforms.center) * aspect;
+    let sector = 6.2831853 / f32(max(uniforms.segments, 1));
+    var a: f32 = atan2(p.y, p.x) - uniforms.angle;
+    a = a - sector * floor(a / sector);
+    a = min(a, sector - a);
+    let uv = vec2<f32>(cos(a + uniforms.angle), sin(a + uniforms.angle)) * length(p) / aspect;
+    return FragmentOutput(source_uv(uniforms.center + uv));
+}
@@ -0,0 +1,28 @@
+// Kawase bloom: the bright parts are blurred with four rounds of diagonal taps, each round a bit
+// further away, and added to the image. Cheaper than the gaussian bloom and softer.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+fn bright(uv: vec2<f32>) -> vec3<f32> {
+    let c = source_uv(uv).rgb;
+    return max(c - vec3<f32>(uniforms.threshold), vec3<f32>(0.0, 0.0, 0.0));
+}
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let texel = 1.0 / vec2<f32>(source_size());
+    var sum: vec3<f32> = bright(tex_coords);
+    for (var i: i32 = 0; i < 4; i = i + 1) {
+        // the offsets of the kawase rounds, 0.5, 1.5, 2.5 and 3.5 times a quarter of the radius
+        let o = (f32(i) + 0.5) * uniforms.radius / 4.0 * texel;
+        sum = sum + bright(tex_coords + vec2<f32>(o.x, o.y));
+        sum = sum + bright(tex_coords + vec2<f32>(-o.x, o.y));
+        sum = sum + bright(tex_coords + vec2<f32>(o.x, -o.y));
+        sum = sum + bright(tex_coords + vec2<f32>(-o.x, -o.y));
+    }
+    let color = source_uv(tex_coords);
+    return FragmentOutput(vec4<f32>(color.rgb + sum / 17.0 * uniforms.intensity, color.a));
+}
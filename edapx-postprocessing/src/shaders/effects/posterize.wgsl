@@ -0,0 +1,26 @@
+// Posterize: every channel is reduced to `levels` values. With `dither` the steps are broken with
+// a 4x4 ordered dithering.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+// the 4x4 bayer matrix between 0.0 and 1.0, the bits of x ^ y and y interleaved and reversed
+fn bayer(p: vec2<i32>) -> f32 {
+    let x = u32(p.x) & 3u;
+    let y = u32(p.y) & 3u;
+    let xy = x ^ y;
+    let v = ((xy & 1u) << 3u) | ((y & 1u) << 2u) | (xy & 2u) | ((y & 2u) >> 1u);
+    return (f32(v) + 0.5) / 16.0;
+}
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let color = source_uv(tex_coords);
+    let px = vec2<i32>(tex_coords * vec2<f32>(source_size()));
+    let n = f32(max(uniforms.levels, 2) - 1);
+    let threshold = (bayer(px) - 0.5) * uniforms.dither;
+    let rgb = floor(color.rgb * n + vec3<f32>(0.5 + threshold)) / n;
+    return FragmentOutput(vec4<f32>(clamp(rgb, vec3<f32>(0.0, 0.0, 0.0), vec3<f32>(1.0, 1.0, 1.0)), color.a));
+}
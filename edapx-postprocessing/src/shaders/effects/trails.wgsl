@@ -0,0 +1,18 @@
+// Feedback trails: the result of the previous frame fades by `decay` and stays behind what is
+// brighter. A `zoom` different from 1.0 makes the trails grow or shrink from the center.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+[[group(0), binding(3)]]
+var history: texture_2d<f32>;
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let color = source_uv(tex_coords);
+    let previous_uv = (tex_coords - vec2<f32>(0.5, 0.5)) / uniforms.zoom + vec2<f32>(0.5, 0.5);
+    let previous = textureSample(history, tex_sampler, previous_uv) * uniforms.decay;
+    return FragmentOutput(max(color, previous));
+}
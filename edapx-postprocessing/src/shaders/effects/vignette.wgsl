@@ -0,0 +1,17 @@
+// Vignette: the borders fade to the color. The distance is 1.0 in the middle of the top and bottom
+// borders, the image begins to fade at `radius - softness`.
+struct FragmentOutput {
+    [[location(0)]] out_color: vec4<f32>;
+};
+
+[[stage(fragment)]]
+fn main(
+    [[location(0)]] tex_coords: vec2<f32>,
+) -> FragmentOutput {
+    let size = vec2<f32>(source_size());
+    let d = (tex_coords - vec2<f32>(0.5, 0.5)) * vec2<f32>(size.x / size.y, 1.0) * 2.0;
+    let fade = smoothStep(uniforms.radius - uniforms.softness, uniforms.radius, length(d));
+    let color = source_uv(tex_coords);
+    let rgb = mix(color.rgb, uniforms.color.rgb, vec3<f32>(fade * uniforms.strength * uniforms.color.a));
+    return FragmentOutput(vec4<f32>(rgb, color.a));
+}
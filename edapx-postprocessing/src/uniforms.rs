@@ -0,0 +1,388 @@
+// The named parameters of a shader, written in the uniform buffer with the alignment rules of
+// WGSL. "time" is always the first one, the effect sets it every frame.
+//
+// let uniforms = Uniforms::new()
+//     .float("strength", 0.14, 0.0..=0.5)
+//     .vec2("center", [0.5, 0.5], 0.0..=1.0)
+//     .color("tint", rgba(1.0, 0.9, 0.8, 1.0));
+//
+// is read in the shader, with the parameters in the same order, as
+//
+// [[block]]
+// struct Data {
+//     time: f32;
+//     strength: f32;
+//     center: vec2<f32>;
+//     tint: vec4<f32>;
+// };
+use nannou::color::Rgba;
+use nannou::prelude::Vec2;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum UniformValue {
+    Float(f32),
+    Int(i32),
+    Vec2([f32; 2]),
+    Vec4([f32; 4]),
+    // a vec4<f32> in the shader, edited with a color picker
+    Color([f32; 4]),
+}
+
+impl UniformValue {
+    pub fn wgsl_type(&self) -> &'static str {
+        match self {
+            UniformValue::Float(_) => "f32",
+            UniformValue::Int(_) => "i32",
+            UniformValue::Vec2(_) => "vec2<f32>",
+            UniformValue::Vec4(_) | UniformValue::Color(_) => "vec4<f32>",
+        }
+    }
+
+    // alignment and size in the uniform address space
+    fn align_and_size(&self) -> (usize, usize) {
+        match self {
+            UniformValue::Float(_) | UniformValue::Int(_) => (4, 4),
+            UniformValue::Vec2(_) => (8, 8),
+            UniformValue::Vec4(_) | UniformValue::Color(_) => (16, 16),
+        }
+    }
+
+    fn write(&self, bytes: &mut [u8]) {
+        let floats: &[f32] = match self {
+            UniformValue::Int(v) => {
+                bytes[..4].copy_from_slice(&v.to_le_bytes());
+                return;
+            }
+            UniformValue::Float(v) => std::slice::from_ref(v),
+            UniformValue::Vec2(v) => v,
+            UniformValue::Vec4(v) | UniformValue::Color(v) => v,
+        };
+        for (i, f) in floats.iter().enumerate() {
+            bytes[i * 4..i * 4 + 4].copy_from_slice(&f.to_le_bytes());
+        }
+    }
+
+    fn clamp(self, (min, max): (f32, f32)) -> Self {
+        match self {
+            UniformValue::Float(v) => UniformValue::Float(v.clamp(min, max)),
+            UniformValue::Int(v) => UniformValue::Int(v.clamp(min as i32, max as i32)),
+            UniformValue::Vec2(v) => {
+                UniformValue::Vec2([v[0].clamp(min, max), v[1].clamp(min, max)])
+            }
+            UniformValue::Vec4(v) => UniformValue::Vec4([
+                v[0].clamp(min, max),
+                v[1].clamp(min, max),
+                v[2].clamp(min, max),
+                v[3].clamp(min, max),
+            ]),
+            UniformValue::Color(v) => UniformValue::Color(v),
+        }
+    }
+}
+
+impl From<f32> for UniformValue {
+    fn from(v: f32) -> Self {
+        UniformValue::Float(v)
+    }
+}
+
+impl From<i32> for UniformValue {
+    fn from(v: i32) -> Self {
+        UniformValue::Int(v)
+    }
+}
+
+impl From<[f32; 2]> for UniformValue {
+    fn from(v: [f32; 2]) -> Self {
+        UniformValue::Vec2(v)
+    }
+}
+
+impl From<Vec2> for UniformValue {
+    fn from(v: Vec2) -> Self {
+        UniformValue::Vec2([v.x, v.y])
+    }
+}
+
+impl From<[f32; 4]> for UniformValue {
+    fn from(v: [f32; 4]) -> Self {
+        UniformValue::Vec4(v)
+    }
+}
+
+impl From<Rgba> for UniformValue {
+    fn from(c: Rgba) -> Self {
+        UniformValue::Color([c.red, c.green, c.blue, c.alpha])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Uniform {
+    pub name: String,
+    pub value: UniformValue,
+    // min and max of every component, the parameters without a range have no slider
+    pub range: Option<(f32, f32)>,
+}
+
+#[derive(Debug)]
+pub enum UniformError {
+    Unknown(String),
+    WrongType {
+        name: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for UniformError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UniformError::Unknown(name) => write!(f, "unknown uniform \"{}\"", name),
+            UniformError::WrongType { name, expected } => {
+                write!(f, "the uniform \"{}\" is a {}", name, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UniformError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Uniforms {
+    uniforms: Vec<Uniform>,
+}
+
+impl Default for Uniforms {
+    fn default() -> Self {
+        Uniforms::new()
+    }
+}
+
+impl Uniforms {
+    // only the time
+    pub fn new() -> Self {
+        Uniforms {
+            uniforms: vec![Uniform {
+                name: "time".to_string(),
+                value: UniformValue::Float(0.0),
+                range: None,
+            }],
+        }
+    }
+
+    pub fn float(self, name: &str, value: f32, range: RangeInclusive<f32>) -> Self {
+        self.with(name, value.into(), Some((*range.start(), *range.end())))
+    }
+
+    pub fn int(self, name: &str, value: i32, range: RangeInclusive<i32>) -> Self {
+        let range = (*range.start() as f32, *range.end() as f32);
+        self.with(name, value.into(), Some(range))
+    }
+
+    pub fn vec2(self, name: &str, value: [f32; 2], range: RangeInclusive<f32>) -> Self {
+        self.with(name, value.into(), Some((*range.start(), *range.end())))
+    }
+
+    pub fn vec4(self, name: &str, value: [f32; 4], range: RangeInclusive<f32>) -> Self {
+        self.with(name, value.into(), Some((*range.start(), *range.end())))
+    }
+
+    pub fn color(self, name: &str, value: Rgba) -> Self {
+        self.with(name, value.into(), None)
+    }
+
+    // a parameter declared twice keeps its first position
+    fn with(mut self, name: &str, value: UniformValue, range: Option<(f32, f32)>) -> Self {
+        let uniform = Uniform {
+            name: name.to_string(),
+            value: range.map_or(value, |r| value.clamp(r)),
+            range,
+        };
+        match self.uniforms.iter_mut().find(|u| u.name == name) {
+            Some(existing) => *existing = uniform,
+            None => self.uniforms.push(uniform),
+        }
+        self
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Uniform> {
+        self.uniforms.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<UniformValue> {
+        self.uniforms
+            .iter()
+            .find(|u| u.name == name)
+            .map(|u| u.value)
+    }
+
+    // The value has to be of the declared type, it is clamped to the range. A vec4 can be set
+    // with a color and a color with a vec4.
+    pub fn set<V: Into<UniformValue>>(&mut self, name: &str, value: V) -> Result<(), UniformError> {
+        let uniform = self
+            .uniforms
+            .iter_mut()
+            .find(|u| u.name == name)
+            .ok_or_else(|| UniformError::Unknown(name.to_string()))?;
+        let value = match (uniform.value, value.into()) {
+            (UniformValue::Float(_), v @ UniformValue::Float(_))
+            | (UniformValue::Int(_), v @ UniformValue::Int(_))
+            | (UniformValue::Vec2(_), v @ UniformValue::Vec2(_)) => v,
+            (UniformValue::Vec4(_), UniformValue::Vec4(v) | UniformValue::Color(v)) => {
+                UniformValue::Vec4(v)
+            }
+            (UniformValue::Color(_), UniformValue::Vec4(v) | UniformValue::Color(v)) => {
+                UniformValue::Color(v)
+            }
+            (declared, _) => {
+                return Err(UniformError::WrongType {
+                    name: name.to_string(),
+                    expected: declared.wgsl_type(),
+                })
+            }
+        };
+        uniform.value = uniform.range.map_or(value, |r| value.clamp(r));
+        Ok(())
+    }
+
+    pub fn set_time(&mut self, time: f32) {
+        self.uniforms[0].value = UniformValue::Float(time);
+    }
+
+    // the offset of every parameter in the buffer
+    pub fn offsets(&self) -> Vec<usize> {
+        let mut offset = 0;
+        self.uniforms
+            .iter()
+            .map(|u| {
+                let (align, size) = u.value.align_and_size();
+                let start = round_up(offset, align);
+                offset = start + size;
+                start
+            })
+            .collect()
+    }
+
+    // The size of the buffer. A struct in the uniform address space is aligned to 16 bytes.
+    pub fn size(&self) -> usize {
+        let end = match (self.offsets().last(), self.uniforms.last()) {
+            (Some(offset), Some(u)) => offset + u.value.align_and_size().1,
+            _ => 0,
+        };
+        round_up(end, 16)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.size()];
+        for (offset, u) in self.offsets().into_iter().zip(self.uniforms.iter()) {
+            u.value.write(&mut bytes[offset..]);
+        }
+        bytes
+    }
+
+    // A row for every parameter, except the time: sliders for the numbers with a range, drag
+    // values for the others, a color picker for the colors. Returns true if something changed.
+    #[cfg(feature = "egui")]
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        for u in self.uniforms.iter_mut().skip(1) {
+            let label = u.name.replace('_', " ");
+            let range = u.range;
+            ui.horizontal(|ui| {
+                ui.label(label);
+                changed |= show_value(ui, &mut u.value, range);
+            });
+        }
+        changed
+    }
+}
+
+#[cfg(feature = "egui")]
+fn show_value(ui: &mut egui::Ui, value: &mut UniformValue, range: Option<(f32, f32)>) -> bool {
+    let drag = |ui: &mut egui::Ui, v: &mut f32| {
+        let widget = match range {
+            Some((min, max)) => egui::DragValue::new(v)
+                .clamp_range(min..=max)
+                .speed((max - min) / 200.0),
+            None => egui::DragValue::new(v).speed(0.01),
+        };
+        ui.add(widget).changed()
+    };
+    match value {
+        UniformValue::Float(v) => match range {
+            Some((min, max)) => ui.add(egui::Slider::new(v, min..=max)).changed(),
+            None => drag(ui, v),
+        },
+        UniformValue::Int(v) => match range {
+            Some((min, max)) => ui
+                .add(egui::Slider::new(v, min as i32..=max as i32))
+                .changed(),
+            None => ui.add(egui::DragValue::new(v)).changed(),
+        },
+        UniformValue::Vec2(v) => v.iter_mut().fold(false, |c, v| drag(ui, v) | c),
+        UniformValue::Vec4(v) => v.iter_mut().fold(false, |c, v| drag(ui, v) | c),
+        UniformValue::Color(v) => ui.color_edit_button_rgba_unmultiplied(v).changed(),
+    }
+}
+
+fn round_up(n: usize, align: usize) -> usize {
+    n.div_ceil(align) * align
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nannou::color::rgba;
+
+    #[test]
+    fn layout() {
+        let uniforms = Uniforms::new()
+            .vec2("center", [0.5, 0.5], 0.0..=1.0)
+            .color("tint", rgba(1.0, 0.5, 0.25, 1.0))
+            .float("strength", 0.2, 0.0..=1.0)
+            .int("steps", 4, 1..=8);
+        // the vec2 is aligned to 8 bytes, the color to 16
+        assert_eq!(uniforms.offsets(), vec![0, 8, 16, 32, 36]);
+        assert_eq!(uniforms.size(), 48);
+        assert_eq!(Uniforms::new().size(), 16);
+
+        let bytes = uniforms.to_bytes();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(&bytes[8..12], &0.5f32.to_le_bytes());
+        assert_eq!(&bytes[20..24], &0.5f32.to_le_bytes());
+        assert_eq!(&bytes[36..40], &4i32.to_le_bytes());
+    }
+
+    #[test]
+    fn set_by_name() {
+        let mut uniforms = Uniforms::new()
+            .float("strength", 0.2, 0.0..=1.0)
+            .vec4("weights", [0.0; 4], -1.0..=1.0)
+            .int("steps", 4, 1..=8);
+        uniforms.set("strength", 0.7).unwrap();
+        assert_eq!(uniforms.get("strength"), Some(UniformValue::Float(0.7)));
+        // clamped to the range
+        uniforms.set("strength", 3.0).unwrap();
+        assert_eq!(uniforms.get("strength"), Some(UniformValue::Float(1.0)));
+        uniforms.set("steps", 100).unwrap();
+        assert_eq!(uniforms.get("steps"), Some(UniformValue::Int(8)));
+        uniforms.set("weights", rgba(2.0, 0.5, 0.0, 1.0)).unwrap();
+        assert_eq!(
+            uniforms.get("weights"),
+            Some(UniformValue::Vec4([1.0, 0.5, 0.0, 1.0]))
+        );
+
+        assert!(matches!(
+            uniforms.set("strength", 1),
+            Err(UniformError::WrongType { .. })
+        ));
+        assert!(matches!(
+            uniforms.set("missing", 1.0),
+            Err(UniformError::Unknown(_))
+        ));
+
+        uniforms.set_time(2.5);
+        assert_eq!(&uniforms.to_bytes()[..4], &2.5f32.to_le_bytes());
+    }
+}
@@ -0,0 +1,103 @@
+// The bindings of the fragment shaders are generated, so the same shader works with any sample
+// count of the source and in a chain. A shader only has its functions and `main`, it reads the
+// source with the generated functions:
+//
+// fn source(coords: vec2<i32>) -> vec4<f32>      the color at the pixel, the samples averaged
+// fn source_uv(uv: vec2<f32>) -> vec4<f32>       the color at the texture coordinates
+// fn source_size() -> vec2<i32>                  the size of the source in pixels
+//
+// and the parameters with `uniforms.time`, `uniforms.strength`, etc.
+use crate::Uniforms;
+
+pub const SAMPLE_COUNTS: [u32; 4] = [1, 2, 4, 8];
+
+// The header with the bindings, the uniforms and the functions reading the source, followed by
+// `source`. Panics if the sample count is not one of SAMPLE_COUNTS.
+pub fn fragment_shader(source: &str, sample_count: u32, uniforms: &Uniforms) -> String {
+    assert!(
+        SAMPLE_COUNTS.contains(&sample_count),
+        "unsupported sample count {}",
+        sample_count
+    );
+    let mut header = String::from("// generated by edapx-postprocessing\n");
+    let texture = if sample_count > 1 {
+        "texture_multisampled_2d<f32>"
+    } else {
+        "texture_2d<f32>"
+    };
+    header.push_str(&format!(
+        "[[group(0), binding(0)]]\nvar tex: {};\n[[group(0), binding(1)]]\nvar tex_sampler: sampler;\n\n",
+        texture
+    ));
+
+    header.push_str("[[block]]\nstruct Data {\n");
+    for u in uniforms.iter() {
+        header.push_str(&format!("    {}: {};\n", u.name, u.value.wgsl_type()));
+    }
+    header.push_str("};\n[[group(0), binding(2)]]\nvar<uniform> uniforms: Data;\n\n");
+
+    header.push_str("fn source_size() -> vec2<i32> {\n    return textureDimensions(tex);\n}\n\n");
+    if sample_count > 1 {
+        header.push_str(&format!(
+            "fn source(coords: vec2<i32>) -> vec4<f32> {{
+    let c = clamp(coords, vec2<i32>(0, 0), source_size() - vec2<i32>(1, 1));
+    var color: vec4<f32> = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    for (var i: i32 = 0; i < {n}; i = i + 1) {{
+        color = color + textureLoad(tex, c, i);
+    }}
+    return color / {n}.0;
+}}
+
+fn source_uv(uv: vec2<f32>) -> vec4<f32> {{
+    return source(vec2<i32>(uv * vec2<f32>(source_size())));
+}}
+",
+            n = sample_count
+        ));
+    } else {
+        // without samples the texture can be filtered
+        header.push_str(
+            "fn source(coords: vec2<i32>) -> vec4<f32> {
+    let c = clamp(coords, vec2<i32>(0, 0), source_size() - vec2<i32>(1, 1));
+    return textureLoad(tex, c, 0);
+}
+
+fn source_uv(uv: vec2<f32>) -> vec4<f32> {
+    return textureSample(tex, tex_sampler, uv);
+}
+",
+        );
+    }
+    header.push_str("\n// end of the generated code\n\n");
+    header + source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_follow_the_sample_count() {
+        let uniforms =
+            Uniforms::new()
+                .float("strength", 0.5, 0.0..=1.0)
+                .vec2("center", [0.5, 0.5], 0.0..=1.0);
+        let body = "fn main() {}";
+        let single = fragment_shader(body, 1, &uniforms);
+        assert!(single.contains("var tex: texture_2d<f32>;"));
+        assert!(single.contains("textureSample(tex, tex_sampler, uv)"));
+        assert!(single.ends_with(body));
+
+        let multi = fragment_shader(body, 8, &uniforms);
+        assert!(multi.contains("var tex: texture_multisampled_2d<f32>;"));
+        assert!(multi.contains("i < 8;"));
+        assert!(multi.contains("return color / 8.0;"));
+        assert!(multi.contains("    time: f32;\n    strength: f32;\n    center: vec2<f32>;\n"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsupported_sample_count() {
+        fragment_shader("", 3, &Uniforms::new());
+    }
+}
@@ -90,6 +90,18 @@ impl Capturer {
         let mut encoder = device.create_command_encoder(&ce_desc);
         self.renderer
             .render_to_texture(device, &mut encoder, &self.draw, &self.texture);
+        window.queue().submit(Some(encoder.finish()));
+
+        self.capture(window, device, elapsed_frames);
+    }
+
+    // Saves the texture without drawing into it, when something else has written it, like the
+    // last pass of an `EffectChain`.
+    pub fn capture(&mut self, window: &Window, device: &Device, elapsed_frames: u64) {
+        let ce_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("texture capturer"),
+        };
+        let mut encoder = device.create_command_encoder(&ce_desc);
 
         // Take a snapshot of the texture. The capturer will do the following:
         //
@@ -100,8 +112,7 @@ impl Capturer {
             .texture_capturer
             .capture(device, &mut encoder, &self.texture);
 
-
-        // Submit the commands for our drawing and texture capture to the GPU.
+        // Submit the commands for our texture capture to the GPU.
         window.queue().submit(Some(encoder.finish()));
 
         if self.is_recording || self.is_taking_screenshot {
@@ -0,0 +1,463 @@
+use crate::scene::Material;
+use nannou::prelude::*;
+pub use ray2d::conic::ConicShape;
+use ray2d::{BoundingVolume, Ray2D};
+
+// intersections closer than this to the origin of the ray are discarded, to avoid
+// that a ray hits again the surface that generated it.
+const MIN_DISTANCE: f64 = 0.001;
+// number of points used to draw a conic and to compute its bounding volume
+pub const CONIC_RESOLUTION: usize = 128;
+
+// A mirror shaped as a conic section. `position` and `rotation` place the local coordinate
+// system of the shape in the scene, `start` and `end` are the parameters delimiting the arc.
+#[derive(Debug)]
+pub struct Conic {
+    pub shape: ConicShape,
+    pub position: Vec2,
+    pub rotation: f32,
+    pub start: f32,
+    pub end: f32,
+    pub material: Material,
+    pub ray_anchor_point: Option<Vec2>,
+    pub bounding_volume: Option<BoundingVolume>,
+}
+
+impl Conic {
+    pub fn new(
+        shape: ConicShape,
+        position: Vec2,
+        rotation: f32,
+        start: f32,
+        end: f32,
+        material: Material,
+    ) -> Self {
+        let mut conic = Conic {
+            shape,
+            position,
+            rotation,
+            start,
+            end,
+            material,
+            ray_anchor_point: None,
+            bounding_volume: None,
+        };
+        conic.ray_anchor_point = Some(conic.focus());
+        conic.bounding_volume = Some(conic.compute_bounding_volume());
+        conic
+    }
+
+    // A parabola with the vertex in `vertex`, opening in the direction given by `rotation`.
+    // `half_aperture` is half of the width of the mirror, measured perpendicularly to the axis.
+    pub fn parabola(
+        vertex: Vec2,
+        rotation: f32,
+        focal_length: f32,
+        half_aperture: f32,
+        material: Material,
+    ) -> Self {
+        Conic::new(
+            ConicShape::Parabola { focal_length },
+            vertex,
+            rotation,
+            -half_aperture,
+            half_aperture,
+            material,
+        )
+    }
+
+    // The parabola passing through `start` and `end`, whose vertex is at distance `depth` from
+    // the middle of the segment start-end. A negative depth bends the mirror on the other side.
+    pub fn parabola_from_endpoints(start: Vec2, end: Vec2, depth: f32, material: Material) -> Self {
+        let chord = end - start;
+        let half_chord = chord.length() / 2.0;
+        let middle = start + chord / 2.0;
+        // the axis is perpendicular to the chord, from the vertex towards the chord.
+        let mut axis = vec2(-chord.y, chord.x).normalize();
+        if depth < 0.0 {
+            axis = -axis;
+        }
+        let depth = depth.abs().max(f32::EPSILON);
+        let focal_length = half_chord * half_chord / (4.0 * depth);
+        Conic::parabola(
+            middle - axis * depth,
+            axis.y.atan2(axis.x),
+            focal_length,
+            half_chord,
+            material,
+        )
+    }
+
+    // A conic defined by a focus, a directrix and the eccentricity: 0 < e < 1 gives an ellipse,
+    // e = 1 a parabola, e > 1 a hyperbola. The directrix is the line passing through
+    // `directrix_point` with direction `directrix_dir`. The arc is the one closest to the
+    // directrix, `half_aperture` is half of its width measured perpendicularly to the axis.
+    pub fn from_focus_and_directrix(
+        focus: Vec2,
+        directrix_point: Vec2,
+        directrix_dir: Vec2,
+        eccentricity: f32,
+        half_aperture: f32,
+        material: Material,
+    ) -> Self {
+        let line_dir = directrix_dir.normalize();
+        let to_focus = focus - directrix_point;
+        // projection of the focus on the directrix
+        let foot = directrix_point + line_dir * to_focus.dot(line_dir);
+        let d = focus.distance(foot);
+        // the axis goes from the directrix towards the focus
+        let axis = (focus - foot).normalize();
+        let rotation = axis.y.atan2(axis.x);
+        let e = eccentricity.max(0.0);
+
+        if (e - 1.0).abs() < f32::EPSILON {
+            let focal_length = d / 2.0;
+            Conic::parabola(
+                focus - axis * focal_length,
+                rotation,
+                focal_length,
+                half_aperture,
+                material,
+            )
+        } else if e < 1.0 {
+            let a = e * d / (1.0 - e * e);
+            let b = a * (1.0 - e * e).sqrt();
+            let c = a * e;
+            // the arc around the vertex (-a, 0)
+            let angle = (half_aperture / b).min(1.0).asin();
+            Conic::new(
+                ConicShape::Ellipse { a, b },
+                focus + axis * c,
+                rotation,
+                PI - angle,
+                PI + angle,
+                material,
+            )
+        } else {
+            let a = e * d / (e * e - 1.0);
+            let b = a * (e * e - 1.0).sqrt();
+            let c = a * e;
+            let t = (half_aperture / b).asinh();
+            Conic::new(
+                ConicShape::Hyperbola { a, b },
+                focus - axis * c,
+                rotation,
+                -t,
+                t,
+                material,
+            )
+        }
+    }
+
+    // The branch of the hyperbola closest to `focus_b`, with foci `focus_a` and `focus_b` and
+    // semi major axis `a`, that has to be smaller than half of the distance between the foci.
+    pub fn hyperbola_from_foci(
+        focus_a: Vec2,
+        focus_b: Vec2,
+        a: f32,
+        half_aperture: f32,
+        material: Material,
+    ) -> Self {
+        let axis = focus_b - focus_a;
+        let c = axis.length() / 2.0;
+        let a = a.min(c * 0.999);
+        let b = (c * c - a * a).sqrt();
+        let t = (half_aperture / b).asinh();
+        Conic::new(
+            ConicShape::Hyperbola { a, b },
+            focus_a + axis / 2.0,
+            axis.y.atan2(axis.x),
+            -t,
+            t,
+            material,
+        )
+    }
+
+    // An arc of ellipse going counterclockwise from `start_angle` to `end_angle`.
+    pub fn ellipse_arc(
+        center: Vec2,
+        radius_x: f32,
+        radius_y: f32,
+        rotation: f32,
+        start_angle: f32,
+        end_angle: f32,
+        material: Material,
+    ) -> Self {
+        Conic::new(
+            ConicShape::Ellipse {
+                a: radius_x,
+                b: radius_y,
+            },
+            center,
+            rotation,
+            start_angle,
+            end_angle,
+            material,
+        )
+    }
+
+    // An arc of the ellipse with foci `focus_a` and `focus_b` and semi major axis `a`, that has
+    // to be bigger than half of the distance between the foci.
+    pub fn ellipse_arc_from_foci(
+        focus_a: Vec2,
+        focus_b: Vec2,
+        a: f32,
+        start_angle: f32,
+        end_angle: f32,
+        material: Material,
+    ) -> Self {
+        let axis = focus_b - focus_a;
+        let c = axis.length() / 2.0;
+        let a = a.max(c * 1.001);
+        let b = (a * a - c * c).sqrt();
+        Conic::ellipse_arc(
+            focus_a + axis / 2.0,
+            a,
+            b,
+            axis.y.atan2(axis.x),
+            start_angle,
+            end_angle,
+            material,
+        )
+    }
+
+    pub fn focus(&self) -> Vec2 {
+        self.to_world(self.shape.focus())
+    }
+
+    pub fn to_local(&self, point: Vec2) -> Vec2 {
+        rotate(point - self.position, -self.rotation)
+    }
+
+    pub fn to_world(&self, point: Vec2) -> Vec2 {
+        self.position + rotate(point, self.rotation)
+    }
+
+    pub fn points(&self, resolution: usize) -> Vec<Vec2> {
+        let resolution = resolution.max(1);
+        (0..=resolution)
+            .map(|i| {
+                let t = self.start + (self.end - self.start) * i as f32 / resolution as f32;
+                self.to_world(self.shape.point(t))
+            })
+            .collect()
+    }
+
+    // true if a point on the curve, in local coordinates, is part of the arc.
+    fn contains(&self, point: Vec2) -> bool {
+        if let ConicShape::Hyperbola { .. } = self.shape {
+            if point.x <= 0.0 {
+                return false;
+            }
+        }
+        let t = self.shape.parameter(point);
+        match self.shape {
+            ConicShape::Ellipse { .. } => {
+                (t - self.start).rem_euclid(2.0 * PI) <= self.end - self.start
+            }
+            _ => t >= self.start && t <= self.end,
+        }
+    }
+
+    // Exact intersection between the ray and the conic, it returns the distance and the surface
+    // normal, that is the gradient of the shape: `Ray2D::refract` and `fresnel` use its side to
+    // know if the ray is entering or leaving the conic.
+    pub fn intersect_ray(&self, ray: &Ray2D) -> Option<(f32, Vec2)> {
+        let orig = self.to_local(ray.orig);
+        let dir = rotate(ray.dir.normalize(), -self.rotation);
+        for t in self.shape.intersect_line(orig, dir).iter().flatten() {
+            if *t > MIN_DISTANCE && t.is_finite() {
+                let local_hit = orig + dir * *t as f32;
+                if self.contains(local_hit) {
+                    let normal = rotate(self.shape.gradient(local_hit), self.rotation).normalize();
+                    return Some((*t as f32, normal));
+                }
+            }
+        }
+        None
+    }
+
+    fn compute_bounding_volume(&self) -> BoundingVolume {
+        let points = self.points(CONIC_RESOLUTION);
+        let mut min = points[0];
+        let mut max = points[0];
+        for p in points.iter() {
+            min = min.min(*p);
+            max = max.max(*p);
+        }
+        // a small margin for the parts of the arc between two samples
+        let radius = (max - min).length() / 2.0 * 1.01 + 1.0;
+        BoundingVolume::Circle {
+            position: (min + max) / 2.0,
+            radius,
+        }
+    }
+}
+
+fn rotate(v: Vec2, angle: f32) -> Vec2 {
+    let (sin, cos) = angle.sin_cos();
+    vec2(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::SurfaceType;
+    use crate::test_helpers::{mirror, ray};
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn parabola_reflects_parallel_rays_into_the_focus() {
+        let parabola = Conic::parabola(vec2(0.0, 0.0), 0.0, 1.0, 4.0, mirror());
+        let r = ray(vec2(10.0, 2.0), vec2(-1.0, 0.0));
+        let (distance, normal) = parabola.intersect_ray(&r).unwrap();
+        assert!((distance - 9.0).abs() < 1e-4);
+        assert_near(normal, vec2(-1.0, 1.0).normalize());
+        let hit = r.orig + r.dir * distance;
+        let reflected = r.reflect(normal);
+        assert_near(reflected, (parabola.focus() - hit).normalize());
+    }
+
+    #[test]
+    fn parabola_misses() {
+        let parabola = Conic::parabola(vec2(0.0, 0.0), 0.0, 1.0, 4.0, mirror());
+        // the curve is behind the ray
+        assert!(parabola
+            .intersect_ray(&ray(vec2(-1.0, 0.0), vec2(-1.0, 0.0)))
+            .is_none());
+        // the line crosses the parabola, but outside of the arc
+        assert!(parabola
+            .intersect_ray(&ray(vec2(10.0, 6.0), vec2(-1.0, 0.0)))
+            .is_none());
+    }
+
+    #[test]
+    fn tangent_hit() {
+        let parabola = Conic::parabola(vec2(0.0, 0.0), 0.0, 1.0, 4.0, mirror());
+        let (distance, normal) = parabola
+            .intersect_ray(&ray(vec2(0.0, -5.0), vec2(0.0, 1.0)))
+            .unwrap();
+        assert!((distance - 5.0).abs() < 1e-4);
+        assert_near(normal, vec2(-1.0, 0.0));
+    }
+
+    #[test]
+    fn hyperbola_uses_one_branch() {
+        let hyperbola =
+            Conic::hyperbola_from_foci(vec2(-5.0, 0.0), vec2(5.0, 0.0), 3.0, 10.0, mirror());
+        // from the center the vertex is hit from the convex side
+        let (distance, normal) = hyperbola
+            .intersect_ray(&ray(vec2(0.0, 0.0), vec2(1.0, 0.0)))
+            .unwrap();
+        assert!((distance - 3.0).abs() < 1e-4);
+        assert_near(normal, vec2(1.0, 0.0));
+        // from the other side the normal is the same
+        let (distance, normal) = hyperbola
+            .intersect_ray(&ray(vec2(10.0, 0.0), vec2(-1.0, 0.0)))
+            .unwrap();
+        assert!((distance - 7.0).abs() < 1e-4);
+        assert_near(normal, vec2(1.0, 0.0));
+        // the other branch is not part of the mirror
+        let (distance, _) = hyperbola
+            .intersect_ray(&ray(vec2(-10.0, 0.0), vec2(1.0, 0.0)))
+            .unwrap();
+        assert!((distance - 13.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ellipse_reflects_from_one_focus_to_the_other() {
+        let (focus_a, focus_b) = (vec2(-4.0, 0.0), vec2(4.0, 0.0));
+        let ellipse = Conic::ellipse_arc_from_foci(focus_a, focus_b, 5.0, 0.0, PI, mirror());
+        let r = ray(focus_a, vec2(4.0, 3.0));
+        let (distance, normal) = ellipse.intersect_ray(&r).unwrap();
+        assert!((distance - 5.0).abs() < 1e-4);
+        assert_near(normal, vec2(0.0, 1.0));
+        assert_near(r.reflect(normal), (focus_b - vec2(0.0, 3.0)).normalize());
+        // the lower half is outside of the arc
+        assert!(ellipse
+            .intersect_ray(&ray(vec2(0.0, 0.0), vec2(0.0, -1.0)))
+            .is_none());
+    }
+
+    #[test]
+    fn ray_leaves_a_refractive_ellipse() {
+        let glass = Material {
+            surface: SurfaceType::Refractive { ior: 1.5 },
+            ..mirror()
+        };
+        let ellipse = Conic::ellipse_arc(vec2(0.0, 0.0), 5.0, 3.0, 0.0, 0.0, 2.0 * PI, glass);
+        let r = ray(vec2(0.0, 0.0), vec2(1.0, 1.0));
+        let (_, normal) = ellipse.intersect_ray(&r).unwrap();
+        // the normal points outside, the ray is leaving the ellipse
+        assert!(normal.dot(r.dir) > 0.0);
+        let refracted = r.refract(normal, 1.5);
+        // Snell's law, from the glass to the air the sine of the angle grows by the ior
+        assert!(refracted.dot(normal) > 0.0);
+        assert!((refracted.perp_dot(normal) - 1.5 * r.dir.perp_dot(normal)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ellipse_arc_across_zero() {
+        let ellipse = Conic::ellipse_arc(vec2(0.0, 0.0), 5.0, 3.0, 0.0, -0.5, 0.5, mirror());
+        assert!(ellipse
+            .intersect_ray(&ray(vec2(0.0, 0.0), vec2(1.0, 0.0)))
+            .is_some());
+        assert!(ellipse
+            .intersect_ray(&ray(vec2(0.0, 0.0), vec2(-1.0, 0.0)))
+            .is_none());
+    }
+
+    #[test]
+    fn parabola_through_two_points() {
+        let (start, end) = (vec2(-4.0, 0.0), vec2(4.0, 0.0));
+        let parabola = Conic::parabola_from_endpoints(start, end, 2.0, mirror());
+        let points = parabola.points(2);
+        assert_near(points[0], end);
+        assert_near(points[1], vec2(0.0, -2.0));
+        assert_near(points[2], start);
+        assert_near(parabola.focus(), vec2(0.0, 0.0));
+        // a negative depth bends it on the other side
+        let flipped = Conic::parabola_from_endpoints(start, end, -2.0, mirror());
+        assert_near(flipped.points(2)[1], vec2(0.0, 2.0));
+    }
+
+    #[test]
+    fn focus_and_directrix() {
+        let focus = vec2(0.0, 0.0);
+        // the directrix is the line x = -2
+        for e in [0.5, 1.0, 2.0].iter() {
+            let conic = Conic::from_focus_and_directrix(
+                focus,
+                vec2(-2.0, 5.0),
+                vec2(0.0, 1.0),
+                *e,
+                1.0,
+                mirror(),
+            );
+            assert_near(conic.focus(), focus);
+            for p in conic.points(8) {
+                let ratio = p.distance(focus) / (p.x + 2.0);
+                assert!((ratio - e).abs() < 1e-3, "e = {}, ratio = {}", e, ratio);
+            }
+        }
+    }
+
+    #[test]
+    fn constructors_from_foci() {
+        let (focus_a, focus_b) = (vec2(1.0, 1.0), vec2(7.0, 9.0));
+        let hyperbola = Conic::hyperbola_from_foci(focus_a, focus_b, 3.0, 4.0, mirror());
+        assert_near(hyperbola.focus(), focus_b);
+        for p in hyperbola.points(8) {
+            let difference = p.distance(focus_a) - p.distance(focus_b);
+            assert!((difference - 6.0).abs() < 1e-3);
+        }
+        let ellipse = Conic::ellipse_arc_from_foci(focus_a, focus_b, 8.0, 0.0, PI, mirror());
+        for p in ellipse.points(8) {
+            let sum = p.distance(focus_a) + p.distance(focus_b);
+            assert!((sum - 16.0).abs() < 1e-3);
+        }
+    }
+}
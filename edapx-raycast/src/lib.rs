@@ -0,0 +1,29 @@
+// The scene, the tracer, the wall and ray generators and the renderers shared by raycastcurves,
+// raycastelements and rayparabolic. The ray geometry (rays, segments, bounding volumes, conic
+// sections and Bézier curves) is in ray2d, this crate has what is built on top of it.
+//
+// scene        the walls: curves, circles, conics and splines, with their material
+// wraycaster   the tracer, a light casting rays that bounce and refract on the walls
+// wall_helper  the generators of the walls, from the rectangles of mondrian
+// ray_helper   the generators of the raycasters
+// capturer     high resolution screenshots and recordings
+pub mod bouncing;
+pub mod capturer;
+pub mod conic;
+pub mod mondrian;
+pub mod ray_helper;
+pub mod ray_light;
+pub mod ray_path;
+pub mod raycaster;
+pub mod scene;
+pub mod spline;
+pub mod wall_helper;
+pub mod wraycaster;
+
+#[cfg(test)]
+mod test_helpers;
+
+pub use capturer::Capturer;
+pub use ray2d::{BoundingVolume, Ray2D};
+pub use scene::{Element, Intersectable, Material, SurfaceType};
+pub use wraycaster::Wraycaster;
@@ -1,58 +1,44 @@
+use crate::ray_path::RayPath;
 use nannou::prelude::*;
 use ray2d::Ray2D;
-#[derive(Debug, Clone, Copy)]
-pub struct Intersection {
-    pub pos: Vec2,
-    pub color: Hsla,
-    pub depth: usize,
-}
 
-impl Intersection{
-    pub fn new(pos: Vec2, color: Hsla, depth: usize) -> Self { 
-        Intersection {
-            pos,
-            color,
-            depth,
-        }
-    }
-}
+// rays carrying less light than this are not traced anymore
+pub const MIN_ENERGY: f32 = 0.01;
 
 #[derive(Debug, Clone)]
 pub struct RayLight {
-    pub intersections: Vec<Intersection>,
+    pub path: RayPath,
     pub color: Hsla,
     pub max_depth: usize,
-    pub count_depth: usize,
+    pub min_energy: f32,
     pub ray: Ray2D,
     pub starting_pos: Vec2,
     pub starting_dir: Vec2,
 }
 
 impl RayLight {
-    pub fn new(origin: Vec2, direction: Vec2, max_depth: usize) -> Self{
-        let intersections: Vec<Intersection> = Vec::new();
+    pub fn new(origin: Vec2, direction: Vec2, max_depth: usize) -> Self {
+        let path = RayPath::new();
         let mut ray = Ray2D::new();
-        let color: Hsla = hsla(random_range(0.0, 1.0), 1.0, random_range(0.5, 1.0)  , 1.0);
+        let color: Hsla = hsla(random_range(0.0, 1.0), 1.0, random_range(0.5, 1.0), 1.0);
         ray.orig = origin;
         ray.dir = direction;
         let starting_pos = origin;
         let starting_dir = direction;
         RayLight {
-            intersections,
+            path,
             color,
             max_depth,
-            count_depth: 0,
+            min_energy: MIN_ENERGY,
             ray,
             starting_pos,
             starting_dir,
         }
     }
 
-    pub fn reset(&mut self){
+    pub fn reset(&mut self) {
         self.ray.orig = self.starting_pos;
         self.ray.dir = self.starting_dir;
-        self.intersections.clear();
-        self.count_depth = 0;
+        self.path.clear();
     }
-
-}
\ No newline at end of file
+}
@@ -0,0 +1,90 @@
+use crate::scene::Material;
+use nannou::prelude::*;
+
+// how far a ray that does not hit anything is drawn
+pub const ESCAPING_RAY_LENGTH: f32 = 10_000.0;
+
+// A piece of a light path, from where a ray starts to where it hits an element.
+// `material` is the material of the element hit at `end`, `None` if the ray escapes the scene.
+// `depth` is the number of bounces before the segment, 0 for the ray leaving the light, and
+// `parent` is the index of the segment that generated this one.
+// Segments with the same `branch` id are connected one after the other. The first secondary ray
+// of a segment continues its branch, every other secondary ray opens a new branch.
+#[derive(Debug, Clone, Copy)]
+pub struct RaySegment {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub start_color: Hsla,
+    pub end_color: Hsla,
+    pub depth: usize,
+    pub energy: f32,
+    pub material: Option<Material>,
+    pub branch: usize,
+    pub parent: Option<usize>,
+}
+
+impl RaySegment {
+    pub fn hits(&self) -> bool {
+        self.material.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RayPath {
+    pub segments: Vec<RaySegment>,
+    pub branch_count: usize,
+}
+
+impl RayPath {
+    pub fn new() -> Self {
+        RayPath::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.segments.clear();
+        self.branch_count = 0;
+    }
+
+    pub fn push(&mut self, segment: RaySegment) -> usize {
+        self.branch_count = self.branch_count.max(segment.branch + 1);
+        self.segments.push(segment);
+        self.segments.len() - 1
+    }
+
+    pub fn new_branch(&mut self) -> usize {
+        self.branch_count += 1;
+        self.branch_count - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    // The segments that end on an element, in the order they have been traced.
+    pub fn hits(&self) -> impl Iterator<Item = &RaySegment> {
+        self.segments.iter().filter(|segment| segment.hits())
+    }
+
+    // The connected segments of a branch, from the first to the last one.
+    pub fn branch(&self, branch: usize) -> impl Iterator<Item = &RaySegment> {
+        self.segments
+            .iter()
+            .filter(move |segment| segment.branch == branch)
+    }
+
+    // The colored points of a branch, starting from where the branch starts. The last segment
+    // is included only if it hits something or if `with_escaping` is true.
+    pub fn branch_points(&self, branch: usize, with_escaping: bool) -> Vec<(Vec2, Hsla)> {
+        let mut points = Vec::new();
+        for segment in self.branch(branch) {
+            if !segment.hits() && !with_escaping {
+                break;
+            }
+            if points.is_empty() {
+                points.push((segment.start, segment.start_color));
+            }
+            points.push((segment.end, segment.end_color));
+        }
+        points
+    }
+}
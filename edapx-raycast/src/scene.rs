@@ -1,5 +1,7 @@
+use crate::conic::{Conic, CONIC_RESOLUTION};
+use crate::spline::{Spline, SPLINE_RESOLUTION};
 use nannou::prelude::*;
-use nannou_ray2d::{BoundingVolume, Ray2D};
+use ray2d::{BoundingVolume, Ray2D};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum SurfaceType {
@@ -57,6 +59,8 @@ pub struct Circle {
 pub enum Element {
     Curve(Curve),
     Circle(Circle),
+    Conic(Conic),
+    Spline(Spline),
 }
 
 impl Element {
@@ -64,24 +68,32 @@ impl Element {
         match *self {
             Element::Curve(ref cu) => &cu.material,
             Element::Circle(ref ci) => &ci.material,
+            Element::Conic(ref co) => &co.material,
+            Element::Spline(ref sp) => &sp.material,
         }
     }
     pub fn bounding_volume(&self) -> Option<&BoundingVolume> {
         match *self {
             Element::Curve(ref cu) => cu.bounding_volume.as_ref(),
             Element::Circle(ref ci) => ci.bounding_volume.as_ref(),
+            Element::Conic(ref co) => co.bounding_volume.as_ref(),
+            Element::Spline(ref sp) => sp.bounding_volume.as_ref(),
         }
     }
     pub fn ray_anchor_point(&self) -> Option<&Vec2> {
         match *self {
             Element::Curve(ref cu) => cu.ray_anchor_point.as_ref(),
             Element::Circle(ref ci) => ci.ray_anchor_point.as_ref(),
+            Element::Conic(ref co) => co.ray_anchor_point.as_ref(),
+            Element::Spline(ref sp) => sp.ray_anchor_point.as_ref(),
         }
     }
     pub fn material_mut(&mut self) -> &mut Material {
         match *self {
             Element::Curve(ref mut cu) => &mut cu.material,
             Element::Circle(ref mut ci) => &mut ci.material,
+            Element::Conic(ref mut co) => &mut co.material,
+            Element::Spline(ref mut sp) => &mut sp.material,
         }
     }
 
@@ -102,6 +114,18 @@ impl Element {
                     .color(circle.material.coloration)
                     .stroke_weight(*wall_width);
             }
+            Element::Conic(ref conic) => {
+                draw.polyline()
+                    .weight(*wall_width)
+                    .color(conic.material.coloration)
+                    .points(conic.points(CONIC_RESOLUTION));
+            }
+            Element::Spline(ref spline) => {
+                draw.polyline()
+                    .weight(*wall_width)
+                    .color(spline.material.coloration)
+                    .points(spline.points(SPLINE_RESOLUTION));
+            }
         }
     }
 }
@@ -115,6 +139,8 @@ impl Intersectable for Element {
         match *self {
             Element::Curve(ref cu) => cu.intersect(ray),
             Element::Circle(ref ci) => ci.intersect(ray),
+            Element::Conic(ref co) => co.intersect(ray),
+            Element::Spline(ref sp) => sp.intersect(ray),
         }
     }
 }
@@ -142,3 +168,28 @@ impl Intersectable for Curve {
         }
     }
 }
+
+impl Intersectable for Conic {
+    fn intersect(&self, ray: &Ray2D) -> Option<(f32, Vec2)> {
+        // the bounding volume is used to discard the rays that can not hit the conic
+        match &self.bounding_volume {
+            Some(volume) => match ray.intersect_bounding_volume(volume) {
+                Some(_) => self.intersect_ray(ray),
+                None => None,
+            },
+            None => self.intersect_ray(ray),
+        }
+    }
+}
+
+impl Intersectable for Spline {
+    fn intersect(&self, ray: &Ray2D) -> Option<(f32, Vec2)> {
+        match &self.bounding_volume {
+            Some(volume) => match ray.intersect_bounding_volume(volume) {
+                Some(_) => self.intersect_ray(ray),
+                None => None,
+            },
+            None => self.intersect_ray(ray),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::scene::Material;
+use edapx_curves::{curve_segments, CurveType};
+use nannou::prelude::*;
+pub use ray2d::bezier::CubicBezier;
+use ray2d::{BoundingVolume, Ray2D};
+
+// number of points used to draw each segment of a spline
+pub const SPLINE_RESOLUTION: usize = 32;
+
+// A smooth curve made of cubic Bézier segments. Quadratic curves and Catmull–Rom splines are
+// converted to cubic Bézier segments when the spline is created.
+#[derive(Debug)]
+pub struct Spline {
+    pub segments: Vec<CubicBezier>,
+    pub material: Material,
+    pub ray_anchor_point: Option<Vec2>,
+    pub bounding_volume: Option<BoundingVolume>,
+}
+
+impl Spline {
+    pub fn new(segments: Vec<CubicBezier>, material: Material) -> Self {
+        let bounding_volume = bounding_circle(&segments);
+        Spline {
+            segments,
+            material,
+            ray_anchor_point: None,
+            bounding_volume,
+        }
+    }
+
+    pub fn quadratic(from: Vec2, ctrl: Vec2, to: Vec2, material: Material) -> Self {
+        Spline::new(vec![CubicBezier::from_quadratic(from, ctrl, to)], material)
+    }
+
+    pub fn cubic(from: Vec2, ctrl1: Vec2, ctrl2: Vec2, to: Vec2, material: Material) -> Self {
+        Spline::new(vec![CubicBezier::new(from, ctrl1, ctrl2, to)], material)
+    }
+
+    // A uniform Catmull–Rom spline passing through all the points, like curveVertex() in Processing.
+    // The segments are the ones of `edapx_curves::curve_segments`.
+    pub fn catmull_rom(points: &[Vec2], closed: bool, material: Material) -> Self {
+        let segments = curve_segments(points, CurveType::CatmullRom, closed)
+            .iter()
+            .map(|[from, ctrl1, ctrl2, to]| CubicBezier::new(*from, *ctrl1, *ctrl2, *to))
+            .collect();
+        Spline::new(segments, material)
+    }
+
+    pub fn points(&self, resolution: usize) -> Vec<Vec2> {
+        let resolution = resolution.max(1);
+        let mut points = Vec::new();
+        for (index, segment) in self.segments.iter().enumerate() {
+            // the first point of a segment is the last point of the previous one
+            let first = if index == 0 { 0 } else { 1 };
+            for i in first..=resolution {
+                points.push(segment.point(i as f32 / resolution as f32));
+            }
+        }
+        points
+    }
+
+    // Closest intersection between the ray and all the segments, it returns the distance and
+    // the surface normal, computed from the derivative of the curve.
+    pub fn intersect_ray(&self, ray: &Ray2D) -> Option<(f32, Vec2)> {
+        let mut closest: Option<(f32, Vec2)> = None;
+        for segment in self.segments.iter() {
+            if let Some((distance, t)) = segment.intersect_ray(ray.orig, ray.dir) {
+                if closest.is_none_or(|(closest_distance, _)| distance < closest_distance) {
+                    closest = Some((distance, segment.normal(t)));
+                }
+            }
+        }
+        closest
+    }
+}
+
+// A Bézier curve lies inside the convex hull of its control points, the circle around them
+// contains the whole spline.
+fn bounding_circle(segments: &[CubicBezier]) -> Option<BoundingVolume> {
+    let mut control_points = segments
+        .iter()
+        .flat_map(|s| vec![s.from, s.ctrl1, s.ctrl2, s.to]);
+    let first = control_points.next()?;
+    let (min, max) = control_points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+    Some(BoundingVolume::Circle {
+        position: (min + max) / 2.0,
+        radius: (max - min).length() / 2.0 + 1.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{mirror, ray};
+
+    #[test]
+    fn quadratic_spline() {
+        let spline = Spline::quadratic(vec2(-1.0, 0.0), vec2(0.0, 2.0), vec2(1.0, 0.0), mirror());
+        assert_eq!(spline.segments.len(), 1);
+        let (distance, normal) = spline
+            .intersect_ray(&ray(vec2(0.0, -5.0), vec2(0.0, 1.0)))
+            .unwrap();
+        assert!((distance - 6.0).abs() < 1e-5);
+        assert!(normal.distance(vec2(0.0, 1.0)) < 1e-5);
+    }
+
+    #[test]
+    fn cubic_spline() {
+        let spline = Spline::cubic(
+            vec2(0.0, 0.0),
+            vec2(1.0, 2.0),
+            vec2(2.0, -2.0),
+            vec2(3.0, 0.0),
+            mirror(),
+        );
+        let (distance, _) = spline
+            .intersect_ray(&ray(vec2(1.5, -5.0), vec2(0.0, 1.0)))
+            .unwrap();
+        assert!((distance - 5.0).abs() < 1e-5);
+        assert!(spline
+            .intersect_ray(&ray(vec2(4.0, -5.0), vec2(0.0, 1.0)))
+            .is_none());
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_the_points() {
+        let points = [vec2(0.0, 0.0), vec2(10.0, 10.0), vec2(20.0, 0.0)];
+        let open = Spline::catmull_rom(&points, false, mirror());
+        assert_eq!(open.segments.len(), 2);
+        for (segment, pair) in open.segments.iter().zip(points.windows(2)) {
+            assert_eq!(segment.from, pair[0]);
+            assert_eq!(segment.to, pair[1]);
+        }
+        assert_eq!(open.points(4).len(), 2 * 4 + 1);
+
+        // the ray hits the spline where two segments meet
+        let (distance, normal) = open
+            .intersect_ray(&ray(vec2(10.0, -5.0), vec2(0.0, 1.0)))
+            .unwrap();
+        assert!((distance - 15.0).abs() < 1e-4);
+        assert!(normal.x.abs() < 1e-5);
+
+        let closed = Spline::catmull_rom(&points, true, mirror());
+        assert_eq!(closed.segments.len(), 3);
+        assert_eq!(closed.segments[2].to, points[0]);
+    }
+
+    #[test]
+    fn not_enough_points() {
+        let spline = Spline::catmull_rom(&[vec2(1.0, 1.0)], false, mirror());
+        assert!(spline.segments.is_empty());
+        assert!(spline.bounding_volume.is_none());
+        assert!(spline
+            .intersect_ray(&ray(vec2(0.0, 0.0), vec2(1.0, 1.0)))
+            .is_none());
+    }
+}
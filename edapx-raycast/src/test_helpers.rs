@@ -0,0 +1,19 @@
+// Fixtures shared by the tests of the walls.
+use crate::scene::{Material, SurfaceType};
+use nannou::prelude::*;
+use ray2d::Ray2D;
+
+pub fn mirror() -> Material {
+    Material {
+        coloration: rgba(1.0, 1.0, 1.0, 1.0),
+        albedo: 1.0,
+        surface: SurfaceType::Reflective { reflectivity: 1.0 },
+    }
+}
+
+pub fn ray(orig: Vec2, dir: Vec2) -> Ray2D {
+    Ray2D {
+        orig,
+        dir: dir.normalize(),
+    }
+}
@@ -1,12 +1,14 @@
+use crate::conic::Conic;
 use crate::mondrian::split_squares;
 pub use crate::mondrian::Square;
 use crate::scene::Curve;
-use crate::Element;
+use crate::scene::Element;
 //use crate::Element::Curve;
 use crate::scene::Material;
 use crate::scene::SurfaceType;
+use crate::spline::Spline;
 use nannou::prelude::*;
-use nannou_raycast::bounding_volume::BoundingVolume;
+use ray2d::BoundingVolume;
 
 pub fn make_walls(
     walls: &mut Vec<Element>,
@@ -19,6 +21,7 @@ pub fn make_walls(
     color_a: Rgba,
     color_b: Rgba,
     material: &Material,
+    wall_shape: usize, // 0 polylines, 1 arcs of ellipse, 2 parabolic mirrors, 3 splines
 ) {
     walls.clear();
     let margin: i32 = 100;
@@ -36,7 +39,12 @@ pub fn make_walls(
     }
     for square in &squares {
         let padding = step as f32 * perc_padding;
-        create_curve_from_square(&square, padding, hole_pct, hole_n, walls, &material);
+        match wall_shape {
+            1 => create_arcs_from_square(square, padding, hole_pct, hole_n, walls, material),
+            2 => create_parabolas_from_square(square, padding, hole_pct, walls, material),
+            3 => create_spline_from_square(square, padding, hole_pct, walls, material),
+            _ => create_curve_from_square(square, padding, hole_pct, hole_n, walls, material),
+        }
     }
     change_color_walls(walls, color_a, color_b);
 }
@@ -126,6 +134,119 @@ pub fn create_curve_from_square(
     }
 }
 
+// Same walls as `create_curve_from_square`, but using arcs of ellipse instead of polylines.
+pub fn create_arcs_from_square(
+    square: &Square,
+    padding: f32,
+    hole: f32,
+    hole_n: usize,
+    walls: &mut Vec<Element>,
+    mat: &Material,
+) {
+    let center = vec2(
+        square.x + square.width / 2.0,
+        square.y + square.height / 2.0,
+    );
+    let radius_x = square.width / 2.0 - padding;
+    let radius_y = square.height / 2.0 - padding;
+
+    let mut arcs = vec![(0.0, 2.0 * PI)];
+    if hole > 0.1 {
+        let n_arcs = hole_n.max(1);
+        let wall_length = 2.0 * PI / n_arcs as f32;
+        let pad = wall_length * hole;
+        arcs = (0..n_arcs)
+            .map(|i| {
+                let start = i as f32 * wall_length;
+                (start, start + wall_length - pad)
+            })
+            .collect();
+    }
+
+    for (start_angle, end_angle) in arcs {
+        let mut arc = Conic::ellipse_arc(
+            center,
+            radius_x,
+            radius_y,
+            0.0,
+            start_angle,
+            end_angle,
+            *mat,
+        );
+        arc.ray_anchor_point = Some(center);
+        walls.push(Element::Conic(arc));
+    }
+}
+
+// Two parabolic mirrors facing each other, sharing the focus in the center of the square.
+// The light coming from the center is reflected parallel to the axis and then focused back
+// in the center by the opposite mirror. The hole makes the mirrors narrower.
+pub fn create_parabolas_from_square(
+    square: &Square,
+    padding: f32,
+    hole: f32,
+    walls: &mut Vec<Element>,
+    mat: &Material,
+) {
+    let center = vec2(
+        square.x + square.width / 2.0,
+        square.y + square.height / 2.0,
+    );
+    // the mirrors are placed along the longest side of the square
+    let horizontal = square.width >= square.height;
+    let (length, aperture) = if horizontal {
+        (square.width / 2.0 - padding, square.height / 2.0 - padding)
+    } else {
+        (square.height / 2.0 - padding, square.width / 2.0 - padding)
+    };
+    let axis = if horizontal {
+        vec2(1.0, 0.0)
+    } else {
+        vec2(0.0, 1.0)
+    };
+    let half_aperture = (aperture * (1.0 - hole)).max(1.0);
+
+    for side in [-1.0, 1.0].iter() {
+        let vertex = center + axis * length * *side;
+        let opening = -axis * *side;
+        walls.push(Element::Conic(Conic::parabola(
+            vertex,
+            opening.y.atan2(opening.x),
+            length,
+            half_aperture,
+            *mat,
+        )));
+    }
+}
+
+// A closed Catmull–Rom spline passing through points placed around the center of the square
+// at a random distance. The hole makes the shape more irregular.
+pub fn create_spline_from_square(
+    square: &Square,
+    padding: f32,
+    hole: f32,
+    walls: &mut Vec<Element>,
+    mat: &Material,
+) {
+    let center = vec2(
+        square.x + square.width / 2.0,
+        square.y + square.height / 2.0,
+    );
+    let n_points = 8;
+    let points: Vec<Vec2> = (0..n_points)
+        .map(|i| {
+            let rad = 2.0 * PI * i as f32 / n_points as f32;
+            let scale = random_range(1.0 - hole, 1.0);
+            let x = (square.width / 2.0 - padding) * scale * rad.cos();
+            let y = (square.height / 2.0 - padding) * scale * rad.sin();
+            center + vec2(x, y)
+        })
+        .collect();
+    let mut spline = Spline::catmull_rom(&points, true, *mat);
+    spline.ray_anchor_point = Some(center);
+    walls.push(Element::Spline(spline));
+}
+
 // TODO, use an aabb instead of a circle for this case
 fn get_circle_from_square(square: &Square, padding: &f32) -> BoundingVolume {
     let radius: f32;
@@ -0,0 +1,461 @@
+use crate::ray_light::RayLight;
+use crate::ray_path::RayPath;
+use crate::ray_path::RaySegment;
+use crate::ray_path::ESCAPING_RAY_LENGTH;
+use crate::scene::Element;
+use crate::scene::Intersectable;
+use crate::scene::Material;
+use crate::scene::SurfaceType;
+use nannou::color::Mix;
+#[allow(dead_code)]
+use nannou::prelude::*;
+use nannou::rand::Rng;
+use ray2d::Ray2D;
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+const EPSILON: f32 = 0.05;
+
+#[derive(Debug)]
+pub struct Wraycaster {
+    pub ray_lights: Vec<RayLight>,
+    pub direction: Vec2,
+    pub max_depth: usize,
+    pub density: usize,
+}
+
+impl Wraycaster {
+    pub fn new(position: Vec2, direction: Vec2, max_depth: usize, density: usize) -> Self {
+        let mut ray_lights: Vec<RayLight> = Vec::new();
+        for i in (0..360).step_by(density) {
+            let radian = deg_to_rad(i as f32);
+            let ray_light = RayLight::new(position, vec2(radian.cos(), radian.sin()), max_depth);
+            ray_lights.push(ray_light);
+        }
+        Wraycaster {
+            ray_lights,
+            direction,
+            max_depth,
+            density,
+        }
+    }
+
+    pub fn move_to(&mut self, new_pos: Vec2) {
+        self.ray_lights.par_iter_mut().for_each(|r| {
+            r.ray.orig.x = new_pos.x;
+            r.ray.orig.y = new_pos.y
+        });
+    }
+
+    pub fn animate(&mut self, win: &geom::Rect, anim_speed: f32, animation_mode: usize, time: f32) {
+        match animation_mode {
+            0 => {
+                // Bounce orizontally
+                for r in self.ray_lights.iter_mut() {
+                    if self.direction.x >= 0.0 {
+                        r.starting_pos.x += 0.1 * anim_speed;
+                    } else {
+                        r.starting_pos.x -= 0.1 * anim_speed;
+                    }
+
+                    //r.ray.orig = r.ray.orig + r.ray.dir.with_magnitude(animation_speed);
+                    if r.starting_pos.x >= win.right() as f32 {
+                        //r.starting_pos.x = win.left();
+
+                        self.direction = vec2(-1.0.cos(), -1.0.sin()).normalize();
+                    } else if r.starting_pos.x < win.left() as f32 {
+                        self.direction = vec2(0.0.cos(), 0.0.sin()).normalize();
+                        //r.starting_pos.x = win.right();
+                    }
+                }
+            }
+            1 => {
+                for r in self.ray_lights.iter_mut() {
+                    let radius = win.w() / 4.0;
+                    let x = (time * 0.001).cos() * radius;
+                    let y = (time * 0.001).sin() * radius;
+
+                    r.starting_pos = r.starting_pos + pt2(x, y);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_polygon(
+        &self,
+        draw: &Draw,
+        poly_weight: f32,
+        weight: f32,
+        draw_not_colliding_rays: bool,
+        mode: usize,
+    ) {
+        let expe = true;
+        match mode {
+            0 => {
+                let mut all_hits: Vec<&RaySegment> = Vec::new();
+                for pray in self.ray_lights.iter() {
+                    all_hits.extend(pray.path.hits())
+                }
+
+                if all_hits.len() > 3 {
+                    let pp = all_hits
+                        .iter()
+                        .map(|segment| (segment.end, segment.end_color));
+                    if poly_weight > 0.5 {
+                        draw.polygon()
+                            .stroke_weight(poly_weight)
+                            .caps_round()
+                            .stroke(all_hits[0].end_color)
+                            .join_round()
+                            .points_colored(pp);
+                    } else {
+                        draw.polygon().points_colored(pp);
+                    }
+                }
+            }
+            1 => {
+                // one polygon for each branch, starting from where the branch starts
+                for pray in self.ray_lights.iter() {
+                    for branch in 0..pray.path.branch_count {
+                        let points_colored = pray.path.branch_points(branch, false);
+                        if points_colored.len() > 1 {
+                            if poly_weight > 0.5 {
+                                draw.polygon()
+                                    .stroke_weight(poly_weight)
+                                    .caps_round()
+                                    .join_round()
+                                    .stroke(pray.color)
+                                    .points_colored(points_colored);
+                            } else {
+                                draw.polygon().points_colored(points_colored);
+                            }
+                        }
+                    }
+                }
+            }
+            2 => {
+                // one polygon for each branch, using only the intersections
+                for pray in self.ray_lights.iter() {
+                    for branch in 0..pray.path.branch_count {
+                        let hits: Vec<&RaySegment> =
+                            pray.path.branch(branch).filter(|s| s.hits()).collect();
+                        if hits.len() > 3 {
+                            let pp = hits.iter().map(|segment| (segment.end, segment.end_color));
+                            if poly_weight > 0.5 {
+                                draw.polygon()
+                                    .stroke_weight(poly_weight)
+                                    .caps_round()
+                                    .stroke(hits[0].end_color)
+                                    .join_round()
+                                    .points_colored(pp);
+                            } else {
+                                draw.polygon().points_colored(pp);
+                            }
+                        }
+                    }
+                }
+            }
+            3 => {
+                // For Oscar
+                for pray in self.ray_lights.iter() {
+                    for branch in 0..pray.path.branch_count {
+                        let hits: Vec<&RaySegment> =
+                            pray.path.branch(branch).filter(|s| s.hits()).collect();
+                        if hits.len() > 3 {
+                            let pp = hits.iter().map(|segment| (segment.end, segment.end_color));
+                            draw.polyline()
+                                .stroke_weight(poly_weight)
+                                .caps_round()
+                                .join_round()
+                                .points_colored(pp);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_arrows(&self, draw: &Draw, weight: f32) {
+        for pray in self.ray_lights.iter() {
+            for segment in pray.path.hits() {
+                draw.arrow()
+                    .start(segment.start)
+                    .end(segment.end)
+                    .stroke_weight(weight)
+                    .color(segment.start_color);
+            }
+        }
+    }
+
+    pub fn draw_rays(&self, draw: &Draw, weight: f32, draw_not_colliding_rays: bool) {
+        for pray in self.ray_lights.iter() {
+            // every branch is a connected polyline
+            for branch in 0..pray.path.branch_count {
+                let points_colored = pray.path.branch_points(branch, draw_not_colliding_rays);
+                if points_colored.len() > 1 {
+                    draw.polyline()
+                        .stroke_weight(weight)
+                        .caps_round()
+                        .join_round()
+                        .points_colored(points_colored);
+                }
+            }
+        }
+    }
+
+    pub fn collide(
+        &mut self,
+        rotation: f32,
+        animation: bool,
+        animation_speed: f32,
+        time: f32,
+        scene: &Vec<Element>,
+        win: geom::Rect,
+    ) {
+        self.ray_lights.par_iter_mut().for_each(|pray| {
+            pray.reset();
+            cast_ray(
+                &pray.ray,
+                pray.color,
+                pray.max_depth,
+                pray.min_energy,
+                &mut pray.path,
+                scene,
+            )
+        })
+    }
+}
+
+// A ray waiting in the queue to be traced. Each branch carries its own depth and energy, so
+// that the reflected and the refracted rays of a Fresnel split do not share the same budget.
+struct PendingRay {
+    orig: Vec2,
+    dir: Vec2,
+    color: Hsla,
+    depth: usize,
+    energy: f32,
+    parent: Option<usize>,
+    branch: usize,
+}
+
+// https://www.scratchapixel.com/lessons/3d-basic-rendering/ray-tracing-overview/light-transport-ray-tracing-whitted
+// The path is built breadth first using a queue instead of recursion. Every traced ray is pushed
+// in `path` as a `RaySegment`, secondary rays point to the segment that generated them.
+pub fn cast_ray(
+    ray: &Ray2D,
+    color: Hsla,
+    max_depth: usize,
+    min_energy: f32,
+    path: &mut RayPath,
+    scene: &Vec<Element>,
+) {
+    // principle of light conservation. The amount of light does not decrease.
+    // but at the same time it does not increase. If a ray generates 2 secondary rays like
+    // in a refractive and reflective surface, the amount of light is split among the 2
+    // secondary rays.
+    // This parameter is used as alpha channel of the color.
+    let light_amount = 1.0;
+    let mut queue: VecDeque<PendingRay> = VecDeque::new();
+    queue.push_back(PendingRay {
+        orig: ray.orig,
+        dir: ray.dir,
+        color,
+        depth: 0,
+        energy: light_amount,
+        parent: None,
+        branch: 0,
+    });
+
+    let mut ray = Ray2D::new();
+    while let Some(pending) = queue.pop_front() {
+        if pending.depth >= max_depth || pending.energy < min_energy {
+            continue;
+        }
+        ray.orig = pending.orig;
+        ray.dir = pending.dir;
+        let (distance, surface_normal, material) = match closest_hit(&ray, scene) {
+            Some(hit) => hit,
+            None => {
+                // the ray does not hit anything, it is a leaf of the path.
+                path.push(RaySegment {
+                    start: pending.orig,
+                    end: pending.orig + pending.dir.normalize() * ESCAPING_RAY_LENGTH,
+                    start_color: pending.color,
+                    end_color: pending.color,
+                    depth: pending.depth,
+                    energy: pending.energy,
+                    material: None,
+                    branch: pending.branch,
+                    parent: pending.parent,
+                });
+                continue;
+            }
+        };
+
+        // collision point
+        let collision = ray.orig + ray.dir.normalize() * distance;
+        let mut hsla = get_color(&surface_normal, &ray.dir.normalize(), &material);
+        hsla.alpha = pending.energy;
+        let index = path.push(RaySegment {
+            start: pending.orig,
+            end: collision,
+            start_color: pending.color,
+            end_color: hsla,
+            depth: pending.depth,
+            energy: pending.energy,
+            material: Some(material),
+            branch: pending.branch,
+            parent: pending.parent,
+        });
+
+        //secondary rays.
+        let mut secondary: Vec<(Vec2, f32)> = Vec::new();
+        match material.surface {
+            SurfaceType::Reflective { reflectivity } => {
+                secondary.push((ray.reflect(surface_normal), pending.energy * reflectivity));
+            }
+            SurfaceType::Refractive { ior } => {
+                secondary.push((ray.refract(surface_normal, ior), pending.energy));
+            }
+            SurfaceType::ReflectiveAndRefractive { reflectivity, ior } => {
+                let fresnel = ray.fresnel(surface_normal, ior) as f32;
+                // always refract, as we are dealong most with segments and curves and they don't have an inside or outside
+                // side. In case of total internal reflection there is nothing to refract.
+                if fresnel < 1.0 {
+                    secondary.push((
+                        ray.refract(surface_normal, ior),
+                        pending.energy * (1.0 - fresnel),
+                    ));
+                }
+                secondary.push((
+                    ray.reflect(surface_normal),
+                    pending.energy * fresnel * reflectivity,
+                ));
+            }
+            SurfaceType::Diffuse => {}
+        }
+
+        for (i, (dir, energy)) in secondary.into_iter().enumerate() {
+            // the first secondary ray continues the branch, the others open a new one
+            let branch = if i == 0 {
+                pending.branch
+            } else {
+                path.new_branch()
+            };
+            queue.push_back(PendingRay {
+                orig: collision + dir.normalize() * EPSILON,
+                dir,
+                color: hsla,
+                depth: pending.depth + 1,
+                energy,
+                parent: Some(index),
+                branch,
+            });
+        }
+    }
+
+    // find the closest intersection point between the ray and the scene. Returns the distance,
+    // the surface normal and the material of the element that has been hit.
+    fn closest_hit(ray: &Ray2D, scene: &Vec<Element>) -> Option<(f32, Vec2, Material)> {
+        let mut closest: Option<(f32, Vec2, Material)> = None;
+        for element in scene.iter() {
+            if let Some((element_dist, element_surface_normal)) = element.intersect(ray) {
+                // save the closest possible collision
+                if closest.is_none_or(|(distance, _, _)| element_dist < distance) {
+                    closest = Some((element_dist, element_surface_normal, *element.material()));
+                }
+            }
+        }
+        closest
+    }
+
+    // at the moment this method is doing nothing
+    fn get_color(surface_direction: &Vec2, ray_direction: &Vec2, material: &Material) -> Hsla {
+        // let mut alpha : f32 = 1.0 - ( *depth as f32 / max_depth as f32);
+        // alpha = alpha.min(0.0).max(1.0);
+        let mut hsla: Hsla = material.coloration.into();
+        // TODO, is there a function to get the inverse of a vector?
+        let inverted_ray_dir = vec2(ray_direction.x * -1.0, ray_direction.y * -1.0);
+        // the normal can point away from the ray, the side that is lit is the one the ray comes from
+        let diffuse_component = surface_direction.dot(inverted_ray_dir).abs().min(1.0);
+        hsla.lightness = hsla.lightness * diffuse_component;
+
+        return hsla;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::Curve;
+
+    fn wall(x: f32, surface: SurfaceType) -> Element {
+        Element::Curve(Curve {
+            points: vec![vec2(x, -10.0), vec2(x, 10.0)],
+            material: Material {
+                coloration: rgba(1.0, 1.0, 1.0, 1.0),
+                albedo: 1.0,
+                surface,
+            },
+            ray_anchor_point: None,
+            bounding_volume: None,
+        })
+    }
+
+    fn trace(scene: &Vec<Element>, max_depth: usize, min_energy: f32) -> RayPath {
+        let mut ray = Ray2D::new();
+        ray.dir = vec2(1.0, 0.0);
+        let mut path = RayPath::new();
+        let color = hsla(0.0, 0.0, 1.0, 1.0);
+        cast_ray(&ray, color, max_depth, min_energy, &mut path, scene);
+        path
+    }
+
+    #[test]
+    fn depth_counts_the_bounces_before_a_segment() {
+        let scene = vec![wall(10.0, SurfaceType::Reflective { reflectivity: 0.5 })];
+        let path = trace(&scene, 10, 0.0);
+        assert_eq!(path.segments.len(), 2);
+        let (hit, escaping) = (path.segments[0], path.segments[1]);
+        assert!(hit.hits() && !escaping.hits());
+        assert_eq!((hit.depth, escaping.depth), (0, 1));
+        assert_eq!(escaping.parent, Some(0));
+        assert!(escaping.end.x < 0.0);
+    }
+
+    #[test]
+    fn reflectivity_scales_the_reflected_energy() {
+        // two parallel mirrors, the ray bounces between them
+        let mirror = SurfaceType::Reflective { reflectivity: 0.5 };
+        let scene = vec![wall(10.0, mirror), wall(-10.0, mirror)];
+        let path = trace(&scene, 3, 0.0);
+        let depths: Vec<usize> = path.segments.iter().map(|s| s.depth).collect();
+        let energies: Vec<f32> = path.segments.iter().map(|s| s.energy).collect();
+        assert_eq!(depths, vec![0, 1, 2]);
+        assert_eq!(energies, vec![1.0, 0.5, 0.25]);
+
+        // the rays with less energy than the minimum are not traced
+        let path = trace(&scene, 10, 0.3);
+        assert_eq!(path.segments.len(), 2);
+
+        let glass = SurfaceType::ReflectiveAndRefractive {
+            reflectivity: 0.5,
+            ior: 1.5,
+        };
+        let path = trace(&vec![wall(10.0, glass)], 10, 0.0);
+        let reflected = path.segments.iter().find(|s| s.end.x < 0.0).unwrap();
+        let refracted = path.segments.iter().find(|s| s.end.x > 10.0).unwrap();
+        // at normal incidence the fresnel coefficient is ((1.5 - 1) / (1.5 + 1))^2 = 0.04
+        assert!((refracted.energy - 0.96).abs() < 1e-4);
+        assert!((reflected.energy - 0.04 * 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn diffuse_surfaces_end_the_path() {
+        let path = trace(&vec![wall(10.0, SurfaceType::Diffuse)], 10, 0.0);
+        assert_eq!(path.segments.len(), 1);
+        assert!(path.segments[0].hits());
+    }
+}
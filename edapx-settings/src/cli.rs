@@ -0,0 +1,85 @@
+use crate::{Settings, SettingsError};
+
+pub fn apply<T, I, S>(settings: &mut T, args: I) -> Result<(), SettingsError>
+where
+    T: Settings,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<String> = args.into_iter().map(|a| a.as_ref().to_string()).collect();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        let flag = arg
+            .strip_prefix("--")
+            .ok_or_else(|| SettingsError::UnknownParam(arg.clone()))?;
+        let (name, mut value) = match flag.split_once('=') {
+            Some((name, value)) => (name.replace('-', "_"), Some(value.to_string())),
+            None => (flag.replace('-', "_"), None),
+        };
+        // the value is the next argument, unless it is another flag
+        if value.is_none() {
+            if let Some(next) = args.get(i + 1) {
+                if !next.starts_with("--") {
+                    value = Some(next.clone());
+                    i += 1;
+                }
+            }
+        }
+        i += 1;
+
+        if name == "preset" {
+            let path = value.ok_or_else(|| SettingsError::InvalidValue {
+                name,
+                value: String::new(),
+            })?;
+            settings.load_preset(path)?;
+            continue;
+        }
+
+        let mut params = settings.params();
+        let param = params
+            .iter_mut()
+            .find(|p| p.name == name)
+            .ok_or_else(|| SettingsError::UnknownParam(name.clone()))?;
+        let value = match value {
+            Some(value) => value,
+            None if matches!(param.value, crate::Value::Bool(_)) => "true".to_string(),
+            None => String::new(),
+        };
+        if !param.value.parse(&value, param.range) {
+            return Err(SettingsError::InvalidValue { name, value });
+        }
+    }
+    Ok(())
+}
+
+pub fn from_env<T: Settings + Default>() -> T {
+    let mut settings = T::default();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help") {
+        println!("{}", settings.usage());
+        std::process::exit(0);
+    }
+    if let Err(err) = settings.apply_args(&args) {
+        eprintln!("{}\n{}", err, settings.usage());
+        std::process::exit(1);
+    }
+    settings
+}
+
+pub fn usage(params: Vec<crate::Param>) -> String {
+    let mut text = String::from("  --preset <path>\n");
+    for param in params.iter() {
+        let kind = match (&param.value, param.range) {
+            (crate::Value::Bool(_), _) => "true|false".to_string(),
+            (_, Some((min, max))) => format!("{}..{}", min, max),
+            (_, None) => "number".to_string(),
+        };
+        text.push_str(&format!(
+            "  --{} <{}> [{}]\n",
+            param.name, kind, param.value
+        ));
+    }
+    text
+}
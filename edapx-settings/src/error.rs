@@ -0,0 +1,53 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(io::Error),
+    // the preset is not valid TOML, the string explains why
+    Parse(String),
+    // a command line argument that does not match any parameter
+    UnknownParam(String),
+    // the value cannot be assigned to the parameter
+    InvalidValue { name: String, value: String },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingsError::Io(err) => write!(f, "cannot read the preset: {}", err),
+            SettingsError::Parse(msg) => write!(f, "invalid preset: {}", msg),
+            SettingsError::UnknownParam(name) => write!(f, "unknown parameter: {}", name),
+            SettingsError::InvalidValue { name, value } => {
+                write!(f, "invalid value for {}: \"{}\"", name, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SettingsError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SettingsError {
+    fn from(err: io::Error) -> Self {
+        SettingsError::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for SettingsError {
+    fn from(err: toml::de::Error) -> Self {
+        SettingsError::Parse(err.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for SettingsError {
+    fn from(err: toml::ser::Error) -> Self {
+        SettingsError::Parse(err.to_string())
+    }
+}
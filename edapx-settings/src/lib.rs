@@ -0,0 +1,287 @@
+// The parameters of a sketch, declared once with the `settings!` macro. From the declaration we
+// get a struct with the default values, an egui panel, presets saved as TOML and overrides from
+// the command line.
+//
+// settings! {
+//     pub struct Settings {
+//         "Walls" {
+//             wall_width: f32 = 2.0, 1.0..=15.0;
+//             show_walls: bool = true;
+//         }
+//         "Rays" {
+//             n_caster: u32 = 2, 1..=50;
+//         }
+//     }
+// }
+//
+// let mut settings = Settings::default();
+// settings.apply_args(std::env::args().skip(1))?;  // --wall_width=4 --show-walls false
+// let changed = settings.show(ui);                 // names of the parameters edited in the panel
+// settings.save_preset("preset.toml")?;
+use std::path::Path;
+
+mod cli;
+mod error;
+mod panel;
+mod preset;
+mod value;
+
+pub use error::SettingsError;
+pub use value::{bounds, AsValue, Bound, Parsed, Value};
+
+pub struct Param<'a> {
+    pub name: &'static str,
+    pub group: &'static str,
+    // min and max, numbers without a range have no slider but a drag value
+    pub range: Option<(f64, f64)>,
+    pub value: Value<'a>,
+}
+
+impl<'a> Param<'a> {
+    // the name shown in the panel, "wall_width" becomes "wall width"
+    pub fn label(&self) -> String {
+        self.name.replace('_', " ")
+    }
+}
+
+// Implemented by the `settings!` macro.
+pub trait Settings {
+    // Every parameter, in the order of the declaration. The values are borrowed mutably, so the
+    // same list is used to show, save and load them.
+    fn params(&mut self) -> Vec<Param<'_>>;
+
+    // The panel with a heading for every group, a slider for every number and a checkbox for
+    // every boolean. Returns the names of the parameters changed in this frame.
+    fn show(&mut self, ui: &mut egui::Ui) -> Vec<&'static str> {
+        panel::show(ui, self.params())
+    }
+
+    // The current values as a TOML preset, a table for every group.
+    fn preset(&mut self) -> Result<String, SettingsError> {
+        preset::write(self.params())
+    }
+
+    // Parameters missing in the preset keep their value, unknown ones are ignored, so the
+    // presets saved by an older version of a sketch can still be loaded.
+    fn apply_preset(&mut self, text: &str) -> Result<(), SettingsError> {
+        preset::read(self.params(), text)
+    }
+
+    fn save_preset<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SettingsError>
+    where
+        Self: Sized,
+    {
+        let text = self.preset()?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    fn load_preset<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SettingsError>
+    where
+        Self: Sized,
+    {
+        let text = std::fs::read_to_string(path)?;
+        self.apply_preset(&text)
+    }
+
+    // Overrides from the command line: `--name=value` or `--name value`, dashes and underscores
+    // are the same. A boolean without a value is set to true. `--preset path` loads a preset,
+    // the arguments after it override its values.
+    fn apply_args<I, S>(&mut self, args: I) -> Result<(), SettingsError>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        cli::apply(self, args)
+    }
+
+    // The default values, overridden by the arguments of the sketch. With `--help` or with an
+    // invalid argument it prints the usage and exits.
+    fn from_env() -> Self
+    where
+        Self: Default + Sized,
+    {
+        cli::from_env()
+    }
+
+    // The list of the command line arguments, with the current values.
+    fn usage(&mut self) -> String {
+        cli::usage(self.params())
+    }
+}
+
+#[macro_export]
+macro_rules! settings {
+    (@range) => {
+        None
+    };
+    (@range $range:expr) => {
+        Some($crate::bounds($range))
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $group:literal {
+                    $( $field:ident : $ty:ty = $default:expr $(, $range:expr)? ; )*
+                }
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($( pub $field: $ty, )*)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($( $field: $default, )*)*
+                }
+            }
+        }
+
+        impl $crate::Settings for $name {
+            fn params(&mut self) -> Vec<$crate::Param<'_>> {
+                vec![
+                    $($(
+                        $crate::Param {
+                            name: stringify!($field),
+                            group: $group,
+                            range: $crate::settings!(@range $($range)?),
+                            value: $crate::AsValue::as_value(&mut self.$field),
+                        },
+                    )*)*
+                ]
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    settings! {
+        #[derive(Debug, PartialEq)]
+        struct Test {
+            "Walls" {
+                wall_width: f32 = 2.0, 1.0..=15.0;
+                hole_n: usize = 2, 0..=6;
+                show_walls: bool = true;
+            }
+            "Rays" {
+                rotation: f32 = 0.0;
+                n_caster: u32 = 2, 1..=50;
+                offset: i32 = -1, -5..=5;
+                seed: u64 = 0;
+            }
+        }
+    }
+
+    #[test]
+    fn declaration() {
+        let mut settings = Test::default();
+        assert_eq!(settings.wall_width, 2.0);
+        assert_eq!(settings.offset, -1);
+        let params = settings.params();
+        assert_eq!(params.len(), 7);
+        assert_eq!(params[0].name, "wall_width");
+        assert_eq!(params[0].label(), "wall width");
+        assert_eq!(params[0].group, "Walls");
+        assert_eq!(params[0].range, Some((1.0, 15.0)));
+        assert_eq!(params[3].group, "Rays");
+        assert_eq!(params[3].range, None);
+    }
+
+    #[test]
+    fn preset_round_trip() {
+        let mut settings = Test {
+            wall_width: 4.5,
+            show_walls: false,
+            n_caster: 7,
+            ..Test::default()
+        };
+        let text = settings.preset().unwrap();
+        assert!(text.contains("[Walls]"));
+
+        let mut loaded = Test::default();
+        loaded.apply_preset(&text).unwrap();
+        assert_eq!(loaded, settings);
+
+        // missing and unknown parameters
+        let mut partial = Test::default();
+        partial
+            .apply_preset("[Rays]\nn_caster = 9\nremoved = 1\n")
+            .unwrap();
+        assert_eq!(partial.n_caster, 9);
+        assert_eq!(partial.wall_width, 2.0);
+
+        // values out of range are clamped, wrong types are errors
+        partial.apply_preset("[Walls]\nhole_n = 100\n").unwrap();
+        assert_eq!(partial.hole_n, 6);
+        assert!(partial.apply_preset("[Walls]\nhole_n = 2.5\n").is_err());
+        assert!(partial.apply_preset("[Walls]\nshow_walls = 1\n").is_err());
+        assert!(partial.apply_preset("not toml").is_err());
+
+        // not finite numbers are not valid values
+        assert!(partial.apply_preset("[Rays]\nrotation = nan\n").is_err());
+        assert!(partial
+            .apply_preset("[Walls]\nwall_width = -inf\n")
+            .is_err());
+        assert!(partial.apply_preset("[Rays]\nrotation = 1e300\n").is_err());
+        assert_eq!(partial.rotation, 0.0);
+
+        // a preset with an invalid value is not applied at all
+        assert!(partial
+            .apply_preset("[Walls]\nwall_width = 5.0\nhole_n = 2.5\n")
+            .is_err());
+        assert_eq!(partial.wall_width, 2.0);
+
+        // an u64 that does not fit in a TOML integer cannot be saved
+        partial.seed = u64::MAX;
+        assert!(matches!(
+            partial.preset(),
+            Err(SettingsError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn command_line() {
+        let mut settings = Test::default();
+        settings
+            .apply_args(["--wall-width=3", "--n_caster", "12", "--show_walls", "no"])
+            .unwrap();
+        assert_eq!(settings.wall_width, 3.0);
+        assert_eq!(settings.n_caster, 12);
+        assert!(!settings.show_walls);
+
+        settings
+            .apply_args(["--show-walls", "--offset=-3"])
+            .unwrap();
+        assert!(settings.show_walls);
+        assert_eq!(settings.offset, -3);
+
+        assert!(matches!(
+            settings.apply_args(["--does-not-exist=1"]),
+            Err(SettingsError::UnknownParam(_))
+        ));
+        assert!(matches!(
+            settings.apply_args(["--hole_n=many"]),
+            Err(SettingsError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            settings.apply_args(["--hole_n"]),
+            Err(SettingsError::InvalidValue { .. })
+        ));
+        for arg in ["--rotation=NaN", "--rotation=inf", "--wall_width=-infinity"].iter() {
+            assert!(matches!(
+                settings.apply_args([*arg]),
+                Err(SettingsError::InvalidValue { .. })
+            ));
+        }
+        assert_eq!(settings.rotation, 0.0);
+        assert!(settings.usage().contains("--wall_width <1..15>"));
+    }
+}
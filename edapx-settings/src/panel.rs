@@ -0,0 +1,36 @@
+use crate::{Param, Value};
+use egui::{DragValue, Slider, Ui};
+
+pub fn show(ui: &mut Ui, params: Vec<Param>) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    let mut group = "";
+    for mut param in params {
+        if param.group != group {
+            group = param.group;
+            ui.heading(group);
+        }
+        ui.horizontal(|ui| {
+            ui.label(param.label());
+            if widget(ui, &mut param).changed() {
+                changed.push(param.name);
+            }
+        });
+    }
+    changed
+}
+
+fn widget(ui: &mut Ui, param: &mut Param) -> egui::Response {
+    match (&mut param.value, param.range) {
+        (Value::Bool(v), _) => ui.checkbox(v, ""),
+        (Value::F32(v), Some((min, max))) => ui.add(Slider::new(*v, min as f32..=max as f32)),
+        (Value::U32(v), Some((min, max))) => ui.add(Slider::new(*v, min as u32..=max as u32)),
+        (Value::Usize(v), Some((min, max))) => ui.add(Slider::new(*v, min as usize..=max as usize)),
+        (Value::U64(v), Some((min, max))) => ui.add(Slider::new(*v, min as u64..=max as u64)),
+        (Value::I32(v), Some((min, max))) => ui.add(Slider::new(*v, min as i32..=max as i32)),
+        (Value::F32(v), None) => ui.add(DragValue::new(*v).speed(0.01)),
+        (Value::U32(v), None) => ui.add(DragValue::new(*v)),
+        (Value::Usize(v), None) => ui.add(DragValue::new(*v)),
+        (Value::U64(v), None) => ui.add(DragValue::new(*v)),
+        (Value::I32(v), None) => ui.add(DragValue::new(*v)),
+    }
+}
@@ -0,0 +1,56 @@
+// Presets are TOML files with a table for every group:
+//
+// [Walls]
+// wall_width = 2.0
+// show_walls = true
+use crate::{Param, SettingsError};
+use toml::value::Table;
+use toml::Value as Toml;
+
+pub fn write(params: Vec<Param>) -> Result<String, SettingsError> {
+    let mut root = Table::new();
+    for param in params.iter() {
+        let group = root
+            .entry(param.group.to_string())
+            .or_insert_with(|| Toml::Table(Table::new()));
+        if let Toml::Table(group) = group {
+            let value = param
+                .value
+                .to_toml()
+                .ok_or_else(|| SettingsError::InvalidValue {
+                    name: param.name.to_string(),
+                    value: param.value.to_string(),
+                })?;
+            group.insert(param.name.to_string(), value);
+        }
+    }
+    Ok(toml::to_string(&Toml::Table(root))?)
+}
+
+// Nothing is assigned if one of the values is not valid.
+pub fn read(params: Vec<Param>, text: &str) -> Result<(), SettingsError> {
+    let root: Table = toml::from_str(text)?;
+    let mut parsed = Vec::new();
+    for param in params {
+        // a parameter outside of its group is accepted too, handy for presets written by hand
+        let value = root
+            .get(param.group)
+            .and_then(|group| group.get(param.name))
+            .or_else(|| root.get(param.name));
+        if let Some(value) = value {
+            match param.value.check_toml(value) {
+                Some(v) => parsed.push((param, v)),
+                None => {
+                    return Err(SettingsError::InvalidValue {
+                        name: param.name.to_string(),
+                        value: value.to_string(),
+                    })
+                }
+            }
+        }
+    }
+    for (mut param, value) in parsed {
+        param.value.set(value, param.range);
+    }
+    Ok(())
+}
@@ -0,0 +1,182 @@
+// The types a parameter can have. Numbers are clamped to the range of the parameter when they
+// come from a preset or from the command line.
+use std::convert::TryFrom;
+use toml::Value as Toml;
+
+pub enum Value<'a> {
+    F32(&'a mut f32),
+    U32(&'a mut u32),
+    Usize(&'a mut usize),
+    U64(&'a mut u64),
+    I32(&'a mut i32),
+    Bool(&'a mut bool),
+}
+
+// Implemented for the types that can be used as parameters.
+pub trait AsValue {
+    fn as_value(&mut self) -> Value<'_>;
+}
+
+impl AsValue for f32 {
+    fn as_value(&mut self) -> Value<'_> {
+        Value::F32(self)
+    }
+}
+
+impl AsValue for u32 {
+    fn as_value(&mut self) -> Value<'_> {
+        Value::U32(self)
+    }
+}
+
+impl AsValue for usize {
+    fn as_value(&mut self) -> Value<'_> {
+        Value::Usize(self)
+    }
+}
+
+impl AsValue for u64 {
+    fn as_value(&mut self) -> Value<'_> {
+        Value::U64(self)
+    }
+}
+
+impl AsValue for i32 {
+    fn as_value(&mut self) -> Value<'_> {
+        Value::I32(self)
+    }
+}
+
+impl AsValue for bool {
+    fn as_value(&mut self) -> Value<'_> {
+        Value::Bool(self)
+    }
+}
+
+// The bounds of a range as f64, used by the `settings!` macro for any numeric range.
+pub trait Bound: Copy {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_bound!(f32, f64, i32, i64, u32, u64, usize);
+
+pub fn bounds<T: Bound>(range: std::ops::RangeInclusive<T>) -> (f64, f64) {
+    (range.start().to_f64(), range.end().to_f64())
+}
+
+// A value read from a preset or from the command line, checked but not assigned yet.
+pub enum Parsed {
+    Bool(bool),
+    Number(f64),
+}
+
+impl<'a> Value<'a> {
+    // None if the value does not fit in a TOML integer, like an u64 bigger than i64::MAX.
+    pub fn to_toml(&self) -> Option<Toml> {
+        Some(match self {
+            // through the shortest decimal representation, 0.6 is saved as 0.6 and not as
+            // 0.6000000238418579
+            Value::F32(v) => Toml::Float(v.to_string().parse().unwrap_or(**v as f64)),
+            Value::U32(v) => Toml::Integer(**v as i64),
+            Value::Usize(v) => Toml::Integer(i64::try_from(**v).ok()?),
+            Value::U64(v) => Toml::Integer(i64::try_from(**v).ok()?),
+            Value::I32(v) => Toml::Integer(**v as i64),
+            Value::Bool(v) => Toml::Boolean(**v),
+        })
+    }
+
+    // Returns None if the TOML value has the wrong type.
+    pub fn check_toml(&self, value: &Toml) -> Option<Parsed> {
+        let n = match (self, value) {
+            (Value::Bool(_), Toml::Boolean(b)) => return Some(Parsed::Bool(*b)),
+            (Value::Bool(_), _) => return None,
+            (_, Toml::Float(f)) => *f,
+            (_, Toml::Integer(i)) => *i as f64,
+            _ => return None,
+        };
+        // nan and inf would go through the clamp, a number too large for an f32 too
+        if !n.is_finite() || !(n as f32).is_finite() {
+            return None;
+        }
+        // integers are not rounded, 2.5 is not a valid scheme id
+        if !matches!(self, Value::F32(_)) && n.fract() != 0.0 {
+            return None;
+        }
+        Some(Parsed::Number(n))
+    }
+
+    pub fn set(&mut self, parsed: Parsed, range: Option<(f64, f64)>) {
+        match (&mut *self, parsed) {
+            (Value::Bool(v), Parsed::Bool(b)) => **v = b,
+            (_, Parsed::Number(n)) => self.set_number(n, range),
+            _ => {}
+        }
+    }
+
+    // Returns false if the TOML value has the wrong type.
+    pub fn set_toml(&mut self, value: &Toml, range: Option<(f64, f64)>) -> bool {
+        match self.check_toml(value) {
+            Some(parsed) => {
+                self.set(parsed, range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Parses a command line value. Booleans accept true/false, yes/no, on/off and 1/0.
+    pub fn parse(&mut self, text: &str, range: Option<(f64, f64)>) -> bool {
+        let text = text.trim();
+        if let Value::Bool(v) = self {
+            match text.to_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => **v = true,
+                "false" | "no" | "off" | "0" => **v = false,
+                _ => return false,
+            }
+            return true;
+        }
+        match text.parse::<f64>() {
+            Ok(n) => self.set_toml(&Toml::Float(n), range),
+            Err(_) => false,
+        }
+    }
+
+    fn set_number(&mut self, n: f64, range: Option<(f64, f64)>) {
+        let n = match range {
+            // the range can be reversed, like conrod sliders
+            Some((a, b)) => n.clamp(a.min(b), a.max(b)),
+            None => n,
+        };
+        match self {
+            Value::F32(v) => **v = n as f32,
+            Value::U32(v) => **v = n.max(0.0) as u32,
+            Value::Usize(v) => **v = n.max(0.0) as usize,
+            Value::U64(v) => **v = n.max(0.0) as u64,
+            Value::I32(v) => **v = n as i32,
+            Value::Bool(_) => {}
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for Value<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::F32(v) => write!(f, "{}", v),
+            Value::U32(v) => write!(f, "{}", v),
+            Value::Usize(v) => write!(f, "{}", v),
+            Value::U64(v) => write!(f, "{}", v),
+            Value::I32(v) => write!(f, "{}", v),
+            Value::Bool(v) => write!(f, "{}", v),
+        }
+    }
+}
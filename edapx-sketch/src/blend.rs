@@ -0,0 +1,40 @@
+use nannou::prelude::*;
+use nannou::wgpu::BlendComponent;
+
+// The blend modes of the sketches, applied to everything drawn by the sketch.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Blend {
+    Normal,
+    Add,
+    Subtract,
+    Lightest,
+}
+
+impl Blend {
+    pub fn all() -> &'static [Blend] {
+        &[Blend::Normal, Blend::Add, Blend::Subtract, Blend::Lightest]
+    }
+
+    // the id wraps around, any value coming from a slider is valid
+    pub fn from_id(id: usize) -> Blend {
+        Blend::all()[id % Blend::all().len()]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Blend::Normal => "normal",
+            Blend::Add => "add",
+            Blend::Subtract => "subtract",
+            Blend::Lightest => "lightest",
+        }
+    }
+
+    pub fn state(&self) -> BlendComponent {
+        match self {
+            Blend::Normal => BLEND_NORMAL,
+            Blend::Add => BLEND_ADD,
+            Blend::Subtract => BLEND_SUBTRACT,
+            Blend::Lightest => BLEND_LIGHTEST,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use nannou::prelude::*;
+use std::path::PathBuf;
+
+// Screenshots and frame sequences of the sketch window, saved in captures/<sketch name>/
+pub struct Capture {
+    // the window of the sketch, `app.main_window()` is the focused one, that can be the controls
+    window: WindowId,
+    directory: PathBuf,
+    name: String,
+    recording: Option<u64>,
+}
+
+impl Capture {
+    pub fn new(app: &App, window: WindowId) -> Self {
+        let name = app.exe_name().unwrap_or_else(|_| "sketch".to_string());
+        let directory = app
+            .project_path()
+            .unwrap_or_default()
+            .join("captures")
+            .join(&name);
+        Capture {
+            window,
+            directory,
+            name,
+            recording: None,
+        }
+    }
+
+    // The seed is in the name, so that the same image can be generated again.
+    pub fn screenshot(&self, app: &App, seed: u64) {
+        if let Some(window) = app.window(self.window) {
+            let frame = window.elapsed_frames();
+            let path = self
+                .directory
+                .join(format!("{}_{}_{}.png", self.name, seed, frame));
+            window.capture_frame(path);
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    // Starts or stops saving every frame, the frames of each recording are numbered from 0.
+    pub fn toggle_recording(&mut self, app: &App) {
+        self.recording = match self.recording {
+            Some(_) => None,
+            None => app
+                .window(self.window)
+                .map(|window| window.elapsed_frames()),
+        };
+    }
+
+    // called every frame
+    pub fn update(&self, app: &App) {
+        if let (Some(start), Some(window)) = (self.recording, app.window(self.window)) {
+            let frame = window.elapsed_frames().saturating_sub(start);
+            let path = self
+                .directory
+                .join("frames")
+                .join(format!("{:05}.png", frame));
+            window.capture_frame(path);
+        }
+    }
+}
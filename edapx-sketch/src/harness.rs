@@ -0,0 +1,261 @@
+use crate::{Blend, Capture, Sketch, State};
+use edapx_colors::{Palette, PaletteEditor};
+use edapx_settings::Settings;
+use nannou::prelude::*;
+use nannou_egui::{self, egui, Egui};
+use std::path::PathBuf;
+
+const GUI_W: u32 = 420;
+const GUI_H: u32 = 720;
+
+pub struct Harness<S: Sketch> {
+    sketch: S,
+    state: State<S::Settings>,
+    egui: Egui,
+    gui_window: WindowId,
+    show_gui: bool,
+    palette_editor: PaletteEditor,
+    capture: Capture,
+    preset_path: PathBuf,
+    // a message shown in the controls, like the path of the saved preset
+    status: String,
+}
+
+pub fn model<S: Sketch>(app: &App) -> Harness<S> {
+    let sketch_window = app
+        .new_window()
+        .view(view::<S>)
+        .key_pressed(key_pressed::<S>)
+        .mouse_pressed(mouse_pressed::<S>)
+        .build()
+        .unwrap();
+
+    let gui_window = app
+        .new_window()
+        .title(app.exe_name().unwrap() + " controls")
+        .size(GUI_W, GUI_H)
+        .view(gui_view::<S>)
+        .raw_event(raw_window_event::<S>)
+        .build()
+        .unwrap();
+    let egui = Egui::from_window(&app.window(gui_window).unwrap());
+
+    // the default values can be changed from the command line, run with --help to see them
+    let mut state = State::<S::Settings>::from_env();
+    let name = app.exe_name().unwrap();
+    let assets = app.assets_path().unwrap();
+    let preset_path = assets.join("presets").join(format!("{}.toml", name));
+    let palette_path = assets.join("palettes").join(format!("{}.toml", name));
+
+    // the palette saved from the editor, the built-in schemes until one is saved
+    let mut status = String::new();
+    if palette_path.exists() {
+        match Palette::load(&palette_path) {
+            Ok(palette) => state.palette = palette,
+            Err(err) => status = err.to_string(),
+        }
+    }
+
+    Harness {
+        sketch: S::generate(app, &state),
+        state,
+        egui,
+        gui_window,
+        show_gui: true,
+        palette_editor: PaletteEditor::new(palette_path.to_str().unwrap()),
+        capture: Capture::new(app, sketch_window),
+        preset_path,
+        status,
+    }
+}
+
+pub fn update<S: Sketch>(app: &App, model: &mut Harness<S>, update: Update) {
+    let changed = update_gui(app, model, &update);
+    if !changed.is_empty() {
+        model.sketch.changed(app, &model.state, &changed);
+    }
+    model.sketch.update(app, &model.state, &update);
+    model.capture.update(app);
+}
+
+fn update_gui<S: Sketch>(app: &App, model: &mut Harness<S>, update: &Update) -> Vec<&'static str> {
+    let egui = &mut model.egui;
+    let state = &mut model.state;
+    let palette_editor = &mut model.palette_editor;
+    let status = &mut model.status;
+    let recording = model.capture.is_recording();
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+
+    let mut changed = Vec::new();
+    let mut screenshot = false;
+    let mut record = false;
+    let mut save_preset = false;
+    let mut load_preset = false;
+    egui::CentralPanel::default().show(&ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading("Sketch");
+            ui.horizontal(|ui| {
+                ui.label("seed");
+                if ui.add(egui::DragValue::new(&mut state.seed)).changed() {
+                    changed.push("seed");
+                }
+                if ui.button("new seed").clicked() {
+                    state.new_seed();
+                    changed.push("seed");
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("blend");
+                for (id, blend) in Blend::all().iter().enumerate() {
+                    if ui
+                        .selectable_label(state.blend_id == id, blend.name())
+                        .clicked()
+                    {
+                        state.blend_id = id;
+                        changed.push("blend_id");
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                screenshot = ui.button("screenshot").clicked();
+                let label = if recording {
+                    "stop recording"
+                } else {
+                    "record"
+                };
+                record = ui.button(label).clicked();
+            });
+            ui.horizontal(|ui| {
+                save_preset = ui.button("save preset").clicked();
+                load_preset = ui.button("load preset").clicked();
+            });
+            if !status.is_empty() {
+                ui.label(status.as_str());
+            }
+
+            egui::CollapsingHeader::new("Palette").show(ui, |ui| {
+                if palette_editor.show(ui, &mut state.palette, &mut state.scheme_id) {
+                    changed.push("scheme_id");
+                }
+            });
+
+            changed.extend(state.settings.show(ui));
+        });
+    });
+    // the egui frame ends here, `ctx` borrows the model
+    drop(ctx);
+
+    if screenshot {
+        model.capture.screenshot(app, model.state.seed);
+    }
+    if record {
+        model.capture.toggle_recording(app);
+    }
+    if save_preset {
+        save(model);
+    }
+    if load_preset && load(model) {
+        changed.push("preset");
+    }
+    changed
+}
+
+fn save<S: Sketch>(model: &mut Harness<S>) {
+    if let Some(directory) = model.preset_path.parent() {
+        let _ = std::fs::create_dir_all(directory);
+    }
+    model.status = match model.state.save_preset(&model.preset_path) {
+        Ok(()) => format!("saved {}", model.preset_path.display()),
+        Err(err) => err.to_string(),
+    };
+}
+
+fn load<S: Sketch>(model: &mut Harness<S>) -> bool {
+    let result = model.state.load_preset(&model.preset_path);
+    model.status = match &result {
+        Ok(()) => format!("loaded {}", model.preset_path.display()),
+        Err(err) => err.to_string(),
+    };
+    result.is_ok()
+}
+
+fn view<S: Sketch>(app: &App, model: &Harness<S>, frame: Frame) {
+    let draw = app.draw();
+    let blended = draw.color_blend(model.state.blend().state());
+    model.sketch.draw(app, &blended, &model.state);
+    draw.to_frame(app, &frame).unwrap();
+}
+
+fn gui_view<S: Sketch>(_app: &App, model: &Harness<S>, frame: Frame) {
+    model.egui.draw_to_frame(&frame).unwrap();
+}
+
+fn raw_window_event<S: Sketch>(
+    _app: &App,
+    model: &mut Harness<S>,
+    event: &nannou::winit::event::WindowEvent,
+) {
+    // Let egui handle things like keyboard and mouse input.
+    model.egui.handle_raw_event(event);
+}
+
+fn key_pressed<S: Sketch>(app: &App, model: &mut Harness<S>, key: Key) {
+    let state = &mut model.state;
+    let changed = match key {
+        Key::S => {
+            model.capture.screenshot(app, state.seed);
+            None
+        }
+        Key::G => {
+            model.show_gui = !model.show_gui;
+            if let Some(window) = app.window(model.gui_window) {
+                window.set_visible(model.show_gui);
+            }
+            None
+        }
+        Key::C => {
+            model.capture.toggle_recording(app);
+            None
+        }
+        Key::N => {
+            state.new_seed();
+            Some("seed")
+        }
+        Key::B => {
+            state.blend_id = (state.blend_id + 1) % Blend::all().len();
+            Some("blend_id")
+        }
+        Key::Right => {
+            state.scheme_id = (state.scheme_id + 1) % state.palette.len().max(1);
+            Some("scheme_id")
+        }
+        Key::Left => {
+            let n = state.palette.len().max(1);
+            state.scheme_id = (state.scheme_id + n - 1) % n;
+            Some("scheme_id")
+        }
+        Key::P => {
+            save(model);
+            None
+        }
+        Key::L => {
+            if load(model) {
+                Some("preset")
+            } else {
+                None
+            }
+        }
+        _other_key => {
+            model.sketch.key_pressed(app, &mut model.state, key);
+            None
+        }
+    };
+    if let Some(name) = changed {
+        model.sketch.changed(app, &model.state, &[name]);
+    }
+}
+
+fn mouse_pressed<S: Sketch>(app: &App, model: &mut Harness<S>, button: MouseButton) {
+    model.sketch.mouse_pressed(app, &mut model.state, button);
+}
@@ -0,0 +1,234 @@
+// The scaffolding shared by the sketches: a main window and a controls window with the settings
+// of the sketch, palette selection, blend modes, seeds, presets, screenshots and recording.
+// A sketch only generates its scene and draws it:
+//
+// settings! {
+//     struct Settings {
+//         "Tiles" {
+//             tile_count_w: u32 = 8, 1..=20;
+//         }
+//     }
+// }
+//
+// struct Tiles { ... }
+//
+// impl Sketch for Tiles {
+//     type Settings = Settings;
+//     fn generate(app: &App, state: &State<Settings>) -> Self { ... }
+//     fn draw(&self, app: &App, draw: &Draw, state: &State<Settings>) { ... }
+// }
+//
+// fn main() {
+//     edapx_sketch::run::<Tiles>();
+// }
+//
+// Keys on the main window:
+// S screenshot, G show/hide the controls, C start/stop recording the frames, N new seed,
+// B next blend mode, Left/Right previous/next scheme, P save the preset, L load the preset.
+use edapx_colors::Palette;
+use edapx_settings::{settings, AsValue, Param, Settings};
+use nannou::color::Rgba;
+use nannou::prelude::*;
+use nannou::rand::rngs::StdRng;
+use nannou::rand::SeedableRng;
+
+mod blend;
+mod capture;
+mod harness;
+
+pub use blend::Blend;
+pub use capture::Capture;
+pub use edapx_settings;
+
+settings! {
+    // for the sketches that have nothing to set
+    pub struct NoSettings {}
+}
+
+pub trait Sketch: Sized + 'static {
+    type Settings: Settings + Default;
+
+    // Builds the scene. Called at start and, by default, every time something changes.
+    fn generate(app: &App, state: &State<Self::Settings>) -> Self;
+
+    // Draws the scene, the blend mode is already applied to `draw`.
+    fn draw(&self, app: &App, draw: &Draw, state: &State<Self::Settings>);
+
+    // called every frame before drawing
+    fn update(&mut self, _app: &App, _state: &State<Self::Settings>, _update: &Update) {}
+
+    // `changed` has the names of the settings edited in the controls, plus "scheme_id", "seed"
+    // and "blend_id" when they change. The scene is generated again, sketches with expensive
+    // scenes can update only what depends on the changed settings.
+    fn changed(&mut self, app: &App, state: &State<Self::Settings>, _changed: &[&str]) {
+        *self = Self::generate(app, state);
+    }
+
+    // the keys not used by the harness
+    fn key_pressed(&mut self, _app: &App, _state: &mut State<Self::Settings>, _key: Key) {}
+
+    fn mouse_pressed(
+        &mut self,
+        _app: &App,
+        _state: &mut State<Self::Settings>,
+        _button: MouseButton,
+    ) {
+    }
+}
+
+// Everything the harness manages, passed to the sketch.
+pub struct State<T> {
+    pub settings: T,
+    pub palette: Palette,
+    pub scheme_id: usize,
+    pub blend_id: usize,
+    pub seed: u64,
+}
+
+impl<T: Default> Default for State<T> {
+    fn default() -> Self {
+        State {
+            settings: T::default(),
+            palette: Palette::new(),
+            scheme_id: 0,
+            blend_id: 0,
+            seed: 0,
+        }
+    }
+}
+
+impl<T> State<T> {
+    // A generator that gives the same numbers for the same seed, use it for everything random
+    // in the scene so that a screenshot can be generated again.
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed)
+    }
+
+    pub fn new_seed(&mut self) {
+        self.seed = random_range(0, 100_000u64);
+    }
+
+    pub fn scheme(&self) -> &[Rgba] {
+        self.palette.get_scheme(self.scheme_id)
+    }
+
+    pub fn color(&self, position: usize) -> Rgba {
+        self.palette.get_color(self.scheme_id, position)
+    }
+
+    pub fn blend(&self) -> Blend {
+        Blend::from_id(self.blend_id)
+    }
+}
+
+// The scheme, the blend mode and the seed are saved in the presets and can be set from the
+// command line together with the settings of the sketch.
+impl<T: Settings> Settings for State<T> {
+    fn params(&mut self) -> Vec<Param<'_>> {
+        let mut params = vec![
+            Param {
+                name: "scheme_id",
+                group: "Sketch",
+                range: None,
+                value: self.scheme_id.as_value(),
+            },
+            Param {
+                name: "blend_id",
+                group: "Sketch",
+                range: Some((0.0, (Blend::all().len() - 1) as f64)),
+                value: self.blend_id.as_value(),
+            },
+            Param {
+                name: "seed",
+                group: "Sketch",
+                range: None,
+                value: self.seed.as_value(),
+            },
+        ];
+        params.extend(self.settings.params());
+        params
+    }
+}
+
+pub fn run<S: Sketch>() {
+    nannou::app(harness::model::<S>)
+        .update(harness::update::<S>)
+        .run();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    settings! {
+        #[derive(Debug, PartialEq)]
+        struct Test {
+            "Walls" {
+                wall_width: f32 = 2.0, 1.0..=15.0;
+                show_walls: bool = true;
+            }
+        }
+    }
+
+    #[test]
+    fn blend_ids_wrap() {
+        assert_eq!(Blend::from_id(0), Blend::Normal);
+        assert_eq!(Blend::from_id(3), Blend::Lightest);
+        assert_eq!(Blend::from_id(4), Blend::Normal);
+        assert_eq!(Blend::from_id(5), Blend::Add);
+        assert_eq!(Blend::from_id(usize::MAX), Blend::Lightest);
+    }
+
+    #[test]
+    fn state_params() {
+        let mut state = State::<Test>::default();
+        let params = state.params();
+        let names: Vec<&str> = params.iter().map(|p| p.name).collect();
+        assert_eq!(
+            names,
+            vec!["scheme_id", "blend_id", "seed", "wall_width", "show_walls"]
+        );
+        assert_eq!(params[0].group, "Sketch");
+        assert_eq!(params[1].range, Some((0.0, 3.0)));
+        assert_eq!(params[3].group, "Walls");
+    }
+
+    #[test]
+    fn preset_round_trip() {
+        let mut state = State::<Test> {
+            settings: Test {
+                wall_width: 4.5,
+                show_walls: false,
+            },
+            scheme_id: 2,
+            blend_id: 1,
+            seed: 42,
+            ..State::default()
+        };
+        let text = state.preset().unwrap();
+        assert!(text.contains("[Sketch]"));
+        assert!(text.contains("[Walls]"));
+
+        let mut loaded = State::<Test>::default();
+        loaded.apply_preset(&text).unwrap();
+        assert_eq!(loaded.settings, state.settings);
+        assert_eq!(loaded.scheme_id, 2);
+        assert_eq!(loaded.blend_id, 1);
+        assert_eq!(loaded.seed, 42);
+
+        // the blend id is clamped to the blend modes
+        loaded.apply_preset("[Sketch]\nblend_id = 10\n").unwrap();
+        assert_eq!(loaded.blend(), Blend::Lightest);
+    }
+
+    #[test]
+    fn command_line() {
+        let mut state = State::<Test>::default();
+        state
+            .apply_args(["--seed", "7", "--blend-id=2", "--wall-width=3"])
+            .unwrap();
+        assert_eq!(state.seed, 7);
+        assert_eq!(state.blend(), Blend::Subtract);
+        assert_eq!(state.settings.wall_width, 3.0);
+    }
+}
@@ -1,6 +1,7 @@
 // http://openframeworks.cc:80/ofBook/chapters/lines.html
 extern crate nannou;
 
+use edapx_curves::{curve_path, CurveType};
 use nannou::prelude::*;
 
 struct Line {
@@ -11,7 +12,9 @@ struct Line {
 struct Model {
     points: Vec<Point2>,
     store_points: bool,
-    lines: Vec<Line>
+    lines: Vec<Line>,
+    // None does not draw the curve through the points
+    curve: Option<CurveType>,
 }
 
 fn main() {
@@ -34,6 +37,7 @@ fn model(app: &App) -> Model {
         points: Vec::new(),
         lines: Vec::new(),
         store_points: false,
+        curve: Some(CurveType::Centripetal),
     }
 }
 
@@ -67,6 +71,16 @@ fn view(app: &App, model: &Model, frame: Frame) {
             .color(DARKSLATEBLUE);
     }
 
+    if let Some(curve) = model.curve {
+        if let Some(path) = curve_path(&model.points, curve, false) {
+            draw.path()
+                .stroke()
+                .weight(2.0)
+                .color(DARKSLATEBLUE)
+                .events(path.iter());
+        }
+    }
+
     draw.to_frame(app, &frame).unwrap();
 }
 
@@ -107,9 +121,16 @@ fn mouse_moved(app: &App, model: &mut Model, pos: Vector2) {
 }
 
 fn key_pressed(_app: &App, model: &mut Model, key: Key) {
-    if key == Key::Space {
-        model.points.clear();
-        model.lines.clear();
+    match key {
+        Key::Space => {
+            model.points.clear();
+            model.lines.clear();
+        }
+        Key::Key1 => model.curve = None,
+        Key::Key2 => model.curve = Some(CurveType::CatmullRom),
+        Key::Key3 => model.curve = Some(CurveType::Centripetal),
+        Key::Key4 => model.curve = Some(CurveType::BSpline),
+        _ => (),
     }
 }
 
@@ -120,7 +120,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
         let x = (i % tile_count_w) as f32 * tile_size - win.w() * 0.5 + tile_size / 2.0;
         let y = (i / tile_count_w) as f32 * tile_size - win.h() * 0.5 + tile_size / 2.0;
         let mut draw = draw.x_y(x, y);
-        let toggle = rng.gen_range(0, 2);
+        let toggle = rng.gen_range(0..2);
         let rotation = match toggle {
             0 => -PI,
             1 => 0.0,
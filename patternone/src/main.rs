@@ -1,15 +1,14 @@
 use nannou::prelude::*;
-use edapx_colors::Palette;
-use nannou::color::gradient::Gradient;
+use edapx_colors::{Gradient, Palette};
 use nannou::rand::rngs::StdRng;
 use nannou::rand::{Rng, SeedableRng};
 
 struct Model {
     scheme_id: usize,
     palette: Palette,
-    gradient_one: Gradient<Hsl>,
-    gradient_two: Gradient<Hsl>,
-    gradient_three: Gradient<Hsl>,
+    gradient_one: Gradient,
+    gradient_two: Gradient,
+    gradient_three: Gradient,
     blend_id: usize,
     act_random_seed: u64,
 }
@@ -32,9 +31,9 @@ fn model(app: &App) -> Model {
     let palette = Palette::new();
     let scheme = palette.get_scheme(scheme_id);
 
-    let gradient_one = Gradient::new(vec![Hsl::from(scheme[0]),Hsl::from(scheme[2])]);
-    let gradient_two = Gradient::new(vec![Hsl::from(scheme[1]),Hsl::from(scheme[3])]);
-    let gradient_three = Gradient::new(vec![Hsl::from(scheme[4]),Hsl::from(scheme[1])]);
+    let gradient_one = Gradient::new(&[scheme[0], scheme[2]]);
+    let gradient_two = Gradient::new(&[scheme[1], scheme[3]]);
+    let gradient_three = Gradient::new(&[scheme[4], scheme[1]]);
 
     Model {
         scheme_id,
@@ -92,9 +91,9 @@ fn key_pressed(app: &App, model: &mut Model, key: Key) {
 
     let scheme_id = model.scheme_id;
     let scheme = model.palette.get_scheme(scheme_id);
-    model.gradient_one = Gradient::new(vec![Hsl::from(scheme[1]),Hsl::from(scheme[2])]);
-    model.gradient_two = Gradient::new(vec![Hsl::from(scheme[3]),Hsl::from(scheme[4])]);
-    model.gradient_three = Gradient::new(vec![Hsl::from(scheme[4]),Hsl::from(scheme[0])]);
+    model.gradient_one = Gradient::new(&[scheme[1], scheme[2]]);
+    model.gradient_two = Gradient::new(&[scheme[3], scheme[4]]);
+    model.gradient_three = Gradient::new(&[scheme[4], scheme[0]]);
 }
 
 fn view(app: &App, model: &Model, frame: Frame) {
@@ -122,7 +121,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
         let tile_h = win.h() / tile_count as f32;
         let x = (i % tile_count) as f32 * tile_w - win.w() * 0.5 + tile_w / 2.0;
         let y = (i / tile_count) as f32 * tile_h - win.h() * 0.5 + tile_h / 2.0;
-        let coin = rng.gen_range(0, 4);
+        let coin = rng.gen_range(0..4);
         draw_circles(&app, &draw, x, y, i, tile_w, tile_h, model, coin);
         draw_poly(&app, &draw, x, y, i, tile_w, tile_h, model, coin);
 
@@ -156,7 +155,7 @@ fn draw_circles(app: &App, draw: &Draw, x: f32, y: f32, index: u32, tile_w: f32,
         let alpha = map_range(n, 1, n_circles, 1.0, 0.0);
         let offset = map_range(n,1, n_circles, circle_thickness, circle_thickness/2.0 * n_circles as f32);
         draw.ellipse()
-            .resolution(64)
+            .resolution(64.0)
             .no_fill()
             .stroke_weight(circle_thickness)
             .stroke(model.gradient_two.get(alpha))
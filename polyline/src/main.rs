@@ -1,11 +1,14 @@
 // http://openframeworks.cc:80/ofBook/chapters/lines.html
 extern crate nannou;
 
+use edapx_curves::{curve_path, CurveType};
 use nannou::prelude::*;
 
 struct Model {
     points: Vec<Point2>,
     store_points: bool,
+    // None draws straight lines between the points
+    curve: Option<CurveType>,
 }
 
 fn main() {
@@ -26,6 +29,7 @@ fn model(app: &App) -> Model {
     Model {
         points: Vec::new(),
         store_points: false,
+        curve: None,
     }
 }
 
@@ -52,11 +56,25 @@ fn view(app: &App, model: &Model, frame: Frame) {
     let draw = app.draw();
     draw.background().color(ORANGERED);
 
-    draw.polyline()
-        .join_round()
-        .color(DARKSLATEBLUE)
-        .stroke_weight(4.)
-        .points(model.points.iter().cloned());
+    match model.curve {
+        None => {
+            draw.polyline()
+                .join_round()
+                .color(DARKSLATEBLUE)
+                .stroke_weight(4.)
+                .points(model.points.iter().cloned());
+        }
+        Some(curve) => {
+            if let Some(path) = curve_path(&model.points, curve, false) {
+                draw.path()
+                    .stroke()
+                    .join_round()
+                    .color(DARKSLATEBLUE)
+                    .stroke_weight(4.)
+                    .events(path.iter());
+            }
+        }
+    }
 
     draw.to_frame(app, &frame).unwrap();
 }
@@ -69,8 +87,13 @@ fn mouse_released(_app: &App, model: &mut Model, _button: MouseButton) {
 }
 
 fn key_pressed(_app: &App, model: &mut Model, key: Key) {
-    if key == Key::Space {
-        model.points.clear();
+    match key {
+        Key::Space => model.points.clear(),
+        Key::Key1 => model.curve = None,
+        Key::Key2 => model.curve = Some(CurveType::CatmullRom),
+        Key::Key3 => model.curve = Some(CurveType::Centripetal),
+        Key::Key4 => model.curve = Some(CurveType::BSpline),
+        _ => (),
     }
 }
 
@@ -0,0 +1,261 @@
+use nannou::prelude::*;
+
+// intersections closer than this to the origin of the ray are discarded, to avoid
+// that a ray hits again the surface that generated it.
+const MIN_DISTANCE: f64 = 0.001;
+// iterations of the bisection used to find the roots, enough to reach the f64 precision on [0, 1]
+const ROOT_ITERATIONS: usize = 52;
+
+#[derive(Debug, Copy, Clone)]
+pub struct CubicBezier {
+    pub from: Vec2,
+    pub ctrl1: Vec2,
+    pub ctrl2: Vec2,
+    pub to: Vec2,
+}
+
+impl CubicBezier {
+    pub fn new(from: Vec2, ctrl1: Vec2, ctrl2: Vec2, to: Vec2) -> Self {
+        CubicBezier {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        }
+    }
+
+    // degree elevation, a quadratic curve is also a cubic curve.
+    pub fn from_quadratic(from: Vec2, ctrl: Vec2, to: Vec2) -> Self {
+        CubicBezier::new(
+            from,
+            from + (ctrl - from) * 2.0 / 3.0,
+            to + (ctrl - to) * 2.0 / 3.0,
+            to,
+        )
+    }
+
+    pub fn point(&self, t: f32) -> Vec2 {
+        let [a, b, c, d] = self.coefficients();
+        ((a * t + b) * t + c) * t + d
+    }
+
+    pub fn derivative(&self, t: f32) -> Vec2 {
+        let [a, b, c, _] = self.coefficients();
+        (a * 3.0 * t + b * 2.0) * t + c
+    }
+
+    // The normal of the curve, perpendicular to the derivative. Where the derivative vanishes,
+    // for example when a control point coincides with an end point, the chord is used instead.
+    pub fn normal(&self, t: f32) -> Vec2 {
+        let mut tangent = self.derivative(t);
+        if tangent.length_squared() < f32::EPSILON {
+            tangent = self.to - self.from;
+        }
+        vec2(-tangent.y, tangent.x).normalize()
+    }
+
+    // The curve written as a * t^3 + b * t^2 + c * t + d
+    fn coefficients(&self) -> [Vec2; 4] {
+        let (p0, p1, p2, p3) = (self.from, self.ctrl1, self.ctrl2, self.to);
+        [
+            -p0 + p1 * 3.0 - p2 * 3.0 + p3,
+            p0 * 3.0 - p1 * 6.0 + p2 * 3.0,
+            -p0 * 3.0 + p1 * 3.0,
+            p0,
+        ]
+    }
+
+    // Exact intersection with a ray. The curve is projected on the normal of the ray, the
+    // roots of the resulting cubic are the points where the curve crosses the ray.
+    // It returns the distance along the ray and the parameter of the curve.
+    pub fn intersect_ray(&self, orig: Vec2, dir: Vec2) -> Option<(f32, f32)> {
+        let dir = dir.normalize();
+        let cross = |v: Vec2| dir.x as f64 * v.y as f64 - dir.y as f64 * v.x as f64;
+        let [a, b, c, d] = self.coefficients();
+        let poly = [cross(a), cross(b), cross(c), cross(d - orig)];
+
+        let mut closest: Option<(f32, f32)> = None;
+        for t in cubic_roots_in_unit_interval(poly).iter().flatten() {
+            let t = *t as f32;
+            let distance = (self.point(t) - orig).dot(dir);
+            if distance as f64 > MIN_DISTANCE
+                && closest.is_none_or(|(closest_distance, _)| distance < closest_distance)
+            {
+                closest = Some((distance, t));
+            }
+        }
+        closest
+    }
+}
+
+fn eval_cubic(poly: [f64; 4], t: f64) -> f64 {
+    ((poly[0] * t + poly[1]) * t + poly[2]) * t + poly[3]
+}
+
+// Roots in [0, 1] of poly[0] * t^3 + poly[1] * t^2 + poly[2] * t + poly[3].
+// The interval is split where the derivative is zero, in each piece the polynomial is monotonic
+// and a root, if there is one, is found by bisection.
+pub fn cubic_roots_in_unit_interval(poly: [f64; 4]) -> [Option<f64>; 3] {
+    let mut bounds = vec![0.0];
+    // derivative: 3a t^2 + 2b t + c
+    let (a, b, c) = (3.0 * poly[0], 2.0 * poly[1], poly[2]);
+    let mut critical: Vec<f64> = Vec::new();
+    if a.abs() > f64::EPSILON {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant >= 0.0 {
+            let sqrt = discriminant.sqrt();
+            critical.push((-b - sqrt) / (2.0 * a));
+            critical.push((-b + sqrt) / (2.0 * a));
+        }
+    } else if b.abs() > f64::EPSILON {
+        critical.push(-c / b);
+    }
+    critical.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    bounds.extend(critical.into_iter().filter(|t| *t > 0.0 && *t < 1.0));
+    bounds.push(1.0);
+
+    let mut roots = [None; 3];
+    let mut n_roots = 0;
+    for pair in bounds.windows(2) {
+        let (mut lo, mut hi) = (pair[0], pair[1]);
+        let (mut f_lo, f_hi) = (eval_cubic(poly, lo), eval_cubic(poly, hi));
+        let root = if f_lo == 0.0 && f_hi == 0.0 {
+            // the curve lies on the ray, there is no single crossing point
+            continue;
+        } else if f_lo == 0.0 {
+            lo
+        } else if f_hi == 0.0 {
+            hi
+        } else if f_lo.signum() == f_hi.signum() {
+            continue;
+        } else {
+            for _ in 0..ROOT_ITERATIONS {
+                let mid = (lo + hi) / 2.0;
+                let f_mid = eval_cubic(poly, mid);
+                if f_mid.signum() == f_lo.signum() {
+                    lo = mid;
+                    f_lo = f_mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            (lo + hi) / 2.0
+        };
+        // a root on the boundary between two pieces is found twice
+        let duplicate = roots[..n_roots]
+            .iter()
+            .flatten()
+            .any(|r| (r - root).abs() < 1e-9);
+        if !duplicate && n_roots < 3 {
+            roots[n_roots] = Some(root);
+            n_roots += 1;
+        }
+    }
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(poly: [f64; 4], expected: &[f64]) {
+        let roots: Vec<f64> = cubic_roots_in_unit_interval(poly)
+            .iter()
+            .flatten()
+            .cloned()
+            .collect();
+        assert_eq!(roots.len(), expected.len(), "{:?}", roots);
+        for (root, expected) in roots.iter().zip(expected.iter()) {
+            assert!((root - expected).abs() < 1e-9, "{:?}", roots);
+        }
+    }
+
+    #[test]
+    fn three_roots() {
+        // (t - 0.2) * (t - 0.5) * (t - 0.8)
+        assert_roots([1.0, -1.5, 0.66, -0.08], &[0.2, 0.5, 0.8]);
+    }
+
+    #[test]
+    fn double_root() {
+        // (t - 0.5)^2 * (t - 2), the curve touches the ray
+        assert_roots([1.0, -3.0, 2.25, -0.5], &[0.5]);
+    }
+
+    #[test]
+    fn roots_outside_of_the_unit_interval() {
+        assert_roots([0.0, 0.0, 1.0, -2.0], &[]);
+        // (t + 1) * (t - 2) * (t - 3)
+        assert_roots([1.0, -4.0, 1.0, 6.0], &[]);
+    }
+
+    #[test]
+    fn degenerate_polynomials() {
+        // (t - 0.25) * (t - 0.75)
+        assert_roots([0.0, 1.0, -1.0, 0.1875], &[0.25, 0.75]);
+        assert_roots([0.0, 0.0, 2.0, -1.0], &[0.5]);
+        assert_roots([0.0, 0.0, 0.0, 1.0], &[]);
+        // the curve lies on the ray
+        assert_roots([0.0, 0.0, 0.0, 0.0], &[]);
+    }
+
+    #[test]
+    fn roots_on_the_ends() {
+        assert_roots([0.0, 0.0, 1.0, 0.0], &[0.0]);
+        assert_roots([0.0, 0.0, 1.0, -1.0], &[1.0]);
+    }
+
+    #[test]
+    fn ray_hits_a_quadratic_curve() {
+        let curve = CubicBezier::from_quadratic(vec2(-1.0, 0.0), vec2(0.0, 2.0), vec2(1.0, 0.0));
+        assert_eq!(curve.point(0.5), vec2(0.0, 1.0));
+        let (distance, t) = curve
+            .intersect_ray(vec2(0.0, -5.0), vec2(0.0, 1.0))
+            .unwrap();
+        assert!((distance - 6.0).abs() < 1e-5);
+        assert!((t - 0.5).abs() < 1e-5);
+        assert!(curve.normal(t).distance(vec2(0.0, 1.0)) < 1e-5);
+        // tangent to the top of the curve
+        let (distance, t) = curve
+            .intersect_ray(vec2(-5.0, 1.0), vec2(1.0, 0.0))
+            .unwrap();
+        assert!((distance - 5.0).abs() < 1e-4);
+        assert!((t - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ray_takes_the_closest_crossing() {
+        // an arch crossed twice by a horizontal ray
+        let curve = CubicBezier::new(
+            vec2(0.0, 0.0),
+            vec2(0.0, 4.0),
+            vec2(4.0, 4.0),
+            vec2(4.0, 0.0),
+        );
+        let (distance, t) = curve
+            .intersect_ray(vec2(-2.0, 1.0), vec2(1.0, 0.0))
+            .unwrap();
+        assert!(t < 0.5);
+        assert!((curve.point(t) - vec2(-2.0 + distance, 1.0)).length() < 1e-4);
+        // going away from the curve
+        assert!(curve
+            .intersect_ray(vec2(-2.0, 1.0), vec2(-1.0, 0.0))
+            .is_none());
+        // above the curve
+        assert!(curve
+            .intersect_ray(vec2(-2.0, 5.0), vec2(1.0, 0.0))
+            .is_none());
+    }
+
+    #[test]
+    fn degenerate_derivative() {
+        // the first control point coincides with the start, the chord gives the normal
+        let curve = CubicBezier::new(
+            vec2(0.0, 0.0),
+            vec2(0.0, 0.0),
+            vec2(2.0, 0.0),
+            vec2(3.0, 0.0),
+        );
+        assert_eq!(curve.normal(0.0), vec2(0.0, 1.0));
+    }
+}
@@ -0,0 +1,181 @@
+use nannou::prelude::*;
+
+// A conic section, in its local coordinate system.
+// The parameter `t` that describes a point on the curve is:
+// - Parabola: the y coordinate of the point.
+// - Hyperbola: the hyperbolic angle, the point is (a * cosh(t), b * sinh(t)).
+// - Ellipse: the angle, the point is (a * cos(t), b * sin(t)).
+#[derive(Debug, Copy, Clone)]
+pub enum ConicShape {
+    // y^2 = 4 * focal_length * x. The vertex is in the origin and the parabola opens towards +x.
+    Parabola { focal_length: f32 },
+    // x^2 / a^2 - y^2 / b^2 = 1. Only the branch with x > 0 is used.
+    Hyperbola { a: f32, b: f32 },
+    // x^2 / a^2 + y^2 / b^2 = 1
+    Ellipse { a: f32, b: f32 },
+}
+
+impl ConicShape {
+    // Coefficients (p, q, r, s) of the implicit equation p * x^2 + q * y^2 + r * x + s = 0
+    pub fn coefficients(&self) -> (f64, f64, f64, f64) {
+        match *self {
+            ConicShape::Parabola { focal_length } => (0.0, 1.0, -4.0 * focal_length as f64, 0.0),
+            ConicShape::Hyperbola { a, b } => (
+                1.0 / (a as f64 * a as f64),
+                -1.0 / (b as f64 * b as f64),
+                0.0,
+                -1.0,
+            ),
+            ConicShape::Ellipse { a, b } => (
+                1.0 / (a as f64 * a as f64),
+                1.0 / (b as f64 * b as f64),
+                0.0,
+                -1.0,
+            ),
+        }
+    }
+
+    pub fn point(&self, t: f32) -> Vec2 {
+        match *self {
+            ConicShape::Parabola { focal_length } => vec2(t * t / (4.0 * focal_length), t),
+            ConicShape::Hyperbola { a, b } => vec2(a * t.cosh(), b * t.sinh()),
+            ConicShape::Ellipse { a, b } => vec2(a * t.cos(), b * t.sin()),
+        }
+    }
+
+    // The parameter of a point that lies on the curve.
+    pub fn parameter(&self, point: Vec2) -> f32 {
+        match *self {
+            ConicShape::Parabola { .. } => point.y,
+            ConicShape::Hyperbola { b, .. } => (point.y / b).asinh(),
+            ConicShape::Ellipse { a, b } => (point.y / b).atan2(point.x / a),
+        }
+    }
+
+    // The focus on the concave side of the curve.
+    pub fn focus(&self) -> Vec2 {
+        match *self {
+            ConicShape::Parabola { focal_length } => vec2(focal_length, 0.0),
+            ConicShape::Hyperbola { a, b } => vec2((a * a + b * b).sqrt(), 0.0),
+            ConicShape::Ellipse { a, b } => {
+                let c = (a * a - b * b).abs().sqrt();
+                if a >= b {
+                    vec2(-c, 0.0)
+                } else {
+                    vec2(0.0, -c)
+                }
+            }
+        }
+    }
+
+    // The gradient of the implicit equation, perpendicular to the curve. It points away from the
+    // focus for the parabola and the ellipse, towards the focus for the hyperbola.
+    pub fn gradient(&self, point: Vec2) -> Vec2 {
+        let (p, q, r, _) = self.coefficients();
+        vec2(
+            (2.0 * p * point.x as f64 + r) as f32,
+            (2.0 * q * point.y as f64) as f32,
+        )
+    }
+
+    // Distances along the line through `orig` with direction `dir` where it meets the curve,
+    // sorted. Both branches and the whole curve are considered, not only an arc.
+    pub fn intersect_line(&self, orig: Vec2, dir: Vec2) -> [Option<f64>; 2] {
+        let (ox, oy) = (orig.x as f64, orig.y as f64);
+        let (dx, dy) = (dir.x as f64, dir.y as f64);
+        let (p, q, r, s) = self.coefficients();
+
+        // substitute the line in the implicit equation of the conic
+        let a = p * dx * dx + q * dy * dy;
+        let b = 2.0 * (p * ox * dx + q * oy * dy) + r * dx;
+        let c = p * ox * ox + q * oy * oy + r * ox + s;
+        solve_quadratic(a, b, c)
+    }
+}
+
+// Real roots of a * t^2 + b * t + c = 0, sorted. It uses the numerically stable form, as `a`
+// goes to zero when a ray is parallel to the axis of a parabola.
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> [Option<f64>; 2] {
+    if a == 0.0 {
+        if b == 0.0 {
+            return [None, None];
+        }
+        return [Some(-c / b), None];
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    let t0 = q / a;
+    let t1 = if q != 0.0 { c / q } else { t0 };
+    if t0 < t1 {
+        [Some(t0), Some(t1)]
+    } else {
+        [Some(t1), Some(t0)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quadratic_roots_are_sorted() {
+        // (t - 1) * (t - 3)
+        assert_eq!(solve_quadratic(1.0, -4.0, 3.0), [Some(1.0), Some(3.0)]);
+        assert_eq!(solve_quadratic(-1.0, 4.0, -3.0), [Some(1.0), Some(3.0)]);
+        // (t - 2)^2
+        assert_eq!(solve_quadratic(1.0, -4.0, 4.0), [Some(2.0), Some(2.0)]);
+        assert_eq!(solve_quadratic(1.0, 0.0, 1.0), [None, None]);
+    }
+
+    #[test]
+    fn degenerate_quadratics() {
+        assert_eq!(solve_quadratic(0.0, 2.0, -4.0), [Some(2.0), None]);
+        assert_eq!(solve_quadratic(0.0, 0.0, 1.0), [None, None]);
+    }
+
+    #[test]
+    fn line_parallel_to_the_axis_of_a_parabola() {
+        let parabola = ConicShape::Parabola { focal_length: 1.0 };
+        let roots = parabola.intersect_line(vec2(5.0, 2.0), vec2(-1.0, 0.0));
+        assert_eq!(roots, [Some(4.0), None]);
+        assert_eq!(parabola.point(2.0), vec2(1.0, 2.0));
+    }
+
+    #[test]
+    fn line_through_both_branches_of_a_hyperbola() {
+        let hyperbola = ConicShape::Hyperbola { a: 3.0, b: 4.0 };
+        let roots = hyperbola.intersect_line(vec2(-10.0, 0.0), vec2(1.0, 0.0));
+        assert!((roots[0].unwrap() - 7.0).abs() < 1e-9);
+        assert!((roots[1].unwrap() - 13.0).abs() < 1e-9);
+        assert_eq!(hyperbola.focus(), vec2(5.0, 0.0));
+    }
+
+    #[test]
+    fn gradient_orientation() {
+        let parabola = ConicShape::Parabola { focal_length: 1.0 };
+        assert!(parabola.gradient(vec2(0.0, 0.0)).x < 0.0);
+        let ellipse = ConicShape::Ellipse { a: 5.0, b: 3.0 };
+        assert!(ellipse.gradient(vec2(-5.0, 0.0)).x < 0.0);
+        assert_eq!(ellipse.focus(), vec2(-4.0, 0.0));
+        // the vertex of the hyperbola is between the center and the focus
+        let hyperbola = ConicShape::Hyperbola { a: 3.0, b: 4.0 };
+        assert!(hyperbola.gradient(vec2(3.0, 0.0)).x > 0.0);
+    }
+
+    #[test]
+    fn parameters_of_the_points() {
+        let shapes = [
+            ConicShape::Parabola { focal_length: 2.0 },
+            ConicShape::Hyperbola { a: 3.0, b: 4.0 },
+            ConicShape::Ellipse { a: 5.0, b: 3.0 },
+        ];
+        for shape in shapes.iter() {
+            for t in [-1.0, 0.0, 0.5, 1.5].iter() {
+                assert!((shape.parameter(shape.point(*t)) - t).abs() < 1e-5);
+            }
+        }
+    }
+}
@@ -1,6 +1,13 @@
 #[allow(dead_code)]
 use nannou::prelude::*;
 
+// exact intersections between rays and curves
+pub mod bezier;
+pub mod conic;
+
+pub use bezier::CubicBezier;
+pub use conic::ConicShape;
+
 #[derive(Debug, Copy, Clone)]
 pub enum BoundingVolume {
     Circle { position: Vec2, radius: f32 },
@@ -221,6 +228,12 @@ impl Ray2D {
     }
 }
 
+impl Default for Ray2D {
+    fn default() -> Self {
+        Ray2D::new()
+    }
+}
+
 // pub trait Intersectable {
 //     fn intersect(&self, ray: &Ray2D) -> Option<f32>;
 // }
@@ -1,33 +1,19 @@
-use edapx_colors::Palette;
+use edapx_colors::{show_report, Deficiency, Palette};
+use edapx_settings::{settings, Settings as _};
 use nannou::prelude::*;
-use nannou_conrod as ui;
-use nannou_conrod::prelude::*;
+use nannou_egui::{self, egui, Egui};
 use rayon::prelude::*;
-use scene::Material;
-use wall_helper::change_surface_walls;
-
-mod gui;
-mod ray_light;
-mod scene;
-pub mod wraycaster;
-use crate::scene::Element;
-use crate::scene::SurfaceType;
-mod mondrian;
-pub use crate::mondrian::Square;
-mod bouncing;
-pub use crate::bouncing::BouncingRay2D;
-mod ray_helper;
-use crate::ray_helper::make_raycasters;
-mod wall_helper;
-use crate::wall_helper::change_color_walls;
-use crate::wall_helper::make_walls;
-mod raycaster;
-pub use crate::wraycaster::Wraycaster;
-use ray2d::BoundingVolume;
-
-use nannou_postprocessing::PostProcessingEffect;
+
+use edapx_raycast::ray_helper::make_raycasters;
+use edapx_raycast::scene::{Element, Material, SurfaceType};
+use edapx_raycast::wall_helper::{change_color_walls, change_surface_walls, make_walls};
+use edapx_raycast::Wraycaster;
+
+use edapx_postprocessing::{Effect, EffectChain, Output, Uniforms};
 
 const EPSILON: f32 = 0.05;
+const GUI_W: u32 = 600;
+const GUI_H: u32 = 900;
 
 // TODO
 
@@ -38,48 +24,63 @@ fn main() {
     nannou::app(model).update(update).run();
 }
 
+settings! {
+    struct Settings {
+        "Walls" {
+            wall_width: f32 = 2.0, 1.0..=15.0;
+            wall_split: f32 = 1.0, 0.0..=1.0;
+            wall_padding: f32 = 0.07, 0.02..=0.2;
+            hole_pct: f32 = 0.25, 0.0..=0.9;
+            hole_n: usize = 2, 0..=6;
+            wall_shape: usize = 0, 0..=3;
+            tile_count_w: u32 = 8, 1..=20;
+            show_walls: bool = true;
+        }
+        "Rays" {
+            n_caster: u32 = 2, 1..=50;
+            raycaster_density: usize = 6, 1..=36;
+            rays_position_mode: usize = 1, 0..=1;
+            collision_radius: f32 = 3.0, 0.0..=185.0;
+            ray_width: f32 = 3.0, 0.5..=10.0;
+            rays_prob: f32 = 0.8, 0.0..=1.0;
+            max_bounces: usize = 4, 1..=6;
+            rotation: f32 = 0.0, -PI..=PI;
+            draw_rays: bool = false;
+            draw_not_colliding_rays: bool = false;
+        }
+        "Colors" {
+            scheme_id: usize = 5, 0..=5;
+            blend_id: usize = 0, 0..=3;
+            color_off: usize = 4, 0..=4;
+            light_color_pct: f32 = 0.5, 0.0..=1.0;
+            palette_alpha: f32 = 0.6, 0.0..=1.0;
+            // 0 is normal vision, then protanopia, deuteranopia and tritanopia
+            vision: usize = 0, 0..=3;
+        }
+        "Style" {
+            polygon_contour_weight: f32 = 5.0, 0.5..=30.0;
+            draw_polygon: bool = true;
+            draw_polygon_mode: usize = 0, 0..=2;
+            draw_arrows: bool = false;
+        }
+        "Animation" {
+            animation: bool = false;
+            animation_mode: usize = 0, 0..=1;
+            animation_speed: f32 = 2.0, 0.01..=80.0;
+        }
+    }
+}
+
 struct Model {
     canvas_rect: geom::Rect,
     scene: Vec<Element>,
-    tile_count_w: u32,
-    n_caster: u32,
-    raycaster_density: usize,
     rays: Vec<Wraycaster>,
-    rays_position_mode: usize,
-    ui: Ui,
-    ids: gui::Ids,
-    ray_width: f32,
-    rays_prob: f32,
-    wall_width: f32,
-    wall_split: f32,
-    hole_pct: f32,
-    hole_n: usize,
-    wall_padding: f32,
-    collision_radius: f32,
-    rotation: f32,
-    scheme_id: usize,
-    max_bounces: usize,
-    blend_id: usize,
-    color_off: usize,
-    palette_alpha: f32,
-    light_color_pct: f32,
+    settings: Settings,
+    preset_path: std::path::PathBuf,
+    egui: Egui,
     palette: Palette,
-    show_walls: bool,
-    clean_bg: bool,
-    transparent_bg: bool,
-    draw_arrows: bool,
-    draw_rays: bool,
-    draw_not_colliding_rays: bool,
-    animation: bool,
-    animation_mode: usize,
-    animation_speed: f32,
-    animation_time: f32,
-    draw_polygon: bool,
-    draw_polygon_mode: usize,
-    polygon_contour_weight: f32,
-    clear_interval: usize,
     material: Material,
-    effect: PostProcessingEffect,
+    effect: EffectChain,
 }
 
 fn model(app: &App) -> Model {
@@ -92,179 +93,128 @@ fn model(app: &App) -> Model {
     // we also draw on a 4k canvas
     let canvas_rect = geom::Rect::from_w_h(texture_size[0] as f32, texture_size[1] as f32);
 
-    let tile_count_w = 8;
     let main_window_id = app
         .new_window()
         .size(win_w, win_h)
         .view(view)
-        .raw_event(raw_window_event)
         .build()
         .unwrap();
 
-    let sample_count = app.window(main_window_id).unwrap().msaa_samples();
-
-    // load the sahders
-    let vs_desc = wgpu::include_wgsl!("shaders/vs.wgsl");
-    // at the moment PostProcessingEffect works only with the default sample_count value, that is 4.
-    let fs_desc = wgpu::include_wgsl!("shaders/cell_noise.wgsl");
+    let main_window = app.window(main_window_id).unwrap();
+    let device = main_window.device();
+    let sample_count = main_window.msaa_samples();
 
-    let effect = PostProcessingEffect::new(
+    // the passes run in order, each one can be disabled from the controls
+    let mut effect = EffectChain::new(
         texture_size,
         sample_count,
-        app.window(main_window_id).unwrap().device(),
-        vs_desc,
-        fs_desc,
+        device,
+        Output::window(&main_window),
+    );
+    // the previous frames under the drawing, with a decay of 0.0 the background is cleared
+    effect.add_effect(device, Effect::Feedback);
+    effect.set("feedback", "decay", 0.0).unwrap();
+    effect.add_pass(
+        device,
+        "cell noise",
+        include_str!("shaders/cell_noise.wgsl"),
+        Uniforms::new()
+            .float("scale", 3.0, 1.0..=10.0)
+            .float("strength", 0.14, 0.0..=0.5)
+            .float("speed", 0.2, 0.0..=2.0),
     );
+    // the light of the rays glows, off at the beginning
+    for e in [Effect::Bloom, Effect::Vignette].iter() {
+        effect.add_effect(device, *e);
+        effect.set_enabled(e.name(), false);
+    }
+    drop(main_window);
 
     // Create the UI.
     let ui_window = app
         .new_window()
         .title(app.exe_name().unwrap() + " controls")
-        .size(gui::WIN_W, gui::WIN_H)
+        .size(GUI_W, GUI_H)
         .view(ui_view)
-        .event(ui_event)
         .raw_event(raw_window_event)
         .build()
         .unwrap();
+    let egui = Egui::from_window(&app.window(ui_window).unwrap());
 
-    //let mut ui = app.new_ui().window(ui_window).build().unwrap();
-    let mut ui = ui::builder(app).window(ui_window).build().unwrap();
-
-    let ids = gui::Ids::new(ui.widget_id_generator());
-    ui.clear_with(color::DARK_CHARCOAL);
-    let mut theme = ui.theme_mut();
-    theme.label_color = color::WHITE;
-    theme.shape_color = color::CHARCOAL;
+    // the default values can be changed from the command line, run with --help to see them
+    let settings = Settings::from_env();
+    let preset_path = app
+        .assets_path()
+        .unwrap()
+        .join(app.exe_name().unwrap() + ".toml");
 
     // initialize the fields of the model
     let mut scene: Vec<Element> = Vec::new();
     let mut rays: Vec<Wraycaster> = Vec::new();
-    let ray_width = 3.0;
-    let wall_width = 2.0;
-    let wall_split = 1.0;
-    let wall_padding = 0.07;
-    let hole_pct = 0.25;
-    let hole_n = 2;
-    let n_caster = 2;
-    let max_bounces = 4;
-    let rotation = 0.0;
-    let collision_radius = 3.0;
-    let rays_prob = 0.8;
-    let rays_position_mode = 1;
-
-    let scheme_id = 5;
-    let blend_id = 0;
-    let color_off = 4;
-    let light_color_pct: f32 = 0.5;
-    let palette = Palette::new();
-    let clear_interval = 14;
+    let mut palette = Palette::new();
+    palette.opacity = settings.palette_alpha;
+    palette.vision = vision(&settings);
+    set_background(&mut effect, &palette, &settings);
     let max_depth = 4;
-    let raycaster_density = 6;
     let material = Material::default();
     make_walls(
         &mut scene,
         &canvas_rect,
-        tile_count_w,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        hole_n,
-        palette.get_first(scheme_id, color_off),
-        palette.get_second(scheme_id, color_off),
+        settings.tile_count_w,
+        settings.wall_split,
+        settings.wall_padding,
+        settings.hole_pct,
+        settings.hole_n,
+        palette.get_first(settings.scheme_id, settings.color_off),
+        palette.get_second(settings.scheme_id, settings.color_off),
         &material,
+        settings.wall_shape,
     );
     make_raycasters(
         &mut rays,
         &canvas_rect,
-        tile_count_w,
-        n_caster,
+        settings.tile_count_w,
+        settings.n_caster,
         max_depth,
-        raycaster_density,
+        settings.raycaster_density,
         &scene,
-        rays_position_mode,
-        rays_prob,
+        settings.rays_position_mode,
+        settings.rays_prob,
     );
     // walls: & Vec<Curve>,
     // rays_position_mode: usize,
     // rays_probability: f32,
-    let show_walls = true;
-    let animation = false;
-    let draw_arrows = false;
-    let animation_speed = 2.0;
-    let animation_mode = 0;
-    let animation_time = 0.0;
-    let draw_polygon = true;
-    let draw_polygon_mode = 0;
-    let draw_rays = false;
-    let polygon_contour_weight = 5.0;
-    let draw_not_colliding_rays = false;
-    let clean_bg = true;
-    let transparent_bg = false;
-    let palette_alpha = 1.0;
-
-    let mut the_model = Model {
+
+    Model {
         canvas_rect,
         scene,
-        n_caster,
-        raycaster_density,
-        tile_count_w,
         rays,
-        rays_position_mode,
-        max_bounces,
-        ui,
-        ids,
-        wall_width,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        hole_n,
-        collision_radius,
-        ray_width,
-        rays_prob,
-        rotation,
-        scheme_id,
-        blend_id,
-        palette_alpha,
-        color_off,
-        light_color_pct,
+        settings,
+        preset_path,
+        egui,
         palette,
-        show_walls,
-        animation,
-        animation_speed,
-        animation_mode,
-        animation_time,
-        draw_polygon,
-        draw_polygon_mode,
-        draw_rays,
-        draw_arrows,
-        clean_bg,
-        transparent_bg,
-        polygon_contour_weight,
-        draw_not_colliding_rays,
-        clear_interval,
         material,
         effect,
-    };
-    ui_event(&app, &mut the_model, WindowEvent::Focused);
-    the_model
+    }
 }
 
-fn update(app: &App, model: &mut Model, _update: Update) {
+fn update(app: &App, model: &mut Model, update: Update) {
+    update_ui(model, &update);
+
     // Use the frame number to animate, ensuring we get a constant update time.
     let elapsed_frames = app.main_window().elapsed_frames();
 
-    
     let time = elapsed_frames as f32 / 60.0;
     // let time = app.time;
 
-    let rot = model.rotation;
-    let anim = model.animation;
-    let anim_speed = model.animation_speed;
+    let rot = model.settings.rotation;
+    let anim = model.settings.animation;
+    let anim_speed = model.settings.animation_speed;
     let scene = &model.scene;
     let canvas_rect = model.canvas_rect;
-    let animation_mode = model.animation_mode;
+    let animation_mode = model.settings.animation_mode;
 
-    if model.animation {
+    if model.settings.animation {
         // Animate raycaster
         model
             .rays
@@ -283,23 +233,15 @@ fn update(app: &App, model: &mut Model, _update: Update) {
     //let mut d = app.draw();
     d.reset();
     let blends = [BLEND_NORMAL, BLEND_ADD, BLEND_SUBTRACT, BLEND_LIGHTEST];
-    let draw = d.color_blend(blends[model.blend_id].clone());
-
-    if model.transparent_bg {
-        let mut color = model.palette.get_fifth(model.scheme_id, model.color_off);
-        color.alpha = 0.0;
-        draw.background().color(color);
-    }
+    let draw = d.color_blend(blends[model.settings.blend_id].clone());
 
-    if model.clean_bg && !model.transparent_bg {
-        let mut color = model.palette.get_fifth(model.scheme_id, model.color_off);
-        color.alpha = 1.0;
-        draw.background().color(color);
-    }
+    // the drawing is transparent, the feedback pass puts it over the previous frames and over
+    // the background
+    draw.background().color(rgba(0.0, 0.0, 0.0, 0.0));
 
-    if model.show_walls {
+    if model.settings.show_walls {
         for element in model.scene.iter() {
-            element.draw(&draw, &model.wall_width);
+            element.draw(&draw, &model.settings.wall_width);
             // Debug bounding volume
             // if let Some(c) = element.bounding_volume() {
             //     match c {
@@ -309,7 +251,7 @@ fn update(app: &App, model: &mut Model, _update: Update) {
             //                 .x_y(position.x, position.y)
             //                 .w_h(radius * 2.0, radius * 2.0)
             //                 .color(element.material().coloration)
-            //                 .stroke_weight(model.wall_width);
+            //                 .stroke_weight(model.settings.wall_width);
             //         }
             //         _ => {}
             //     }
@@ -318,22 +260,26 @@ fn update(app: &App, model: &mut Model, _update: Update) {
     }
 
     for r in &model.rays {
-        if model.draw_polygon {
+        if model.settings.draw_polygon {
             r.draw_polygon(
                 &draw,
-                model.polygon_contour_weight,
-                model.ray_width,
-                model.draw_not_colliding_rays,
-                model.draw_polygon_mode,
+                model.settings.polygon_contour_weight,
+                model.settings.ray_width,
+                model.settings.draw_not_colliding_rays,
+                model.settings.draw_polygon_mode,
             );
         }
 
-        if model.draw_arrows {
-            r.draw_arrows(&draw, model.ray_width);
+        if model.settings.draw_arrows {
+            r.draw_arrows(&draw, model.settings.ray_width);
         }
 
-        if model.draw_rays {
-            r.draw_rays(&draw, model.ray_width, model.draw_not_colliding_rays);
+        if model.settings.draw_rays {
+            r.draw_rays(
+                &draw,
+                model.settings.ray_width,
+                model.settings.draw_not_colliding_rays,
+            );
         }
     }
 
@@ -350,318 +296,146 @@ fn view(_app: &App, model: &Model, frame: Frame) {
     model.effect.view(frame);
 }
 
-fn ui_event(_app: &App, model: &mut Model, _event: WindowEvent) {
-    let ui = &mut model.ui.set_widgets();
-    {
-        // WALLS
-        for value in gui::slider(model.wall_width as f32, 1.0, 15.0)
-            .top_left_with_margin(20.0)
-            .label("wall width")
-            .set(model.ids.wall_width, ui)
-        {
-            model.wall_width = value;
-        }
-
-        for value in gui::slider(model.wall_split as f32, 0.0, 1.0)
-            .label("wall split")
-            .set(model.ids.wall_split, ui)
-        {
-            model.wall_split = value;
-        }
-
-        for value in gui::slider(model.wall_padding as f32, 0.2, 0.02)
-            .label("wall padding")
-            .set(model.ids.wall_padding, ui)
-        {
-            model.wall_padding = value;
-        }
-
-        for value in gui::slider(model.hole_pct as f32, 0.0, 0.9)
-            .label("hole")
-            .set(model.ids.hole_pct, ui)
-        {
-            model.hole_pct = value;
-        }
-
-        for value in gui::slider(model.hole_n as f32, 0.0, 6.0)
-            .label("hole_n")
-            .set(model.ids.hole_n, ui)
-        {
-            model.hole_n = value as usize;
-        }
-        for value in gui::slider(model.tile_count_w as f32, 1.0, 20.0)
-            .label("tile_count_w")
-            .set(model.ids.tile_count_w, ui)
-        {
-            model.tile_count_w = value as u32;
-        }
-
-        for v in gui::toggle(model.show_walls as bool)
-            .label("Show wall")
-            .set(model.ids.show_walls, ui)
-        {
-            model.show_walls = v;
-        }
-        for _click in gui::button()
-            .label("Walls Refl Refr")
-            .set(model.ids.button_refl_refr, ui)
-        {
-            let surface = SurfaceType::ReflectiveAndRefractive {
-                reflectivity: 1.0,
-                ior: 1.4,
-            };
-            model.material.surface = surface;
-            change_surface_walls(&mut model.scene, &surface)
-        }
-        for _click in gui::button()
-            .label("Walls Refl")
-            .set(model.ids.button_refl, ui)
-        {
-            let surface = SurfaceType::Reflective { reflectivity: 1.0 };
-            model.material.surface = surface;
-            change_surface_walls(&mut model.scene, &surface)
-        }
-        for _click in gui::button()
-            .label("Walls Diffuse")
-            .set(model.ids.button_diffuse, ui)
-        {
-            let surface = SurfaceType::Diffuse;
-            model.material.surface = surface;
-            change_surface_walls(&mut model.scene, &surface)
-        }
-
-        for _click in gui::button()
-            .label("Regenerate Walls")
-            .set(model.ids.button_regenerate, ui)
-        {
-            let canvas_rect = model.canvas_rect;
-            make_walls(
-                &mut model.scene,
-                &canvas_rect,
-                model.tile_count_w,
-                model.wall_split,
-                model.wall_padding,
-                model.hole_pct,
-                model.hole_n,
-                model.palette.get_first(model.scheme_id, model.color_off),
-                model.palette.get_second(model.scheme_id, model.color_off),
-                &model.material,
-            );
-
-            make_raycasters(
-                &mut model.rays,
-                &canvas_rect,
-                model.tile_count_w,
-                model.n_caster,
-                model.max_bounces,
-                model.raycaster_density,
-                &model.scene,
-                model.rays_position_mode,
-                model.rays_prob,
-            )
-        }
-
-        // RAYCASTER
-        for value in gui::slider(model.n_caster as f32, 1.0, 50.0)
-            //.left(gui::PAD + gui::COL_W)
-            //.top_left_with_margin_on(model.ids.wall_width, 30.0)
-            .top_left_with_margins_on(model.ids.wall_width, 0.0, gui::PAD + gui::COL_W)
-            //.top_right_with_margins_on(model.ids.wall_width, gui::PAD, 15.0)
-            //.top_right_of(model.ids.wall_width)
-            .label("n_caster ")
-            .set(model.ids.n_caster, ui)
-        {
-            model.n_caster = value as u32;
-        }
-
-        for value in gui::slider(model.raycaster_density as f32, 1.0, 36.0)
-            .label("raycaster_density ")
-            .set(model.ids.raycaster_density, ui)
-        {
-            model.raycaster_density = value as usize;
-        }
-
-        for value in gui::slider(model.rays_position_mode as f32, 0.0, 1.0)
-            .label("raycaster pos mode")
-            .set(model.ids.rays_position_mode, ui)
-        {
-            model.rays_position_mode = value as usize;
-        }
-
-        for value in gui::slider(model.collision_radius as f32, 0.0, 185.0)
-            .label("collision radius")
-            .set(model.ids.collision_radius, ui)
-        {
-            model.collision_radius = value;
-        }
-
-        for value in gui::slider(model.ray_width, 0.5, 10.0)
-            .label("ray width")
-            .set(model.ids.ray_width, ui)
-        {
-            model.ray_width = value;
-        }
-        for value in gui::slider(model.rays_prob as f32, 0.0, 1.0)
-            .label("rays prob.")
-            .set(model.ids.rays_prob, ui)
-        {
-            model.rays_prob = value;
-        }
-        for value in gui::slider(model.max_bounces as f32, 1.0, 6.0)
-            .label("max_bounces")
-            .set(model.ids.max_bounces, ui)
-        {
-            model.max_bounces = value as usize;
-        }
-
-        for value in gui::slider(model.clear_interval as f32, 5.0, 20.0)
-            .label("clear_interval")
-            .set(model.ids.clear_interval, ui)
-        {
-            model.clear_interval = value as usize;
-        }
-
-        for val in gui::slider(model.rotation, -PI, PI)
-            .label("Rotation")
-            .set(model.ids.rotation, ui)
-        {
-            model.rotation = val;
-        }
-
-        for v in gui::toggle(model.draw_rays as bool)
-            .label("Draw rays")
-            .set(model.ids.draw_rays, ui)
-        {
-            model.draw_rays = v;
-        }
-
-        for v in gui::toggle(model.draw_not_colliding_rays as bool)
-            .label("Draw Not Colliding rays")
-            .set(model.ids.draw_not_colliding_rays, ui)
-        {
-            model.draw_not_colliding_rays = v;
-        }
-
-        // COLORS
-        for value in gui::slider(model.scheme_id as f32, 0.0, 5.0)
-            .top_left_with_margins_on(model.ids.n_caster, 0.0, gui::PAD + gui::COL_W)
-            .label("scheme_id")
-            .set(model.ids.scheme_id, ui)
-        {
-            model.scheme_id = value as usize;
-            change_color_walls(
-                &mut model.scene,
-                model.palette.get_first(model.scheme_id, model.color_off),
-                model.palette.get_second(model.scheme_id, model.color_off),
-            );
-        }
-
-        for value in gui::slider(model.blend_id as f32, 0.0, 3.0)
-            .label("blend_id")
-            .set(model.ids.blend_id, ui)
-        {
-            model.blend_id = value as usize;
-        }
-
-        for value in gui::slider(model.color_off as f32, 0.0, 4.0)
-            .label("color_off")
-            .set(model.ids.color_off, ui)
-        {
-            model.color_off = value as usize;
-            change_color_walls(
-                &mut model.scene,
-                model.palette.get_first(model.scheme_id, model.color_off),
-                model.palette.get_second(model.scheme_id, model.color_off),
-            );
-        }
-        for value in gui::slider(model.light_color_pct as f32, 0.0, 1.0)
-            .label("light color %")
-            .set(model.ids.light_color_pct, ui)
-        {
-            model.light_color_pct = value;
-        }
-
-        for value in gui::slider(model.palette_alpha as f32, 0.0, 1.0)
-            .label("palette_alpha")
-            .set(model.ids.palette_alpha, ui)
-        {
-            model.palette_alpha = value;
-            model.palette.set_alpha(value);
-            change_color_walls(
-                &mut model.scene,
-                model.palette.get_first(model.scheme_id, model.color_off),
-                model.palette.get_second(model.scheme_id, model.color_off),
-            );
-        }
-
-        for value in gui::slider(model.polygon_contour_weight, 0.5, 30.0)
-            .label("polygon cont weight")
-            .set(model.ids.polygon_contour_weight, ui)
-        {
-            model.polygon_contour_weight = value;
-        }
+fn update_ui(model: &mut Model, update: &Update) {
+    let egui = &mut model.egui;
+    let settings = &mut model.settings;
+    let effect = &mut model.effect;
+    let palette = &model.palette;
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+
+    let mut changed = Vec::new();
+    let mut surface = None;
+    let mut regenerate = false;
+    let mut save_preset = false;
+    let mut load_preset = false;
+    egui::CentralPanel::default().show(&ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            changed = settings.show(ui);
+
+            // contrast of the scheme and the colors that look the same with a color vision
+            // deficiency
+            if let Some(report) = palette.report(settings.scheme_id, settings.color_off) {
+                egui::CollapsingHeader::new("Color report").show(ui, |ui| show_report(ui, &report));
+            }
+
+            ui.heading("Material");
+            ui.horizontal(|ui| {
+                if ui.button("Walls Refl Refr").clicked() {
+                    surface = Some(SurfaceType::ReflectiveAndRefractive {
+                        reflectivity: 1.0,
+                        ior: 1.4,
+                    });
+                }
+                if ui.button("Walls Refl").clicked() {
+                    surface = Some(SurfaceType::Reflective { reflectivity: 1.0 });
+                }
+                if ui.button("Walls Diffuse").clicked() {
+                    surface = Some(SurfaceType::Diffuse);
+                }
+            });
+            regenerate = ui.button("Regenerate Walls").clicked();
+
+            ui.heading("Effects");
+            effect.show(ui);
+
+            ui.heading("Preset");
+            ui.horizontal(|ui| {
+                save_preset = ui.button("save").clicked();
+                load_preset = ui.button("load").clicked();
+            });
+        });
+    });
+    // the egui frame ends here, `ctx` borrows the model
+    drop(ctx);
+
+    if let Some(surface) = surface {
+        model.material.surface = surface;
+        change_surface_walls(&mut model.scene, &surface)
+    }
 
-        for v in gui::toggle(model.clean_bg as bool)
-            .label("Draw Bg")
-            .set(model.ids.clean_bg, ui)
-        {
-            model.clean_bg = v;
-        }
-        for v in gui::toggle(model.transparent_bg as bool)
-            .label("Transparent Bg")
-            .set(model.ids.transparent_bg, ui)
-        {
-            model.transparent_bg = v;
+    if save_preset {
+        if let Err(err) = model.settings.save_preset(&model.preset_path) {
+            println!("{}", err);
         }
-
-        for v in gui::toggle(model.draw_polygon as bool)
-            .label("Draw poly")
-            .set(model.ids.draw_polygon, ui)
-        {
-            model.draw_polygon = v;
+    }
+    if load_preset {
+        match model.settings.load_preset(&model.preset_path) {
+            Ok(()) => {
+                update_colors(model);
+                regenerate = true;
+            }
+            Err(err) => println!("{}", err),
         }
+    }
 
-        for value in gui::slider(model.draw_polygon_mode as f32, 0.0, 2.0)
-            .label("Draw poly mode")
-            .set(model.ids.draw_polygon_mode, ui)
-        {
-            model.draw_polygon_mode = value as usize;
-        }
+    let colors = ["scheme_id", "color_off", "palette_alpha", "vision"];
+    if changed.iter().any(|name| colors.contains(name)) {
+        update_colors(model);
+    }
 
-        for v in gui::toggle(model.draw_arrows as bool)
-            .label("Draw Arrows")
-            .set(model.ids.draw_arrows, ui)
-        {
-            model.draw_arrows = v;
-        }
+    if regenerate {
+        let canvas_rect = model.canvas_rect;
+        let s = &model.settings;
+        make_walls(
+            &mut model.scene,
+            &canvas_rect,
+            s.tile_count_w,
+            s.wall_split,
+            s.wall_padding,
+            s.hole_pct,
+            s.hole_n,
+            model.palette.get_first(s.scheme_id, s.color_off),
+            model.palette.get_second(s.scheme_id, s.color_off),
+            &model.material,
+            s.wall_shape,
+        );
+
+        make_raycasters(
+            &mut model.rays,
+            &canvas_rect,
+            s.tile_count_w,
+            s.n_caster,
+            s.max_bounces,
+            s.raycaster_density,
+            &model.scene,
+            s.rays_position_mode,
+            s.rays_prob,
+        )
+    }
+}
 
-        for v in gui::toggle(model.animation as bool)
-            .label("Animation")
-            .set(model.ids.animation, ui)
-        {
-            model.animation = v;
-        }
+// The palette takes opacity and vision from the settings, the walls the colors of the scheme.
+fn update_colors(model: &mut Model) {
+    let s = &model.settings;
+    model.palette.opacity = s.palette_alpha;
+    model.palette.vision = vision(s);
+    change_color_walls(
+        &mut model.scene,
+        model.palette.get_first(s.scheme_id, s.color_off),
+        model.palette.get_second(s.scheme_id, s.color_off),
+    );
+    set_background(&mut model.effect, &model.palette, s);
+}
 
-        for value in gui::slider(model.animation_mode as f32, 0.0, 1.0)
-            .label("animation_mode")
-            .set(model.ids.animation_mode, ui)
-        {
-            model.animation_mode = value as usize;
-        }
+// The background of the feedback pass is the fifth color. It is set when the colors change, so
+// that it can still be picked in the effect panel.
+fn set_background(effect: &mut EffectChain, palette: &Palette, settings: &Settings) {
+    let bg = palette.get_fifth(settings.scheme_id, settings.color_off);
+    effect
+        .set("feedback", "background", [bg.red, bg.green, bg.blue, 1.0])
+        .unwrap();
+}
 
-        for value in gui::slider(model.animation_speed as f32, 80.0, 0.01)
-            .label("animation speed")
-            .set(model.ids.animation_speed, ui)
-        {
-            model.animation_speed = value;
-        }
-    }
+fn vision(settings: &Settings) -> Option<Deficiency> {
+    settings.vision.checked_sub(1).map(|i| Deficiency::all()[i])
 }
 
-fn ui_view(app: &App, model: &Model, frame: Frame) {
-    model.ui.draw_to_frame_if_changed(app, &frame).unwrap();
+fn ui_view(_app: &App, model: &Model, frame: Frame) {
+    model.egui.draw_to_frame(&frame).unwrap();
 }
 
-fn raw_window_event(app: &App, model: &mut Model, event: &ui::RawWindowEvent) {
-    model.ui.handle_raw_event(app, event);
+fn raw_window_event(_app: &App, model: &mut Model, event: &nannou::winit::event::WindowEvent) {
+    // Let egui handle things like keyboard and mouse input.
+    model.egui.handle_raw_event(event);
 }
@@ -2,18 +2,6 @@ struct FragmentOutput {
     [[location(0)]] out_color: vec4<f32>;
 };
 
-[[block]]
-struct Data {
-    time: f32;
-};
-
-[[group(0), binding(0)]]
-var tex: texture_multisampled_2d<f32>;
-[[group(0), binding(1)]]
-var tex_sampler: sampler;
-[[group(0), binding(2)]]
-var<uniform> uniforms: Data;
-
 
 fn rand(st:vec2<f32>) -> f32 {
     return fract(sin(dot(st.xy,
@@ -73,14 +61,14 @@ fn main(
     [[location(0)]] tex_coords: vec2<f32>,
 ) -> FragmentOutput {
     // Get the integer tex coordinates.
-    let tex_size: vec2<i32> = textureDimensions(tex);
+    let tex_size: vec2<i32> = source_size();
 
     //let tex_x: i32 = i32(f32(tex_size.x) * tex_coords.x);
 
 
     // 1) let's tile the space
     // Scale
-    let scaled_tex_coords = tex_coords * 3.0;
+    let scaled_tex_coords = tex_coords * uniforms.scale;
 
 
     // Tile the space
@@ -113,7 +101,7 @@ fn main(
     }
 
     let c = (m_dist * 2.0) - 1.0;
-    let animated_c = c * sin(gnoise(vec2<f32>(uniforms.time*0.2))) * 0.14;
+    let animated_c = c * sin(gnoise(vec2<f32>(uniforms.time*uniforms.speed))) * uniforms.strength;
 
     //let tex = texture2D(u_texture_1,(gl_FragCoord.xy/ u_resolution.xy)+animated_c).xyz;
 
@@ -121,21 +109,14 @@ fn main(
     let tex_y: i32 = i32(f32(tex_size.y) * (tex_coords.y + animated_c));
     let itex_coords: vec2<i32> = vec2<i32>(tex_x, tex_y);
 
-    // Average the pixels with its neighbours
+    // the drawing is already resolved by the effect chain
     var color: vec4<f32> = vec4<f32>(0.0, 0.0, 0.0, 0.0);
     if (itex_coords.x >= tex_size.x || itex_coords.x <= 0 || itex_coords.y >= tex_size.y || itex_coords.y <= 0) {
-        color = textureLoad(tex, vec2<i32>(1,1), 0);
+        color = source(vec2<i32>(1,1));
     }else{
-        color = color + textureLoad(tex, itex_coords, 0);
-        color = color + textureLoad(tex, itex_coords, 1);
-        color = color + textureLoad(tex, itex_coords, 2);
-        color = color + textureLoad(tex, itex_coords, 3);
-        color = color * 0.25;
+        color = source(itex_coords);
     };
 
-    // This is wrong, to apply a pixel effect on a pixel already averaged
-    // leads to aliasing. Anyway ...
-
     //color = vec4<f32>(m_dist, 0.0, 1.0, 1.0);
     //color = vec4<f32>(vec3<f32>(tex_coords.x+c, tex_coords.y+c, 1.0), 1.0);
 
@@ -1,8 +1,8 @@
 use edapx_colors::Palette;
+use edapx_settings::{settings, Settings as _};
 use nannou::prelude::*;
-use nannou::ui::prelude::*;
+use nannou_egui::{self, egui, Egui};
 use rayon::prelude::*;
-
 mod mondrian;
 use crate::mondrian::split_squares;
 pub use crate::mondrian::Square;
@@ -16,77 +16,61 @@ fn main() {
     nannou::app(model).update(update).run();
 }
 
+settings! {
+    struct Settings {
+        "Walls" {
+            wall_width: f32 = 2.0, 1.0..=15.0;
+            wall_split: f32 = 0.3, 0.0..=1.0;
+            wall_padding: f32 = 0.07, 0.02..=0.2;
+            hole_pct: f32 = 0.25, 0.0..=0.9;
+            hole_n: usize = 2, 0..=6;
+            tile_count_w: u32 = 8, 1..=20;
+            show_walls: bool = true;
+        }
+        "Rays" {
+            n_caster: u32 = 20, 1..=50;
+            collision_radius: f32 = 3.0, 0.0..=185.0;
+            ray_width: f32 = 3.0, 1.0..=10.0;
+            rays_prob: f32 = 0.0, 0.0..=1.0;
+            max_bounces: usize = 10, 1..=400;
+            rotation: f32 = 0.0, -PI..=PI;
+        }
+        "Colors" {
+            scheme_id: usize = 5, 0..=5;
+            blend_id: usize = 0, 0..=3;
+            color_off: usize = 4, 0..=4;
+        }
+        "Style" {
+            polygon_contour_weight: f32 = 5.0, 1.0..=30.0;
+            draw_polygon: bool = true;
+            draw_arrows: bool = true;
+            draw_tex_overlay: bool = false;
+        }
+        "Animation" {
+            animation: bool = true;
+            animation_speed: f32 = 2.0, 0.01..=80.0;
+        }
+    }
+}
+
 struct Curve {
-    points: Vec<Vector2>,
+    points: Vec<Vec2>,
 }
 
 struct Model {
     walls: Vec<Curve>,
-    tile_count_w: u32,
-    n_caster: u32,
     rays: Vec<BouncingRay2D>,
     draw_gui: bool,
-    ui: Ui,
-    ids: Ids,
-    ray_width: f32,
-    rays_prob: f32,
-    wall_width: f32,
-    wall_split: f32,
-    hole_pct: f32,
-    hole_n: usize,
-    wall_padding: f32,
-    collision_radius: f32,
-    rotation: f32,
-    scheme_id: usize,
-    max_bounces: usize,
-    blend_id: usize,
-    color_off: usize,
+    egui: Egui,
+    settings: Settings,
     palette: Palette,
-    show_walls: bool,
-    draw_arrows: bool,
-    draw_tex_overlay: bool,
-    animation: bool,
-    animation_speed: f32,
     animation_time: f32,
-    draw_polygon: bool,
-    polygon_contour_weight: f32,
     texture: wgpu::Texture,
-    clear_interval: usize,
-}
-
-widget_ids! {
-    struct Ids {
-        wall_width,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        hole_n,
-        tile_count_w,
-        button,
-        n_caster,
-        ray_width,
-        rays_prob,
-        max_bounces,
-        collision_radius,
-        rotation,
-        scheme_id,
-        blend_id,
-        color_off,
-        animation_time,
-        draw_polygon,
-        draw_arrows,
-        polygon_contour_weight,
-        animation,
-        animation_speed,
-        show_walls,
-        draw_tex_overlay,
-        clear_interval
-    }
 }
 
 fn model(app: &App) -> Model {
-    let tile_count_w = 8;
-    app.new_window()
+    let win_id = app
+        .new_window()
         //.size(1280, 720)
         .size(1600, 900)
         //.size(1777, 1000)
@@ -95,6 +79,7 @@ fn model(app: &App) -> Model {
         // .size(2560, 1440) // 16:9
         .view(view)
         .key_pressed(key_pressed)
+        .raw_event(raw_window_event)
         .build()
         .unwrap();
 
@@ -105,49 +90,25 @@ fn model(app: &App) -> Model {
     let draw_gui = true;
 
     // Create the UI.
-    let mut ui = app.new_ui().build().unwrap();
-
-    // Generate some ids for our widgets.
-    let ids = Ids::new(ui.widget_id_generator());
-
-    let ray_width = 3.0;
-    let wall_width = 2.0;
-    let wall_split = 0.3;
-    let wall_padding = 0.07;
-    let hole_pct = 0.25;
-    let hole_n = 2;
-    let n_caster = 20;
-    let max_bounces = 10;
-    let rotation = 0.0;
-    let collision_radius = 3.0;
-    let rays_prob = 0.0;
-
-    let scheme_id = 5;
-    let blend_id = 0;
-    let color_off = 4;
+    let egui = Egui::from_window(&app.window(win_id).unwrap());
+
+    // the default values can be changed from the command line, run with --help to see them
+    let settings = Settings::from_env();
     let palette = Palette::new();
-    let clear_interval = 14;
     make_walls(
         &mut walls,
         &win,
-        tile_count_w,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        hole_n,
-        rays_prob,
-        rotation,
-        n_caster,
+        settings.tile_count_w,
+        settings.wall_split,
+        settings.wall_padding,
+        settings.hole_pct,
+        settings.hole_n,
+        settings.rays_prob,
+        settings.rotation,
+        settings.n_caster,
     );
-    make_rays(&mut rays, &win, tile_count_w, n_caster);
-    let show_walls = true;
-    let animation = true;
-    let draw_arrows = true;
-    let animation_speed = 2.0;
+    make_rays(&mut rays, &win, settings.tile_count_w, settings.n_caster);
     let animation_time = 0.0;
-    let draw_polygon = true;
-    let polygon_contour_weight = 5.0;
-    let draw_tex_overlay = false;
 
     // texture
     // Load the image from disk and upload it to a GPU texture.
@@ -159,273 +120,56 @@ fn model(app: &App) -> Model {
 
     Model {
         walls,
-        n_caster,
-        tile_count_w,
         rays,
-        max_bounces,
         draw_gui,
-        ui,
-        ids,
-        wall_width,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        hole_n,
-        collision_radius,
-        ray_width,
-        rays_prob,
-        rotation,
-        scheme_id,
-        blend_id,
-        color_off,
+        egui,
+        settings,
         palette,
-        show_walls,
-        animation,
-        animation_speed,
         animation_time,
-        draw_polygon,
-        draw_arrows,
-        polygon_contour_weight,
-        draw_tex_overlay,
-        clear_interval,
         texture,
     }
 }
 
-fn update(_app: &App, model: &mut Model, _update: Update) {
-    // Calling `set_widgets` allows us to instantiate some widgets.
-    let ui = &mut model.ui.set_widgets();
-    {
-        fn slider(val: f32, min: f32, max: f32) -> widget::Slider<'static, f32> {
-            widget::Slider::new(val, min, max)
-                .w_h(200.0, 30.0)
-                .label_font_size(15)
-                .rgb(0.3, 0.3, 0.3)
-                .label_rgb(1.0, 1.0, 1.0)
-                .border(0.0)
-        }
-
-        fn toggle(val: bool) -> widget::Toggle<'static> {
-            widget::Toggle::new(val)
-                .w_h(200.0, 30.0)
-                .label_font_size(15)
-                .rgb(0.3, 0.3, 0.3)
-                .label_rgb(1.0, 1.0, 1.0)
-                .border(0.0)
-        }
-
-        for value in slider(model.wall_width as f32, 1.0, 15.0)
-            .top_left_with_margin(10.0)
-            .label("wall width")
-            .set(model.ids.wall_width, ui)
-        {
-            model.wall_width = value;
-        }
-
-        for value in slider(model.wall_split as f32, 0.0, 1.0)
-            .down(3.0)
-            .label("wall split")
-            .set(model.ids.wall_split, ui)
-        {
-            model.wall_split = value;
-        }
-
-        for value in slider(model.wall_padding as f32, 0.2, 0.02)
-            .down(3.0)
-            .label("wall padding")
-            .set(model.ids.wall_padding, ui)
-        {
-            model.wall_padding = value;
-        }
-
-        for value in slider(model.hole_pct as f32, 0.0, 0.9)
-            .down(3.0)
-            .label("hole")
-            .set(model.ids.hole_pct, ui)
-        {
-            model.hole_pct = value;
-        }
-
-        for value in slider(model.hole_n as f32, 0.0, 6.0)
-            .down(1.0)
-            .label("hole_n")
-            .set(model.ids.hole_n, ui)
-        {
-            model.hole_n = value as usize;
-        }
-
-        for value in slider(model.n_caster as f32, 1.0, 50.0)
-            .down(3.0)
-            .label("n_caster ")
-            .set(model.ids.n_caster, ui)
-        {
-            model.n_caster = value as u32;
-        }
-
-        for value in slider(model.tile_count_w as f32, 1.0, 20.0)
-            .down(3.0)
-            .label("tile_count_w")
-            .set(model.ids.tile_count_w, ui)
-        {
-            model.tile_count_w = value as u32;
-        }
-
-        for _click in widget::Button::new()
-            .down(3.0)
-            //.w_h(200.0, 60.0)
-            .label("Regenerate Walls")
-            .label_font_size(15)
-            .rgb(0.3, 0.3, 0.3)
-            .label_rgb(1.0, 1.0, 1.0)
-            .border(0.0)
-            .set(model.ids.button, ui)
-        {
-            let win = _app.window_rect();
-            make_walls(
-                &mut model.walls,
-                &win,
-                model.tile_count_w,
-                model.wall_split,
-                model.wall_padding,
-                model.hole_pct,
-                model.hole_n,
-                model.rays_prob,
-                model.rotation,
-                model.n_caster,
-            );
-            make_rays(&mut model.rays, &win, model.tile_count_w, model.n_caster)
-        }
-
-        for value in slider(model.collision_radius as f32, 0.0, 185.0)
-            .down(3.0)
-            .label("collision radius")
-            .set(model.ids.collision_radius, ui)
-        {
-            model.collision_radius = value;
-        }
-
-        for value in slider(model.ray_width, 1.0, 10.0)
-            .down(3.0)
-            .label("ray width")
-            .set(model.ids.ray_width, ui)
-        {
-            model.ray_width = value;
-        }
-        for value in slider(model.rays_prob as f32, 0.0, 1.0)
-            .down(3.0)
-            .label("rays prob.")
-            .set(model.ids.rays_prob, ui)
-        {
-            model.rays_prob = value;
-        }
-        for value in slider(model.max_bounces as f32, 1.0, 400.0)
-            .down(3.0)
-            .label("max_bounces")
-            .set(model.ids.max_bounces, ui)
-        {
-            model.max_bounces = value as usize;
-        }
-        for value in slider(model.clear_interval as f32, 5.0, 20.0)
-            .down(3.0)
-            .label("clear_interval")
-            .set(model.ids.clear_interval, ui)
-        {
-            model.clear_interval = value as usize;
-        }
-
-        for val in slider(model.rotation, -PI, PI)
-            .down(3.0)
-            .label("Rotation")
-            .set(model.ids.rotation, ui)
-        {
-            model.rotation = val;
-        }
-
-        for value in slider(model.scheme_id as f32, 0.0, 5.0)
-            .down(3.0)
-            .label("scheme_id")
-            .set(model.ids.scheme_id, ui)
-        {
-            model.scheme_id = value as usize;
-        }
-
-        for value in slider(model.blend_id as f32, 0.0, 3.0)
-            .down(3.0)
-            .label("blend_id")
-            .set(model.ids.blend_id, ui)
-        {
-            model.blend_id = value as usize;
-        }
-
-        for value in slider(model.color_off as f32, 0.0, 4.0)
-            .down(3.0)
-            .label("color_off")
-            .set(model.ids.color_off, ui)
-        {
-            model.color_off = value as usize;
-        }
-
-        for value in slider(model.polygon_contour_weight, 1.0, 30.0)
-            .down(3.0)
-            .label("polygon cont weight")
-            .set(model.ids.polygon_contour_weight, ui)
-        {
-            model.polygon_contour_weight = value;
-        }
-
-        for v in toggle(model.draw_polygon as bool)
-            .down(3.0)
-            .label("Draw poly")
-            .set(model.ids.draw_polygon, ui)
-        {
-            model.draw_polygon = v;
-        }
-
-        for v in toggle(model.draw_arrows as bool)
-            .down(3.0)
-            .label("Draw Arrows")
-            .set(model.ids.draw_arrows, ui)
-        {
-            model.draw_arrows = v;
-        }
-
-        for v in toggle(model.draw_tex_overlay as bool)
-            .down(3.0)
-            .label("Draw Overlay")
-            .set(model.ids.draw_tex_overlay, ui)
-        {
-            model.draw_tex_overlay = v;
-        }
-
-        for v in toggle(model.animation as bool)
-            .down(3.0)
-            .label("Animation")
-            .set(model.ids.animation, ui)
-        {
-            model.animation = v;
-        }
-
-        for value in slider(model.animation_speed as f32, 80.0, 0.01)
-            .down(3.0)
-            .label("animation speed")
-            .set(model.ids.animation_speed, ui)
-        {
-            model.animation_speed = value;
-        }
-
-        for v in toggle(model.show_walls as bool)
-            .down(3.0)
-            .label("Show wall")
-            .set(model.ids.show_walls, ui)
-        {
-            model.show_walls = v;
-        }
+fn update(_app: &App, model: &mut Model, update: Update) {
+    let egui = &mut model.egui;
+    let settings = &mut model.settings;
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+    let mut regenerate = false;
+    egui::Window::new("Settings").show(&ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            settings.show(ui);
+            regenerate = ui.button("Regenerate Walls").clicked();
+        });
+    });
+    drop(ctx);
+
+    if regenerate {
+        let win = _app.window_rect();
+        make_walls(
+            &mut model.walls,
+            &win,
+            model.settings.tile_count_w,
+            model.settings.wall_split,
+            model.settings.wall_padding,
+            model.settings.hole_pct,
+            model.settings.hole_n,
+            model.settings.rays_prob,
+            model.settings.rotation,
+            model.settings.n_caster,
+        );
+        make_rays(
+            &mut model.rays,
+            &win,
+            model.settings.tile_count_w,
+            model.settings.n_caster,
+        )
     }
 
     let t = _app.time;
-    let ro = model.rotation;
-    let an = model.animation;
-    let ans = model.animation_speed;
+    let ro = model.settings.rotation;
+    let an = model.settings.animation;
+    let ans = model.settings.animation_speed;
     let ww = &model.walls;
     let win = _app.window_rect();
     model
@@ -441,7 +185,7 @@ fn ray_collides(
     animation_speed: f32,
     time: f32,
     walls: &Vec<Curve>,
-    win : geom::Rect,
+    win: geom::Rect,
 ) {
     r.collisions.clear();
     r.reflections.clear();
@@ -450,22 +194,21 @@ fn ray_collides(
     if animation {
         if r.primary_ray.dir.x > 0.0 {
             r.primary_ray.orig.x += 0.1 * animation_speed;
-        }else{
+        } else {
             r.primary_ray.orig.x -= 0.1 * animation_speed;
         }
         //r.primary_ray.orig = r.primary_ray.orig + r.primary_ray.dir.with_magnitude(animation_speed);
-        if r.primary_ray.orig.x >= win.right() as f32{
+        if r.primary_ray.orig.x >= win.right() as f32 {
             r.primary_ray.orig.x = win.left();
-        }
-        else if r.primary_ray.orig.x <= win.left() as f32{
+        } else if r.primary_ray.orig.x <= win.left() as f32 {
             r.primary_ray.orig.x = win.right();
         }
     }
 
     while !r.max_bounces_reached() {
-        let collision: Vector2;
+        let collision: Vec2;
         let mut distance: f32 = Float::infinity();
-        let mut surface_normal: Vector2 = vec2(0.0, 0.0);
+        let mut surface_normal: Vec2 = vec2(0.0, 0.0);
         // find the closest intersection point between the ray and the walls
         for curve in walls.iter() {
             if let Some(collision) = r.ray.intersect_polyline(&curve.points) {
@@ -478,11 +221,11 @@ fn ray_collides(
         }
         if distance < Float::infinity() {
             // collision point
-            collision = r.ray.orig + r.ray.dir.with_magnitude(distance);
+            collision = r.ray.orig + r.ray.dir.normalize() * distance;
             r.bounces += 1;
             let refl = r.ray.reflect(surface_normal);
             r.refl_intensity.push(r.ray.dir.dot(refl).abs());
-            r.ray.orig = collision + refl.with_magnitude(0.03);
+            r.ray.orig = collision + refl.normalize() * 0.03;
             r.ray.dir = refl;
             r.collisions.push(collision);
             r.reflections.push(refl);
@@ -496,19 +239,22 @@ fn ray_collides(
 
 fn view(app: &App, model: &Model, frame: Frame) {
     let blends = [BLEND_NORMAL, BLEND_ADD, BLEND_SUBTRACT, BLEND_LIGHTEST];
-    let draw = app.draw().color_blend(blends[model.blend_id].clone());
-    frame.clear(model.palette.get_fifth(model.scheme_id, model.color_off));
-
-
-
+    let draw = app
+        .draw()
+        .color_blend(blends[model.settings.blend_id].clone());
+    frame.clear(
+        model
+            .palette
+            .get_fifth(model.settings.scheme_id, model.settings.color_off),
+    );
 
     // // draw the walls
-    // if model.show_walls {
+    // if model.settings.show_walls {
     //     for curve in model.walls.iter() {
     //         //println!("{:?}", curve.points.len());
     //         draw.polyline()
-    //             .weight(model.wall_width)
-    //             .color(model.palette.get_second(model.scheme_id, model.color_off))
+    //             .weight(model.settings.wall_width)
+    //             .color(model.palette.get_second(model.settings.scheme_id, model.settings.color_off))
     //             // look at points_colored
     //             .points(curve.points.clone());
     //         //.caps_round();
@@ -517,17 +263,17 @@ fn view(app: &App, model: &Model, frame: Frame) {
 
     // for r in &model.rays {
     //     draw.arrow()
-    //         .color(model.palette.get_scheme(model.scheme_id)[0])
+    //         .color(model.palette.get_scheme(model.settings.scheme_id)[0])
     //         .start(r.ray.orig)
-    //         .weight(model.ray_width * 2.0)
+    //         .weight(model.settings.ray_width * 2.0)
     //         .end(r.ray.orig + r.ray.dir.with_magnitude(20.0));
     //     for (&c, &i) in r.collisions.iter().zip(r.refl_intensity.iter()) {
     //         draw.ellipse()
     //             .no_fill()
-    //             .stroke(model.palette.get_scheme(model.scheme_id)[2])
+    //             .stroke(model.palette.get_scheme(model.settings.scheme_id)[2])
     //             .stroke_weight(3.0)
     //             .x_y(c.x, c.y)
-    //             .w_h(model.collision_radius * i, model.collision_radius * i);
+    //             .w_h(model.settings.collision_radius * i, model.settings.collision_radius * i);
     //     }
 
     //     let mut col = rgba(0.0, 0.0, 0.0, 0.0);
@@ -538,64 +284,78 @@ fn view(app: &App, model: &Model, frame: Frame) {
     //         .zip(r.reflections.iter())
     //         .map(|(&co, &re)| {
     //             if re.x > 0.0 {
-    //                 col = model.palette.get_scheme(model.scheme_id)[2]
+    //                 col = model.palette.get_scheme(model.settings.scheme_id)[2]
     //             } else {
-    //                 col = model.palette.get_scheme(model.scheme_id)[3]
+    //                 col = model.palette.get_scheme(model.settings.scheme_id)[3]
     //             }
     //             (pt2(co.x, co.y), col)
     //         });
 
-    //     if model.draw_polygon {
+    //     if model.settings.draw_polygon {
     //         draw.polygon().points_colored(ppp);
     //     };
 
     //     draw.path()
     //         .stroke()
-    //         .stroke_weight(model.ray_width)
+    //         .stroke_weight(model.settings.ray_width)
     //         .caps_round()
     //         .points(r.collisions.iter().cloned())
-    //         .color(model.palette.get_scheme(model.scheme_id)[0]);
+    //         .color(model.palette.get_scheme(model.settings.scheme_id)[0]);
 
     //     for (&c, &r) in r.collisions.iter().zip(r.reflections.iter()) {
     //         draw.arrow()
     //             .start(c)
     //             .end(c + r.with_magnitude(20.0))
-    //             .stroke_weight(model.ray_width)
-    //             .color(model.palette.get_scheme(model.scheme_id)[4]);
+    //             .stroke_weight(model.settings.ray_width)
+    //             .color(model.palette.get_scheme(model.settings.scheme_id)[4]);
     //     }
 
-
     //}
 
     // draw the walls
-    if model.show_walls {
+    if model.settings.show_walls {
         for curve in model.walls.iter() {
             //println!("{:?}", curve.points.len());
             draw.polyline()
-                .weight(model.wall_width)
-                .color(model.palette.get_second(model.scheme_id, model.color_off))
+                .weight(model.settings.wall_width)
+                .color(
+                    model
+                        .palette
+                        .get_second(model.settings.scheme_id, model.settings.color_off),
+                )
                 .points(curve.points.clone());
             //.caps_round();
         }
     }
 
     for r in &model.rays {
-        if model.draw_arrows {
+        if model.settings.draw_arrows {
             draw.arrow()
-                .color(model.palette.get_first(model.scheme_id, model.color_off))
+                .color(
+                    model
+                        .palette
+                        .get_first(model.settings.scheme_id, model.settings.color_off),
+                )
                 .start(r.ray.orig)
-                .stroke_weight(model.ray_width)
-                .end(r.ray.orig + r.ray.dir.with_magnitude(ARROW_LENGTH));
+                .stroke_weight(model.settings.ray_width)
+                .end(r.ray.orig + r.ray.dir.normalize() * ARROW_LENGTH);
         }
 
-        if r.collisions.len() > 3 && model.collision_radius > 0.0 {
+        if r.collisions.len() > 3 && model.settings.collision_radius > 0.0 {
             for (&c, &i) in r.collisions.iter().zip(r.refl_intensity.iter()) {
                 draw.ellipse()
                     .no_fill()
-                    .stroke(model.palette.get_third(model.scheme_id, model.color_off))
+                    .stroke(
+                        model
+                            .palette
+                            .get_third(model.settings.scheme_id, model.settings.color_off),
+                    )
                     .stroke_weight(3.0)
                     .x_y(c.x, c.y)
-                    .w_h(model.collision_radius * i, model.collision_radius * i);
+                    .w_h(
+                        model.settings.collision_radius * i,
+                        model.settings.collision_radius * i,
+                    );
             }
         }
 
@@ -607,9 +367,13 @@ fn view(app: &App, model: &Model, frame: Frame) {
             .zip(r.reflections.iter())
             .map(|(&co, &re)| {
                 if re.x > 0.0 {
-                    col = model.palette.get_third(model.scheme_id, model.color_off)
+                    col = model
+                        .palette
+                        .get_third(model.settings.scheme_id, model.settings.color_off)
                 } else {
-                    col = model.palette.get_fourth(model.scheme_id, model.color_off)
+                    col = model
+                        .palette
+                        .get_fourth(model.settings.scheme_id, model.settings.color_off)
                 }
                 // let xc = map_range(co.x, win.left(), win.right(), 0.0, 1.0);
                 // let xy = map_range(co.y, win.bottom(), win.top(), 0.0, 1.0);
@@ -618,37 +382,48 @@ fn view(app: &App, model: &Model, frame: Frame) {
                 (pt2(co.x, co.y), col)
             });
 
-        if model.draw_polygon {
+        if model.settings.draw_polygon {
             if ppp.len() > 3 {
                 draw.polygon()
-                    .stroke(model.palette.get_second(model.scheme_id, model.color_off))
-                    .stroke_weight(model.polygon_contour_weight)
+                    .stroke(
+                        model
+                            .palette
+                            .get_second(model.settings.scheme_id, model.settings.color_off),
+                    )
+                    .stroke_weight(model.settings.polygon_contour_weight)
                     .join_round()
                     .points_colored(ppp);
                 //draw.polygon().points_textured(&model.texture, ppp);
             }
         };
 
-
         if r.collisions.len() > 3 {
             draw.path()
                 .stroke()
                 .caps_round()
-                .stroke_weight(model.ray_width)
+                .stroke_weight(model.settings.ray_width)
                 .points(r.collisions.iter().cloned())
-                .color(model.palette.get_first(model.scheme_id, model.color_off));
+                .color(
+                    model
+                        .palette
+                        .get_first(model.settings.scheme_id, model.settings.color_off),
+                );
         }
 
         for (&c, &r) in r.collisions.iter().zip(r.reflections.iter()) {
-            if model.draw_arrows {
+            if model.settings.draw_arrows {
                 draw.arrow()
                     .start(c)
-                    .end(c + r.with_magnitude(40.0))
-                    .stroke_weight(model.ray_width)
-                    .color(model.palette.get_first(model.scheme_id, model.color_off));
+                    .end(c + r.normalize() * 40.0)
+                    .stroke_weight(model.settings.ray_width)
+                    .color(
+                        model
+                            .palette
+                            .get_first(model.settings.scheme_id, model.settings.color_off),
+                    );
             }
         }
-        if model.draw_tex_overlay {
+        if model.settings.draw_tex_overlay {
             draw.texture(&model.texture).w_h(800.0, 800.0);
         }
     }
@@ -656,7 +431,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
     draw.to_frame(app, &frame).unwrap();
 
     if model.draw_gui {
-        model.ui.draw_to_frame(app, &frame).unwrap();
+        model.egui.draw_to_frame(&frame).unwrap();
     }
 }
 
@@ -676,7 +451,6 @@ fn make_walls(
     let margin: i32 = 100;
     let step = (win.w() as f32) as u32 / tile_count_w;
 
-
     let mut squares: Vec<Square> = Vec::new();
     squares.push(Square {
         x: win.left() + (margin as f32 / 2.0),
@@ -693,7 +467,6 @@ fn make_walls(
     }
 }
 
-
 fn create_curvedwalls_from_square(
     square: &Square,
     walls: &mut Vec<Curve>,
@@ -702,9 +475,7 @@ fn create_curvedwalls_from_square(
     hole: f32,
     hole_n: usize,
 ) {
-
     create_curve_from_square(square, mode, padding, hole, hole_n, walls);
-
 }
 
 fn create_curve_from_square(
@@ -741,7 +512,6 @@ fn create_curve_from_square(
         }
 
         if i == end_to {
-
             points.push(center + vec2(x, y));
             walls.push(Curve {
                 points: points.clone(),
@@ -753,7 +523,6 @@ fn create_curve_from_square(
     }
 }
 
-
 fn make_rays(
     rays: &mut Vec<BouncingRay2D>,
     win: &geom::Rect,
@@ -767,22 +536,21 @@ fn make_rays(
     for _y in 0..n_caster {
         let mut r = BouncingRay2D::new();
         let x;
-        //r.primary_ray.dir = Vector2::from_angle(random_range(-PI, PI));
+        //r.primary_ray.dir = Vec2::from_angle(random_range(-PI, PI));
         if _y % 2 == 0 {
-            r.primary_ray.dir = Vector2::from_angle(0.0);
+            r.primary_ray.dir = vec2(0.0.cos(), 0.0.sin());
             x = win.left();
-        }else{
-            r.primary_ray.dir = Vector2::from_angle(-PI);
+        } else {
+            r.primary_ray.dir = vec2((-PI).cos(), (-PI).sin());
             x = win.right();
         }
         r.ray.dir = r.primary_ray.dir;
-        
-        let o = vec2(x, (_y * padding ) as f32 - win.h()/2 as f32);
+
+        let o = vec2(x, (_y * padding) as f32 - win.h() / 2 as f32);
         r.primary_ray.orig = o;
         r.ray.orig = o;
         rays.push(r);
     }
-
 }
 
 fn key_pressed(app: &App, model: &mut Model, key: Key) {
@@ -796,6 +564,7 @@ fn key_pressed(app: &App, model: &mut Model, key: Key) {
     }
 }
 
-fn raw_window_event(app: &App, model: &mut Model, event: &ui::RawWindowEvent) {
-    model.ui.handle_raw_event(app, event);
+fn raw_window_event(_app: &App, model: &mut Model, event: &nannou::winit::event::WindowEvent) {
+    // Let egui handle things like keyboard and mouse input.
+    model.egui.handle_raw_event(event);
 }
@@ -7,17 +7,17 @@ pub struct BouncingRay2D {
     pub ray: Ray2D,
     pub bounces: usize,
     pub max_bounces: usize,
-    pub collisions: Vec<Vector2>,
-    pub reflections: Vec<Vector2>,
-    pub refractions: Vec<Vector2>,
+    pub collisions: Vec<Vec2>,
+    pub reflections: Vec<Vec2>,
+    pub refractions: Vec<Vec2>,
     pub refl_intensity: Vec<f32>,
 }
 
 impl BouncingRay2D {
     pub fn new() -> Self {
-        let collisions: Vec<Vector2> = Vec::new();
-        let reflections: Vec<Vector2> = Vec::new();
-        let refractions: Vec<Vector2> = Vec::new();
+        let collisions: Vec<Vec2> = Vec::new();
+        let reflections: Vec<Vec2> = Vec::new();
+        let refractions: Vec<Vec2> = Vec::new();
         let refl_intensity: Vec<f32> = Vec::new();
         BouncingRay2D {
             primary_ray: Ray2D::new(),
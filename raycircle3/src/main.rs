@@ -1,6 +1,7 @@
 use edapx_colors::Palette;
+use edapx_settings::{settings, Settings as _};
 use nannou::prelude::*;
-use nannou::ui::prelude::*;
+use nannou_egui::{self, egui, Egui};
 
 use ray2d::Ray2D;
 
@@ -8,15 +9,45 @@ fn main() {
     nannou::app(model).update(update).run();
 }
 
+settings! {
+    struct Settings {
+        "Balls" {
+            wall_width: f32 = 2.0, 1.0..=15.0;
+            show_balls: bool = true;
+        }
+        "Rays" {
+            collision_radius: f32 = 3.0, 3.0..=85.0;
+            ray_width: f32 = 3.0, 1.0..=10.0;
+            max_bounces: usize = 5, 1..=50;
+            rotation: f32 = 0.0, -PI..=PI;
+        }
+        "Colors" {
+            scheme_id: usize = 5, 0..=5;
+            blend_id: usize = 0, 0..=3;
+            color_off: usize = 4, 0..=4;
+        }
+        "Style" {
+            draw_refl: bool = true;
+            polygon_contour_weight: f32 = 5.0, 1.0..=30.0;
+            draw_polygon: bool = false;
+            draw_tex_overlay: bool = false;
+        }
+        "Animation" {
+            animation: bool = false;
+            animation_speed: f32 = 1.0, 0.1..=2.0;
+        }
+    }
+}
+
 struct Circle {
-    pos: Vector2,
+    pos: Vec2,
     radius: f32,
 }
 
 struct RayBall {
-    pos: Vector2,
+    pos: Vec2,
     rays: Vec<Ray2D>,
-    collisions: Vec<Vector2>,
+    collisions: Vec<Vec2>,
 }
 
 struct Model {
@@ -24,54 +55,20 @@ struct Model {
     balls: Vec<Circle>,
     rays_balls: Vec<RayBall>,
     draw_gui: bool,
-    ui: Ui,
-    ids: Ids,
-    ray_width: f32,
-    wall_width: f32,
-    collision_radius: f32,
-    rotation: f32,
-    scheme_id: usize,
-    max_bounces: usize,
-    blend_id: usize,
-    color_off: usize,
+    egui: Egui,
+    settings: Settings,
     palette: Palette,
-    show_balls: bool,
-    draw_tex_overlay: bool,
-    animation: bool,
-    animation_speed: f32,
-    draw_refl: bool,
-    draw_polygon: bool,
-    polygon_contour_weight: f32,
     padding: f32,
 }
 
-widget_ids! {
-    struct Ids {
-        wall_width,
-        ray_width,
-        max_bounces,
-        collision_radius,
-        rotation,
-        scheme_id,
-        blend_id,
-        color_off,
-        draw_refl,
-        draw_polygon,
-        polygon_contour_weight,
-        animation,
-        padding,
-        animation_speed,
-        show_balls,
-        draw_tex_overlay
-    }
-}
-
 fn model(app: &App) -> Model {
     let tile_count_w = 2;
-    app.new_window()
+    let win_id = app
+        .new_window()
         .size(800, 800)
         .view(view)
         .key_pressed(key_pressed)
+        .raw_event(raw_window_event)
         .build()
         .unwrap();
 
@@ -82,20 +79,10 @@ fn model(app: &App) -> Model {
     let draw_gui = true;
 
     // Create the UI.
-    let mut ui = app.new_ui().build().unwrap();
-
-    // Generate some ids for our widgets.
-    let ids = Ids::new(ui.widget_id_generator());
+    let egui = Egui::from_window(&app.window(win_id).unwrap());
 
-    let ray_width = 3.0;
-    let wall_width = 2.0;
-    let max_bounces = 5;
-    let rotation = 0.0;
-    let collision_radius = 3.0;
-
-    let scheme_id = 5;
-    let blend_id = 0;
-    let color_off = 4;
+    // the default values can be changed from the command line, run with --help to see them
+    let settings = Settings::from_env();
     let palette = Palette::new();
     let padding = 0.44;
     let rad = (win.w() / tile_count_w as f32) * 0.3;
@@ -108,178 +95,30 @@ fn model(app: &App) -> Model {
         rad,
         11,
     );
-    let show_balls = true;
-    let animation = false;
-    let animation_speed = 1.0;
-    let draw_refl = true;
-    let draw_polygon = false;
-    let polygon_contour_weight = 5.0;
-    let draw_tex_overlay = false;
-
 
     Model {
         tile_count_w,
         balls,
         rays_balls,
-        max_bounces,
         draw_gui,
-        ui,
-        ids,
-        wall_width,
-        collision_radius,
-        ray_width,
-        rotation,
-        scheme_id,
-        blend_id,
-        color_off,
+        egui,
+        settings,
         palette,
-        show_balls,
-        animation,
-        animation_speed,
-        draw_refl,
-        draw_polygon,
         padding,
-        polygon_contour_weight,
-        draw_tex_overlay,
     }
 }
 
-fn update(_app: &App, model: &mut Model, _update: Update) {
-    // Calling `set_widgets` allows us to instantiate some widgets.
-    let ui = &mut model.ui.set_widgets();
-    {
-        fn slider(val: f32, min: f32, max: f32) -> widget::Slider<'static, f32> {
-            widget::Slider::new(val, min, max)
-                .w_h(200.0, 30.0)
-                .label_font_size(15)
-                .rgb(0.3, 0.3, 0.3)
-                .label_rgb(1.0, 1.0, 1.0)
-                .border(0.0)
-        }
-
-        fn toggle(val: bool) -> widget::Toggle<'static> {
-            widget::Toggle::new(val)
-                .w_h(200.0, 30.0)
-                .label_font_size(15)
-                .rgb(0.3, 0.3, 0.3)
-                .label_rgb(1.0, 1.0, 1.0)
-                .border(0.0)
-        }
-
-        for value in slider(model.wall_width as f32, 1.0, 15.0)
-            .top_left_with_margin(20.0)
-            .label("wall width")
-            .set(model.ids.wall_width, ui)
-        {
-            model.wall_width = value;
-        }
-
-        for value in slider(model.collision_radius as f32, 3.0, 85.0)
-            .down(10.0)
-            .label("collision radius")
-            .set(model.ids.collision_radius, ui)
-        {
-            model.collision_radius = value;
-        }
-
-        for value in slider(model.ray_width, 1.0, 10.0)
-            .down(10.0)
-            .label("ray width")
-            .set(model.ids.ray_width, ui)
-        {
-            model.ray_width = value;
-        }
-        for value in slider(model.max_bounces as f32, 1.0, 50.0)
-            .down(10.0)
-            .label("max_bounces")
-            .set(model.ids.max_bounces, ui)
-        {
-            model.max_bounces = value as usize;
-        }
-
-        for val in slider(model.rotation, -PI, PI)
-            .down(10.0)
-            .label("Rotation")
-            .set(model.ids.rotation, ui)
-        {
-            model.rotation = val;
-        }
-
-        for value in slider(model.scheme_id as f32, 0.0, 5.0)
-            .down(10.0)
-            .label("scheme_id")
-            .set(model.ids.scheme_id, ui)
-        {
-            model.scheme_id = value as usize;
-        }
-
-        for value in slider(model.blend_id as f32, 0.0, 3.0)
-            .down(10.0)
-            .label("blend_id")
-            .set(model.ids.blend_id, ui)
-        {
-            model.blend_id = value as usize;
-        }
-
-        for value in slider(model.color_off as f32, 0.0, 4.0)
-            .down(10.0)
-            .label("color_off")
-            .set(model.ids.color_off, ui)
-        {
-            model.color_off = value as usize;
-        }
-
-        for v in toggle(model.draw_refl as bool)
-            .label("Draw reflection")
-            .set(model.ids.draw_refl, ui)
-        {
-            model.draw_refl = v;
-        }
-
-        for value in slider(model.polygon_contour_weight, 1.0, 30.0)
-            .down(10.0)
-            .label("polygon cont weight")
-            .set(model.ids.polygon_contour_weight, ui)
-        {
-            model.polygon_contour_weight = value;
-        }
-
-        for v in toggle(model.draw_polygon as bool)
-            .label("Draw poly")
-            .set(model.ids.draw_polygon, ui)
-        {
-            model.draw_polygon = v;
-        }
-
-        for v in toggle(model.draw_tex_overlay as bool)
-            .label("Draw Overlay")
-            .set(model.ids.draw_tex_overlay, ui)
-        {
-            model.draw_tex_overlay = v;
-        }
-
-        for v in toggle(model.animation as bool)
-            .label("Animation")
-            .set(model.ids.animation, ui)
-        {
-            model.animation = v;
-        }
-
-        for value in slider(model.animation_speed as f32, 2.0, 0.1)
-            .down(10.0)
-            .label("animation speed")
-            .set(model.ids.animation_speed, ui)
-        {
-            model.animation_speed = value;
-        }
-
-        for v in toggle(model.show_balls as bool)
-            .label("Show wall")
-            .set(model.ids.show_balls, ui)
-        {
-            model.show_balls = v;
-        }
-    }
+fn update(_app: &App, model: &mut Model, update: Update) {
+    let egui = &mut model.egui;
+    let settings = &mut model.settings;
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+    egui::Window::new("Settings").show(&ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            settings.show(ui);
+        });
+    });
+    drop(ctx);
 
     for rbal in model.rays_balls.iter_mut() {
         // find the closest intersection
@@ -290,13 +129,13 @@ fn update(_app: &App, model: &mut Model, _update: Update) {
             let mut coll = vec2(0.0, 0.0);
             for b in model.balls.iter_mut() {
                 //if b.pos.distance(rbal.pos) {
-                if let Some(collision_distance) = r.intersect_circle(b.pos, b.radius) {
+                if let Some((collision_distance, _)) = r.intersect_circle(&b.pos, &b.radius) {
                     //println!("coll");
                     //println!("{:?}", distance);
 
                     if collision_distance < distance {
                         distance = collision_distance;
-                        coll = r.orig + r.dir.with_magnitude(collision_distance);
+                        coll = r.orig + r.dir.normalize() * collision_distance;
                     }
                 }
                 //}
@@ -305,16 +144,17 @@ fn update(_app: &App, model: &mut Model, _update: Update) {
                 // there was a close collision, draw it
                 *c = coll;
             } else {
-                let cc = r.orig + r.dir.with_magnitude(sensibility);
+                let cc = r.orig + r.dir.normalize() * sensibility;
                 *c = cc;
             }
         }
 
-        if model.animation {
+        if model.settings.animation {
             let win = _app.window_rect();
             let side = win.w() as f32 / model.tile_count_w as f32;
             let padding = side * model.padding;
-            rbal.pos.y = rbal.pos.y + (_app.time * model.animation_speed).cos() * (padding / 6.0);
+            rbal.pos.y =
+                rbal.pos.y + (_app.time * model.settings.animation_speed).cos() * (padding / 6.0);
         }
     }
 }
@@ -322,20 +162,25 @@ fn update(_app: &App, model: &mut Model, _update: Update) {
 fn view(app: &App, model: &Model, frame: Frame) {
     let win = app.window_rect();
     let blends = [BLEND_NORMAL, BLEND_ADD, BLEND_SUBTRACT, BLEND_LIGHTEST];
-    let draw = app.draw().color_blend(blends[model.blend_id].clone());
-    frame.clear(model.palette.get_scheme(model.scheme_id)[4]);
+    let draw = app
+        .draw()
+        .color_blend(blends[model.settings.blend_id].clone());
+    frame.clear(model.palette.get_scheme(model.settings.scheme_id)[4]);
     //let draw = app.draw();
-    draw.background()
-        .color(model.palette.get_fifth(model.scheme_id, model.color_off));
+    draw.background().color(
+        model
+            .palette
+            .get_fifth(model.settings.scheme_id, model.settings.color_off),
+    );
 
     // // draw the balls
-    // if model.show_balls {
+    // if model.settings.show_balls {
     //     for c in &model.balls {
     //         draw_circle_polygon(&draw, &model, &c.pos, &c.radius, &vec2(1.0, 1.0), &10.0);
     //         // draw.ellipse()
     //         //     .x_y(c.pos.x, c.pos.y)
     //         //     .w_h(c.radius * 2.0, c.radius * 2.0)
-    //         //     .color(model.palette.get_second(model.scheme_id, model.color_off));
+    //         //     .color(model.palette.get_second(model.settings.scheme_id, model.settings.color_off));
     //     }
     // }
 
@@ -377,7 +222,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
     draw.to_frame(app, &frame).unwrap();
 
     if model.draw_gui {
-        model.ui.draw_to_frame(app, &frame).unwrap();
+        model.egui.draw_to_frame(&frame).unwrap();
     }
 }
 
@@ -403,13 +248,13 @@ fn make_balls(
             let mut r_y = c_y - radius;
             let r_padding = radius * 2.0 / (resolution as f32 + 1.0);
             let mut rays: Vec<Ray2D> = Vec::new();
-            let mut collisions: Vec<Vector2> = Vec::new();
+            let mut collisions: Vec<Vec2> = Vec::new();
             for i in (0..=360).step_by(resolution as usize) {
                 let rad = deg_to_rad(i as f32);
                 let mut r = Ray2D::new();
                 r.orig = vec2(c_x + padding, r_y);
-                r.dir = Vector2::from_angle(rad);
-                let coll = r.orig + r.dir.with_magnitude(200.0);
+                r.dir = vec2(rad.cos(), rad.sin());
+                let coll = r.orig + r.dir.normalize() * 200.0;
                 collisions.push(coll);
                 rays.push(r);
             }
@@ -444,20 +289,22 @@ fn draw_ray_ball(draw: &Draw, model: &Model, ray_ball: &RayBall) {
 fn draw_circle_polygon(
     draw: &Draw,
     model: &Model,
-    center: &Vector2,
+    center: &Vec2,
     radius: &f32,
-    lightDir: &Vector2,
+    lightDir: &Vec2,
     offset: &f32,
 ) {
     let points = (0..=360).step_by(2).map(|i| {
         let rad = deg_to_rad(i as f32);
         (
             *center + vec2(rad.sin() * *radius, rad.cos() * *radius),
-            model.palette.get_second(model.scheme_id, model.color_off),
+            model
+                .palette
+                .get_second(model.settings.scheme_id, model.settings.color_off),
         )
     });
     draw.polygon()
-        .stroke_weight(model.polygon_contour_weight)
+        .stroke_weight(model.settings.polygon_contour_weight)
         //.stroke()
         .points_colored(points);
 }
@@ -491,6 +338,7 @@ fn lcm(a: usize, b: usize) -> usize {
     a * b / gcd(a, b)
 }
 
-fn raw_window_event(app: &App, model: &mut Model, event: &ui::RawWindowEvent) {
-    model.ui.handle_raw_event(app, event);
-}
\ No newline at end of file
+fn raw_window_event(_app: &App, model: &mut Model, event: &nannou::winit::event::WindowEvent) {
+    // Let egui handle things like keyboard and mouse input.
+    model.egui.handle_raw_event(event);
+}
@@ -2,36 +2,21 @@ pub const WIN_W: u32 = 970;
 pub const WIN_H: u32 = 350;
 pub const ENABLE_4K_CAPTURE: bool = false;
 
-use edapx_colors::Palette;
+use edapx_colors::{show_report, Palette, PaletteEditor};
+use edapx_postprocessing::{Effect, EffectChain, Output};
+use edapx_settings::{settings, Settings as _};
 use nannou::prelude::*;
 use nannou_egui::{self, egui, Egui};
 use rayon::prelude::*;
-use scene::Material;
-use wall_helper::change_surface_walls;
 use std::env;
 use std::path::PathBuf;
 use std::path::Path;
 
-//mod gui;
-mod ray_light;
-mod scene;
-pub mod wraycaster;
-use crate::scene::Element;
-use crate::scene::SurfaceType;
-mod mondrian;
-pub use crate::mondrian::Square;
-mod bouncing;
-pub use crate::bouncing::BouncingRay2D;
-mod ray_helper;
-use crate::ray_helper::make_raycasters;
-mod wall_helper;
-use crate::wall_helper::change_color_walls;
-use crate::wall_helper::make_walls;
-mod raycaster;
-pub use crate::wraycaster::Wraycaster;
-pub mod capturer;
-pub use crate::capturer::Capturer;
-use nannou_ray2d::BoundingVolume;
+use edapx_raycast::Capturer;
+use edapx_raycast::ray_helper::make_raycasters;
+use edapx_raycast::scene::{Element, Material, SurfaceType};
+use edapx_raycast::wall_helper::{change_color_walls, change_surface_walls, make_walls};
+use edapx_raycast::Wraycaster;
 
 const EPSILON: f32 = 0.05;
 
@@ -43,40 +28,50 @@ fn main() {
     nannou::app(model).update(update).exit(exit).run();
 }
 
-struct Settings {
-    tile_count_w: u32,
-    n_caster: u32,
-    raycaster_density: usize,
-    rays_position_mode: usize,
-    ray_width: f32,
-    rays_prob: f32,
-    wall_width: f32,
-    wall_split: f32,
-    hole_pct: f32,
-    hole_n: usize,
-    wall_padding: f32,
-    collision_radius: f32,
-    rotation: f32,
-    scheme_id: usize,
-    max_bounces: usize,
-    blend_id: usize,
-    color_off: usize,
-    palette_alpha: f32,
-    light_color_pct: f32,
-    show_walls: bool,
-    clean_bg: bool,
-    transparent_bg: bool,
-    draw_arrows: bool,
-    draw_rays: bool,
-    draw_not_colliding_rays: bool,
-    animation: bool,
-    animation_mode: usize,
-    animation_speed: f32,
-    animation_time: f32,
-    draw_polygon: bool,
-    draw_polygon_mode: usize,
-    polygon_contour_weight: f32,
-    clear_interval: usize,
+settings! {
+    struct Settings {
+        "Walls" {
+            wall_width: f32 = 2.0, 1.0..=15.0;
+            wall_split: f32 = 1.0, 0.0..=1.0;
+            wall_padding: f32 = 0.07, 0.02..=0.2;
+            hole_pct: f32 = 0.25, 0.0..=0.9;
+            hole_n: usize = 2, 1..=10;
+            wall_shape: usize = 0, 0..=3;
+            tile_count_w: u32 = 8, 1..=20;
+            show_walls: bool = true;
+        }
+        "Rays" {
+            n_caster: u32 = 2, 1..=6;
+            raycaster_density: usize = 6, 1..=36;
+            rays_position_mode: usize = 1, 0..=1;
+            collision_radius: f32 = 3.0, 0.0..=185.0;
+            ray_width: f32 = 3.0, 0.5..=10.0;
+            rays_prob: f32 = 0.8, 0.0..=1.0;
+            max_bounces: usize = 4, 1..=6;
+            rotation: f32 = 0.0, -PI..=PI;
+            draw_rays: bool = true;
+            draw_not_colliding_rays: bool = false;
+        }
+        "Colors" {
+            // no slider, the palette editor can add schemes
+            scheme_id: usize = 5;
+            blend_id: usize = 0, 0..=3;
+            color_off: usize = 4, 0..=4;
+            light_color_pct: f32 = 0.5, 0.0..=1.0;
+            palette_alpha: f32 = 0.9, 0.0..=1.0;
+        }
+        "Style" {
+            polygon_contour_weight: f32 = 1.0, 0.5..=30.0;
+            draw_polygon: bool = true;
+            draw_polygon_mode: usize = 1, 0..=2;
+            draw_arrows: bool = false;
+        }
+        "Animation" {
+            animation: bool = false;
+            animation_mode: usize = 0, 0..=1;
+            animation_speed: f32 = 0.5, 0.01..=80.0;
+        }
+    }
 }
 
 struct Model {
@@ -84,9 +79,13 @@ struct Model {
     scene: Vec<Element>,
     rays: Vec<Wraycaster>,
     palette: Palette,
+    palette_editor: PaletteEditor,
     settings: Settings,
+    preset_path: std::path::PathBuf,
     egui: Egui,
     capturer: Capturer,
+    // the feedback of the previous frames, it writes into the texture of the capturer
+    effect: EffectChain,
     material: Material,
 }
 
@@ -110,7 +109,6 @@ fn model(app: &App) -> Model {
     // we also draw on a 4k canvas
     let canvas_rect = geom::Rect::from_w_h(texture_size[0] as f32, texture_size[1] as f32);
 
-    let tile_count_w = 8;
     let main_window_id = app
         .new_window()
         .size(win_w, win_h)
@@ -134,6 +132,19 @@ fn model(app: &App) -> Model {
     );
     // end capturer
 
+    let mut effect = EffectChain::new(
+        texture_size,
+        sample_count,
+        app.window(main_window_id).unwrap().device(),
+        Output::texture(&capturer.texture),
+    );
+    effect.add_effect(
+        app.window(main_window_id).unwrap().device(),
+        Effect::Feedback,
+    );
+    // with a decay of 0.0 the background is cleared
+    effect.set("feedback", "decay", 0.0).unwrap();
+
     // Create the UI.
     let ui_window = app
         .new_window()
@@ -149,43 +160,26 @@ fn model(app: &App) -> Model {
     // initialize the fields of the model
     let mut scene: Vec<Element> = Vec::new();
     let mut rays: Vec<Wraycaster> = Vec::new();
-    let settings = Settings {
-        tile_count_w: 8,
-        n_caster: 2,
-        raycaster_density: 6,
-        rays_position_mode: 1,
-        ray_width: 3.0,
-        rays_prob: 0.8,
-        wall_width: 2.0,
-        wall_split: 1.0,
-        hole_pct: 0.25,
-        hole_n: 2,
-        wall_padding: 0.07,
-        collision_radius: 3.0,
-        rotation: 0.0,
-        scheme_id: 5,
-        max_bounces: 4,
-        blend_id: 0,
-        color_off: 4,
-        palette_alpha: 0.9,
-        light_color_pct: 0.5,
-        show_walls: true,
-        clean_bg: true,
-        transparent_bg: true,
-        draw_arrows: false,
-        draw_rays: true,
-        draw_not_colliding_rays: false,
-        animation: false,
-        animation_mode: 0,
-        animation_speed: 0.5,
-        animation_time: 0.8,
-        draw_polygon: true,
-        draw_polygon_mode: 1,
-        polygon_contour_weight: 1.0,
-        clear_interval: 14,
+    // the default values can be changed from the command line, run with --help to see them
+    let settings = Settings::from_env();
+    let preset_path = app
+        .assets_path()
+        .unwrap()
+        .join(app.exe_name().unwrap() + ".toml");
+
+    let palette_path = app.assets_path().unwrap().join("palette.toml");
+    // the palette saved from the editor, the built-in schemes until one is saved
+    let mut palette = if palette_path.exists() {
+        Palette::load(&palette_path).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            Palette::new()
+        })
+    } else {
+        Palette::new()
     };
-
-    let palette = Palette::new();
+    let palette_editor = PaletteEditor::new(palette_path.to_str().unwrap());
+    palette.opacity = settings.palette_alpha;
+    set_background(&mut effect, &palette, &settings);
 
     let max_depth = 4;
 
@@ -193,7 +187,7 @@ fn model(app: &App) -> Model {
     make_walls(
         &mut scene,
         &canvas_rect,
-        tile_count_w,
+        settings.tile_count_w,
         settings.wall_split,
         settings.wall_padding,
         settings.hole_pct,
@@ -201,11 +195,12 @@ fn model(app: &App) -> Model {
         palette.get_first(settings.scheme_id, settings.color_off),
         palette.get_second(settings.scheme_id, settings.color_off),
         &material,
+        settings.wall_shape,
     );
     make_raycasters(
         &mut rays,
         &canvas_rect,
-        tile_count_w,
+        settings.tile_count_w,
         settings.n_caster,
         max_depth,
         settings.raycaster_density,
@@ -217,16 +212,18 @@ fn model(app: &App) -> Model {
     // rays_position_mode: usize,
     // rays_probability: f32,
 
-    let window = app.window(main_window_id).unwrap();
-    let egui = Egui::from_window(&window);
+    let egui = Egui::from_window(&app.window(ui_window).unwrap());
     let the_model = Model {
         egui,
         canvas_rect,
         scene,
         settings,
+        preset_path,
         rays,
         palette,
+        palette_editor,
         capturer,
+        effect,
         material,
     };
     //ui_event(&app, &mut the_model, WindowEvent::Focused);
@@ -234,290 +231,16 @@ fn model(app: &App) -> Model {
 }
 
 fn update(app: &App, model: &mut Model, update: Update) {
-    // we call draw only if the redraw value is set to true.
-    // only in the gui it is possible to set it to true
-    let mut redraw = false;
-
-    // egui
-    let egui = &mut model.egui;
-    let settings = &mut model.settings;
-
-    let canvas_rect = model.canvas_rect;
-    let mut material = &mut model.material;
-    let palette = &model.palette;
-    let mut rays = &mut model.rays;
-    let mut scene = &mut model.scene;
-
-    egui.set_elapsed_time(update.since_start);
-    let ctx = egui.begin_frame();
-
-    egui::SidePanel::left("Scene").show(&ctx, |ui| {
-        ui.heading("Scene");
-        ui.horizontal(|ui| {
-            ui.label("n holes:");
-            ui.add(egui::Slider::new(&mut settings.hole_n, 1..=10));
-        });
-        ui.horizontal(|ui| {
-            ui.label("hole %:");
-            ui.add(egui::Slider::new(&mut settings.hole_pct, 0.0..=0.9));
-        });
-        ui.horizontal(|ui| {
-            ui.label("tiles count:");
-            ui.add(egui::Slider::new(&mut settings.tile_count_w, 1..=20));
-        });
-        ui.horizontal(|ui| {
-            ui.label("wall_split:");
-            ui.add(egui::Slider::new(&mut settings.wall_split, 0.0..=1.0));
-        });
-        ui.horizontal(|ui| {
-            ui.label("wall_padding:");
-            ui.add(egui::Slider::new(&mut settings.wall_padding, 0.02..=0.2));
-        });
-        ui.horizontal(|ui| {
-            ui.label("tiles count:");
-            ui.add(egui::Slider::new(&mut settings.tile_count_w, 1..=20));
-        });
-
-        ui.heading("Wall Style");
-        ui.horizontal(|ui| {
-            ui.label("show walls");
-            ui.checkbox(&mut settings.show_walls, "");
-        });
-        ui.horizontal(|ui| {
-            ui.label("walls width");
-            ui.add(egui::Slider::new(&mut settings.wall_width, 1.0..=15.0));
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("material");
-            if ui
-                .add(egui::RadioButton::new(
-                    material.surface == SurfaceType::Diffuse,
-                    "Diffuse",
-                ))
-                .clicked()
-            {
-                material.surface = SurfaceType::Diffuse;
-                change_surface_walls(&mut scene, &material.surface)
-            }
-            if ui
-                .add(egui::RadioButton::new(
-                    material.surface
-                        == SurfaceType::Reflective {
-                            reflectivity: (1.0),
-                        },
-                    "Reflective",
-                ))
-                .clicked()
-            {
-                material.surface = SurfaceType::Reflective {
-                    reflectivity: (1.0),
-                }
-            }
-            if ui
-                .add(egui::RadioButton::new(
-                    material.surface
-                        == SurfaceType::ReflectiveAndRefractive {
-                            reflectivity: (1.0),
-                            ior: (1.4),
-                        },
-                    "ReflectiveAndRefractive",
-                ))
-                .clicked()
-            {
-                material.surface = SurfaceType::ReflectiveAndRefractive {
-                    reflectivity: (1.0),
-                    ior: (1.4),
-                }
-            }
-        });
-
-        if ui.add(egui::Button::new("Regenerate Walls")).clicked() {
-            regenerate_scene_and_rays(
-                &mut rays,
-                &mut scene,
-                settings,
-                canvas_rect,
-                material,
-                palette,
-            );
-        };
-
-        // if ui.button("Click each year").clicked() {
-        //     self.age += 1;
-        // }
-        //ui.label(format!("Hello '{}', age {}", self.name, self.age));
-    });
-
-    egui::SidePanel::left("Raycaster").show(&ctx, |ui| {
-        ui.heading("Rays");
-        ui.horizontal(|ui| {
-            ui.label("N. raycasters:");
-            ui.add(egui::Slider::new(&mut settings.n_caster, 1..=6));
-        });
-        ui.horizontal(|ui| {
-            ui.label("raycaster density:");
-            ui.add(egui::Slider::new(&mut settings.raycaster_density, 1..=36));
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("rays position mode:");
-            ui.add(egui::Slider::new(&mut settings.rays_position_mode, 0..=1));
-        });
-        ui.horizontal(|ui| {
-            ui.label("collision_radius:");
-            ui.add(egui::Slider::new(
-                &mut settings.collision_radius,
-                0.0..=185.0,
-            ));
-        });
-        ui.horizontal(|ui| {
-            ui.label("rays position mode:");
-            ui.add(egui::Slider::new(&mut settings.rays_position_mode, 0..=1));
-        });
-        ui.horizontal(|ui| {
-            ui.label("ray width:");
-            ui.add(egui::Slider::new(&mut settings.ray_width, 0.5..=10.0));
-        });
-        ui.horizontal(|ui| {
-            ui.label("rays prob:");
-            ui.add(egui::Slider::new(&mut settings.rays_prob, 0.0..=1.0));
-        });
-        ui.horizontal(|ui| {
-            ui.label("max bounces:");
-            ui.add(egui::Slider::new(&mut settings.max_bounces, 1..=6));
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("clear interval:");
-            ui.add(egui::Slider::new(&mut settings.clear_interval, 5..=20));
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("rotation:");
-            ui.add(egui::Slider::new(&mut settings.rotation, -PI..=PI));
-        });
-
-        ui.heading("Ray Style");
-        ui.horizontal(|ui| {
-            ui.label("draw rays");
-            ui.checkbox(&mut settings.draw_rays, "");
-        });
-        ui.horizontal(|ui| {
-            ui.label("draw not collyding rays");
-            ui.checkbox(&mut settings.draw_not_colliding_rays, "");
-        });
-        ui.horizontal(|ui| {
-            ui.label("draw arrows");
-            ui.checkbox(&mut settings.draw_arrows, "");
-        });
-    });
-
-    egui::SidePanel::left("Style").show(&ctx, |ui| {
-        ui.heading("Animation");
-        ui.horizontal(|ui| {
-            ui.label("enable animation");
-            ui.checkbox(&mut settings.animation, "");
-        });
-        ui.horizontal(|ui| {
-            ui.label("animation mode");
-            ui.add(egui::Slider::new(&mut settings.animation_mode, 0..=1));
-        });
-
-
-        ui.heading("General Colors");
-        ui.horizontal(|ui| {
-            ui.label("scheme id");
-            ui.add(egui::Slider::new(&mut settings.scheme_id, 0..=5));
-            if ui.input().pointer.any_released() {
-                change_color_walls(
-                    scene,
-                    palette.get_first(settings.scheme_id, settings.color_off),
-                    palette.get_second(settings.scheme_id, settings.color_off),
-                );
-            }
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("blend mode");
-            ui.add(egui::Slider::new(&mut settings.blend_id, 0..=3));
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("color offset");
-            ui.add(egui::Slider::new(&mut settings.color_off, 0..=4));
-            if ui.input().pointer.any_released() {
-                change_color_walls(
-                    scene,
-                    palette.get_first(settings.scheme_id, settings.color_off),
-                    palette.get_second(settings.scheme_id, settings.color_off),
-                );
-            }
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("light color %");
-            ui.add(egui::Slider::new(&mut settings.light_color_pct, 0.0..=1.0));
-            if ui.input().pointer.any_released() {
-                change_color_walls(
-                    scene,
-                    palette.get_first(settings.scheme_id, settings.color_off),
-                    palette.get_second(settings.scheme_id, settings.color_off),
-                );
-            }
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("palette alpha %");
-            ui.add(egui::Slider::new(&mut settings.palette_alpha, 0.0..=1.0));
-            if ui.input().pointer.any_released() {
-                change_color_walls(
-                    scene,
-                    palette.get_first(settings.scheme_id, settings.color_off),
-                    palette.get_second(settings.scheme_id, settings.color_off),
-                );
-            }
-        });
-
-        ui.heading("Polygon Style");
-        ui.horizontal(|ui| {
-            ui.label("draw polygon");
-            ui.checkbox(&mut settings.draw_polygon, "");
-        });
-        ui.horizontal(|ui| {
-            ui.label("polygon contour weight");
-            ui.add(egui::Slider::new(
-                &mut settings.polygon_contour_weight,
-                0.5..=30.0,
-            ));
-        });
-        ui.horizontal(|ui| {
-            ui.label("draw polygon mode");
-            ui.add(egui::Slider::new(&mut settings.draw_polygon_mode, 0..=2));
-        });
-
-        ui.heading("Background Style");
-        ui.horizontal(|ui| {
-            ui.label("clean bg");
-            ui.checkbox(&mut settings.clean_bg, "");
-        });
-        ui.horizontal(|ui| {
-            ui.label("transparent bg");
-            ui.checkbox(&mut settings.transparent_bg, "");
-        });
+    // Use the frame number to animate, ensuring we get a constant update time.
+    let elapsed_frames = app.main_window().elapsed_frames();
 
-        //redraw if any of those was touched
-        if ui.input().pointer.any_released() {
-            redraw = true;
-        }
-    });
+    // the rays collide in the first frame, then only if something changed in the gui or the
+    // animation is on.
+    let redraw = update_ui(model, &update) || elapsed_frames == 0;
+    let time = elapsed_frames as f32 / 60.0;
+    // let time = app.time;
 
     if model.settings.animation | redraw {
-        // Use the frame number to animate, ensuring we get a constant update time.
-        let elapsed_frames = app.main_window().elapsed_frames();
-        let time = elapsed_frames as f32 / 60.0;
-        // let time = app.time;
-
         let rot = model.settings.rotation;
         let anim = model.settings.animation;
         let anim_speed = model.settings.animation_speed;
@@ -537,83 +260,204 @@ fn update(app: &App, model: &mut Model, update: Update) {
             .rays
             .par_iter_mut()
             .for_each(|ray| ray.collide(rot, anim, anim_speed, time, scene, canvas_rect));
+    }
 
-        // Because we draw in the texture, all the code that usually goes in the view method has to be moved into the update
-        // function.
-
-        // VIEW
-        // First, reset the `draw` state.
-        let d = &model.capturer.draw;
-        d.reset();
-        let blends = [BLEND_NORMAL, BLEND_ADD, BLEND_SUBTRACT, BLEND_LIGHTEST];
-        let draw = d.color_blend(blends[model.settings.blend_id].clone());
-
-        if model.settings.transparent_bg {
-            let mut color = model
-                .palette
-                .get_fifth(model.settings.scheme_id, model.settings.color_off);
-            color.alpha = 0.0;
-            draw.background().color(color);
+    // the feedback pass runs every frame, even when the rays are not moving.
+    // Because we draw in the texture, all the code that usually goes in the view method has to be moved into the update
+    // function.
+
+    // VIEW
+    // First, reset the `draw` state.
+    let d = &model.effect.draw;
+    d.reset();
+    let blends = [BLEND_NORMAL, BLEND_ADD, BLEND_SUBTRACT, BLEND_LIGHTEST];
+    let draw = d.color_blend(blends[model.settings.blend_id].clone());
+
+    // the drawing is transparent, the feedback pass puts it over the previous frames
+    draw.background().color(rgba(0.0, 0.0, 0.0, 0.0));
+
+    if model.settings.show_walls {
+        for element in model.scene.iter() {
+            element.draw(&draw, &model.settings.wall_width);
+            // Debug bounding volume
+            // if let Some(c) = element.bounding_volume() {
+            //     match c {
+            //         BoundingVolume::Circle { position, radius } => {
+            //             draw.ellipse()
+            //                 .no_fill()
+            //                 .x_y(position.x, position.y)
+            //                 .w_h(radius * 2.0, radius * 2.0)
+            //                 .color(element.material().coloration)
+            //                 .stroke_weight(model.wall_width);
+            //         }
+            //         _ => {}
+            //     }
+            // }
         }
+    }
 
-        if model.settings.clean_bg && !model.settings.transparent_bg {
-            let mut color = model
-                .palette
-                .get_fifth(model.settings.scheme_id, model.settings.color_off);
-            color.alpha = 1.0;
-            draw.background().color(color);
+    for r in &model.rays {
+        if model.settings.draw_polygon {
+            r.draw_polygon(
+                &draw,
+                model.settings.polygon_contour_weight,
+                model.settings.ray_width,
+                model.settings.draw_not_colliding_rays,
+                model.settings.draw_polygon_mode,
+            );
         }
 
-        if model.settings.show_walls {
-            for element in model.scene.iter() {
-                element.draw(&draw, &model.settings.wall_width);
-                // Debug bounding volume
-                // if let Some(c) = element.bounding_volume() {
-                //     match c {
-                //         BoundingVolume::Circle { position, radius } => {
-                //             draw.ellipse()
-                //                 .no_fill()
-                //                 .x_y(position.x, position.y)
-                //                 .w_h(radius * 2.0, radius * 2.0)
-                //                 .color(element.material().coloration)
-                //                 .stroke_weight(model.wall_width);
-                //         }
-                //         _ => {}
-                //     }
-                // }
-            }
+        if model.settings.draw_arrows {
+            r.draw_arrows(&draw, model.settings.ray_width);
         }
 
-        for r in &model.rays {
-            if model.settings.draw_polygon {
-                r.draw_polygon(
-                    &draw,
-                    model.settings.polygon_contour_weight,
-                    model.settings.ray_width,
-                    model.settings.draw_not_colliding_rays,
-                    model.settings.draw_polygon_mode,
-                );
-            }
-
-            if model.settings.draw_arrows {
-                r.draw_arrows(&draw, model.settings.ray_width);
-            }
-
-            if model.settings.draw_rays {
-                r.draw_rays(
-                    &draw,
-                    model.settings.ray_width,
-                    model.settings.draw_not_colliding_rays,
-                );
-            }
+        if model.settings.draw_rays {
+            r.draw_rays(
+                &draw,
+                model.settings.ray_width,
+                model.settings.draw_not_colliding_rays,
+            );
         }
+    }
+
+    // Render our drawing to the texture.
+    let window = app.main_window();
+    let device = window.device();
+    model.effect.update_buffer(&window, time);
+    model.effect.update(&window, &device);
+    model
+        .effect
+        .render_to(&window, &device, &model.capturer.texture);
+    model.capturer.capture(&window, &device, elapsed_frames);
+}
 
-        // Render our drawing to the texture.
-        let window = app.main_window();
-        let device = window.device();
-        model.capturer.update(&window, &device, elapsed_frames);
-        redraw = false;
+// Returns true if the scene has to be drawn again.
+fn update_ui(model: &mut Model, update: &Update) -> bool {
+    let egui = &mut model.egui;
+    let settings = &mut model.settings;
+    let effect = &mut model.effect;
+    let palette = &mut model.palette;
+    let palette_editor = &mut model.palette_editor;
+    let material = &model.material;
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+
+    let mut changed = Vec::new();
+    let mut scheme_edited = false;
+    let mut surface = None;
+    let mut regenerate = false;
+    let mut save_preset = false;
+    let mut load_preset = false;
+    egui::SidePanel::left("Settings").show(&ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            changed = settings.show(ui);
+        });
+    });
+    egui::CentralPanel::default().show(&ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading("General Colors");
+            palette_editor.color_offset = settings.color_off;
+            scheme_edited = palette_editor.show(ui, palette, &mut settings.scheme_id);
+            // contrast and color vision report of all the schemes
+            egui::CollapsingHeader::new("All the schemes").show(ui, |ui| {
+                let reports = palette.reports(settings.color_off);
+                for (scheme, report) in palette.schemes.iter().zip(reports.iter()) {
+                    ui.label(&scheme.name);
+                    show_report(ui, report);
+                }
+            });
+
+            ui.heading("Material");
+            ui.horizontal(|ui| {
+                let surfaces = [
+                    (SurfaceType::Diffuse, "Diffuse"),
+                    (SurfaceType::Reflective { reflectivity: 1.0 }, "Reflective"),
+                    (
+                        SurfaceType::ReflectiveAndRefractive {
+                            reflectivity: 1.0,
+                            ior: 1.4,
+                        },
+                        "ReflectiveAndRefractive",
+                    ),
+                ];
+                for (s, name) in surfaces.iter() {
+                    if ui.radio(material.surface == *s, *name).clicked() {
+                        surface = Some(*s);
+                    }
+                }
+            });
+            regenerate = ui.button("Regenerate Walls").clicked();
+
+            // decay 0.0 clears the background, 1.0 keeps every frame
+            ui.heading("Background Feedback");
+            effect.show(ui);
+
+            ui.heading("Preset");
+            ui.horizontal(|ui| {
+                save_preset = ui.button("save").clicked();
+                load_preset = ui.button("load").clicked();
+            });
+        });
+    });
+    // the egui frame ends here, `ctx` borrows the model
+    drop(ctx);
+
+    if let Some(surface) = surface {
+        model.material.surface = surface;
+        change_surface_walls(&mut model.scene, &surface)
     }
+
+    if save_preset {
+        if let Err(err) = model.settings.save_preset(&model.preset_path) {
+            eprintln!("{}", err);
+        }
+    }
+    let mut loaded = false;
+    if load_preset {
+        match model.settings.load_preset(&model.preset_path) {
+            Ok(()) => loaded = true,
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+
+    let colors = ["scheme_id", "color_off", "palette_alpha"];
+    if scheme_edited || loaded || changed.iter().any(|name| colors.contains(name)) {
+        update_colors(model);
+    }
+
+    if regenerate || loaded {
+        regenerate_scene_and_rays(
+            &mut model.rays,
+            &mut model.scene,
+            &model.settings,
+            model.canvas_rect,
+            &model.material,
+            &model.palette,
+        );
+    }
+
+    !changed.is_empty() || scheme_edited || surface.is_some() || regenerate || loaded
+}
+
+// The palette takes the opacity from the settings, the walls the colors of the scheme.
+fn update_colors(model: &mut Model) {
+    let s = &model.settings;
+    model.palette.opacity = s.palette_alpha;
+    change_color_walls(
+        &mut model.scene,
+        model.palette.get_first(s.scheme_id, s.color_off),
+        model.palette.get_second(s.scheme_id, s.color_off),
+    );
+    set_background(&mut model.effect, &model.palette, s);
+}
+
+// The background of the feedback pass is the fifth color. It is set when the colors change, so
+// that it can still be picked in the effect panel.
+fn set_background(effect: &mut EffectChain, palette: &Palette, settings: &Settings) {
+    let bg = palette.get_fifth(settings.scheme_id, settings.color_off);
+    effect
+        .set("feedback", "background", [bg.red, bg.green, bg.blue, 1.0])
+        .unwrap();
 }
 
 fn view(_app: &App, model: &Model, frame: Frame) {
@@ -667,7 +511,7 @@ fn exit(app: &App, model: Model) {
 fn regenerate_scene_and_rays(
     rays: &mut Vec<Wraycaster>,
     scene: &mut Vec<Element>,
-    settings: &mut Settings,
+    settings: &Settings,
     canvas_rect: geom::Rect,
     material: &Material,
     palette: &Palette,
@@ -683,6 +527,7 @@ fn regenerate_scene_and_rays(
         palette.get_first(settings.scheme_id, settings.color_off),
         palette.get_second(settings.scheme_id, settings.color_off),
         &material,
+        settings.wall_shape,
     );
 
     make_raycasters(
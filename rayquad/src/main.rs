@@ -1,5 +1,6 @@
+use edapx_settings::{settings, Settings as _};
 use nannou::prelude::*;
-use nannou::ui::prelude::*;
+use nannou_egui::{self, egui, Egui};
 use ray2d::Ray2D;
 
 const N_WALL: usize = 10;
@@ -8,95 +9,59 @@ fn main() {
     nannou::app(model).update(update).run();
 }
 
-struct Model {
-    walls: Vec<Vector2>,
-    draw_gui: bool,
-    ui: Ui,
-    ids: Ids,
-    ray_width: f32,
-    wall_width: f32,
-    rotation: f32,
+settings! {
+    struct Settings {
+        "Rays" {
+            wall_width: f32 = 2.0, 1.0..=5.0;
+            ray_width: f32 = 6.0, 1.0..=10.0;
+            rotation: f32 = 0.0, -PI..=PI;
+        }
+    }
 }
 
-widget_ids! {
-    struct Ids {
-        ray_width,
-        wall_width,
-        rotation,
-    }
+struct Model {
+    walls: Vec<Vec2>,
+    draw_gui: bool,
+    egui: Egui,
+    settings: Settings,
 }
 
 fn model(app: &App) -> Model {
-    app.new_window()
+    let window_id = app
+        .new_window()
         .size(800, 800)
         .view(view)
         .key_pressed(key_pressed)
+        .raw_event(raw_window_event)
         .build()
         .unwrap();
 
-    let mut walls: Vec<Vector2> = Vec::new();
+    let mut walls: Vec<Vec2> = Vec::new();
     let win = app.window_rect();
     make_walls(&mut walls, &win);
 
     let draw_gui = true;
 
     // Create the UI.
-    let mut ui = app.new_ui().build().unwrap();
-
-    // Generate some ids for our widgets.
-    let ids = Ids::new(ui.widget_id_generator());
-
-    let ray_width = 6.0;
-    let wall_width = 2.0;
-    let rotation = 0.0;
+    let egui = Egui::from_window(&app.window(window_id).unwrap());
 
     Model {
         walls,
         draw_gui,
-        ui,
-        ids,
-        ray_width,
-        wall_width,
-        rotation,
+        egui,
+        // the default values can be changed from the command line, run with --help to see them
+        settings: Settings::from_env(),
     }
 }
 
-fn update(_app: &App, model: &mut Model, _update: Update) {
-    // Calling `set_widgets` allows us to instantiate some widgets.
-    let ui = &mut model.ui.set_widgets();
-
-    fn slider(val: f32, min: f32, max: f32) -> widget::Slider<'static, f32> {
-        widget::Slider::new(val, min, max)
-            .w_h(200.0, 30.0)
-            .label_font_size(15)
-            .rgb(0.3, 0.3, 0.3)
-            .label_rgb(1.0, 1.0, 1.0)
-            .border(0.0)
-    }
-
-    for value in slider(model.wall_width as f32, 1.0, 5.0)
-        .top_left_with_margin(20.0)
-        .label("wall width")
-        .set(model.ids.wall_width, ui)
-    {
-        model.wall_width = value;
-    }
-
-    for value in slider(model.ray_width, 1.0, 10.0)
-        .down(10.0)
-        .label("ray width")
-        .set(model.ids.ray_width, ui)
-    {
-        model.ray_width = value;
-    }
-
-    for value in slider(model.rotation, -PI, PI)
-        .down(10.0)
-        .label("Rotation")
-        .set(model.ids.rotation, ui)
-    {
-        model.rotation = value;
-    }
+fn update(_app: &App, model: &mut Model, update: Update) {
+    let egui = &mut model.egui;
+    let settings = &mut model.settings;
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+    egui::Window::new("Settings").show(&ctx, |ui| {
+        settings.show(ui);
+    });
 }
 
 fn view(app: &App, model: &Model, frame: Frame) {
@@ -106,31 +71,31 @@ fn view(app: &App, model: &Model, frame: Frame) {
     let mut r = Ray2D::new();
     //r.orig = model.position;
     //r.look_at(app.mouse.x, app.mouse.y);
-    r.set_dir_from_angle(model.rotation);
-    r.draw(&draw, 60.0, model.ray_width, rgb(0.3, 0.3, 0.3));
+    r.set_dir_from_angle(model.settings.rotation);
+    r.draw(&draw, 60.0, model.settings.ray_width, rgb(0.3, 0.3, 0.3));
 
-    let mut collision: Vector2 = vec2(0.0, 0.0);
+    let mut collision: Vec2 = vec2(0.0, 0.0);
     let mut distance: f32 = Float::infinity();
-    let mut surface_normal: Vector2 = vec2(0.0, 0.0);
+    let mut surface_normal: Vec2 = vec2(0.0, 0.0);
 
     // find the closest intersection point between the ray and the walls
     for index in (0..N_WALL).step_by(2) {
         draw.line()
-            .weight(model.wall_width)
+            .weight(model.settings.wall_width)
             .color(STEELBLUE)
             .start(model.walls[index])
             .caps_round()
             .end(model.walls[index + 1]);
 
         if let Some(collision_distance) = r.intersect_segment(
-            model.walls[index].x,
-            model.walls[index].y,
-            model.walls[index + 1].x,
-            model.walls[index + 1].y,
+            &model.walls[index].x,
+            &model.walls[index].y,
+            &model.walls[index + 1].x,
+            &model.walls[index + 1].y,
         ) {
             if collision_distance < distance {
                 distance = collision_distance;
-                collision = r.orig + r.dir.with_magnitude(collision_distance);
+                collision = r.orig + r.dir.normalize() * collision_distance;
                 let segment_dir = (model.walls[index] - model.walls[index + 1]).normalize();
                 surface_normal = vec2(segment_dir.y, -segment_dir.x);
             }
@@ -150,7 +115,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
             .color(YELLOW)
             .start(collision)
             .caps_round()
-            .end(collision + refl.with_magnitude(100.0));
+            .end(collision + refl.normalize() * 100.0);
 
         // refraction
         let refr = r.refract(surface_normal, 1.2);
@@ -158,17 +123,17 @@ fn view(app: &App, model: &Model, frame: Frame) {
             .color(INDIGO)
             .start(collision)
             .caps_round()
-            .end(collision + refr.with_magnitude(100.0));
+            .end(collision + refr.normalize_or_zero() * 100.0);
     };
 
     draw.to_frame(app, &frame).unwrap();
 
     if model.draw_gui {
-        model.ui.draw_to_frame(app, &frame).unwrap();
+        model.egui.draw_to_frame(&frame).unwrap();
     }
 }
 
-fn make_walls(walls: &mut Vec<Vector2>, win: &geom::Rect) {
+fn make_walls(walls: &mut Vec<Vec2>, win: &geom::Rect) {
     while walls.len() < N_WALL * 2 {
         let start_p = vec2(
             random_range(-win.w() / 2.0, win.w() / 2.0),
@@ -194,6 +159,7 @@ fn key_pressed(app: &App, model: &mut Model, key: Key) {
         _other_key => {}
     }
 }
-fn raw_window_event(app: &App, model: &mut Model, event: &ui::RawWindowEvent) {
-    model.ui.handle_raw_event(app, event);
+fn raw_window_event(_app: &App, model: &mut Model, event: &nannou::winit::event::WindowEvent) {
+    // Let egui handle things like keyboard and mouse input.
+    model.egui.handle_raw_event(event);
 }
@@ -1,7 +1,7 @@
 use edapx_colors::Palette;
+use edapx_settings::{settings, Settings as _};
 use nannou::prelude::*;
-use nannou_conrod as ui;
-use nannou_conrod::prelude::*;
+use nannou_egui::{self, egui, Egui};
 
 mod bouncing;
 mod mondrian;
@@ -15,69 +15,56 @@ fn main() {
     nannou::app(model).update(update).run();
 }
 
+settings! {
+    struct Settings {
+        "Walls" {
+            wall_width: f32 = 2.0, 1.0..=15.0;
+            wall_split: f32 = 0.6, 0.0..=1.0;
+            wall_padding: f32 = 0.07, 0.02..=0.2;
+            hole_pct: f32 = 0.3, 0.0..=0.8;
+            wall_mode: u32 = 2, 1..=5;
+            tile_count_w: u32 = 8, 1..=20;
+            show_walls: bool = true;
+        }
+        "Rays" {
+            collision_radius: f32 = 0.3, 0.0..=185.0;
+            ray_width: f32 = 3.0, 1.0..=10.0;
+            rays_prob: f32 = 0.5, 0.0..=1.0;
+            max_bounces: usize = 40, 1..=600;
+            rotation: f32 = 0.0, -PI..=PI;
+        }
+        "Colors" {
+            scheme_id: usize = 5, 0..=5;
+            blend_id: usize = 0, 0..=3;
+            color_off: usize = 4, 0..=4;
+        }
+        "Style" {
+            polygon_contour_weight: f32 = 5.0, 1.0..=30.0;
+            draw_polygon: bool = true;
+            draw_arrows: bool = true;
+            draw_tex_overlay: bool = false;
+        }
+        "Animation" {
+            animation: bool = true;
+            animation_speed: f32 = 0.01, 0.0001..=0.1;
+        }
+    }
+}
+
 struct Model {
-    walls: Vec<Vector2>,
-    tile_count_w: u32,
-    wall_mode: u32,
+    walls: Vec<Vec2>,
     rays: Vec<BouncingRay2D>,
     draw_gui: bool,
-    ui: Ui,
-    ids: Ids,
-    ray_width: f32,
-    rays_prob: f32,
-    wall_width: f32,
-    wall_split: f32,
-    hole_pct: f32,
-    wall_padding: f32,
-    collision_radius: f32,
-    rotation: f32,
-    scheme_id: usize,
-    max_bounces: usize,
-    blend_id: usize,
-    color_off: usize,
+    egui: Egui,
+    settings: Settings,
     palette: Palette,
-    show_walls: bool,
-    draw_arrows: bool,
-    draw_tex_overlay: bool,
-    animation: bool,
-    animation_speed: f32,
     animation_time: f32,
-    draw_polygon: bool,
-    polygon_contour_weight: f32,
     texture: wgpu::Texture,
 }
 
-widget_ids! {
-    struct Ids {
-        wall_width,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        tile_count_w,
-        button,
-        wall_mode,
-        ray_width,
-        rays_prob,
-        max_bounces,
-        collision_radius,
-        rotation,
-        scheme_id,
-        blend_id,
-        color_off,
-        animation_time,
-        draw_polygon,
-        draw_arrows,
-        polygon_contour_weight,
-        animation,
-        animation_speed,
-        show_walls,
-        draw_tex_overlay
-    }
-}
-
 fn model(app: &App) -> Model {
-    let tile_count_w = 8;
-    let win_id = app.new_window()
+    let win_id = app
+        .new_window()
         //.size(1280, 720)
         //.size(1777, 1000)
         //.size(1920,1080)
@@ -92,52 +79,30 @@ fn model(app: &App) -> Model {
         .build()
         .unwrap();
 
-    let mut walls: Vec<Vector2> = Vec::new();
+    let mut walls: Vec<Vec2> = Vec::new();
     let mut rays: Vec<BouncingRay2D> = Vec::new();
     let win = app.window_rect();
 
     let draw_gui = true;
 
     // Create the UI.
-    let mut ui = ui::builder(app).window(win_id).build().unwrap();
-
-    // Generate some ids for our widgets.
-    let ids = Ids::new(ui.widget_id_generator());
-
-    let ray_width = 3.0;
-    let wall_width = 2.0;
-    let wall_split = 0.6;
-    let wall_padding = 0.07;
-    let hole_pct = 0.3;
-    let wall_mode = 2;
-    let max_bounces = 40;
-    let rotation = 0.0;
-    let collision_radius = 0.3;
-    let rays_prob = 0.5;
-
-    let scheme_id = 5;
-    let blend_id = 0;
-    let color_off = 4;
+    let egui = Egui::from_window(&app.window(win_id).unwrap());
+
+    // the default values can be changed from the command line, run with --help to see them
+    let settings = Settings::from_env();
     let palette = Palette::new();
     make_walls(
         &mut walls,
         &mut rays,
         &win,
-        tile_count_w,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        rays_prob,
-        wall_mode,
+        settings.tile_count_w,
+        settings.wall_split,
+        settings.wall_padding,
+        settings.hole_pct,
+        settings.rays_prob,
+        settings.wall_mode,
     );
-    let show_walls = true;
-    let animation = true;
-    let draw_arrows = true;
-    let animation_speed = 0.01;
     let animation_time = 0.0;
-    let draw_polygon = true;
-    let polygon_contour_weight = 5.0;
-    let draw_tex_overlay = false;
 
     // texture
     // Load the image from disk and upload it to a GPU texture.
@@ -149,252 +114,47 @@ fn model(app: &App) -> Model {
 
     Model {
         walls,
-        wall_mode,
-        tile_count_w,
         rays,
-        max_bounces,
         draw_gui,
-        ui,
-        ids,
-        wall_width,
-        wall_split,
-        wall_padding,
-        hole_pct,
-        collision_radius,
-        ray_width,
-        rays_prob,
-        rotation,
-        scheme_id,
-        blend_id,
-        color_off,
+        egui,
+        settings,
         palette,
-        show_walls,
-        animation,
-        animation_speed,
         animation_time,
-        draw_polygon,
-        draw_arrows,
-        polygon_contour_weight,
-        draw_tex_overlay,
         texture,
     }
 }
 
-fn update(_app: &App, model: &mut Model, _update: Update) {
-    // Calling `set_widgets` allows us to instantiate some widgets.
-    let ui = &mut model.ui.set_widgets();
-    {
-        fn slider(val: f32, min: f32, max: f32) -> widget::Slider<'static, f32> {
-            widget::Slider::new(val, min, max)
-                .w_h(200.0, 30.0)
-                .label_font_size(15)
-                .rgb(0.3, 0.3, 0.3)
-                .label_rgb(1.0, 1.0, 1.0)
-                .border(0.0)
-        }
-
-        fn toggle(val: bool) -> widget::Toggle<'static> {
-            widget::Toggle::new(val)
-                .w_h(200.0, 30.0)
-                .label_font_size(15)
-                .rgb(0.3, 0.3, 0.3)
-                .label_rgb(1.0, 1.0, 1.0)
-                .border(0.0)
-        }
-
-        for value in slider(model.wall_width as f32, 1.0, 15.0)
-            .top_left_with_margin(10.0)
-            .label("wall width")
-            .set(model.ids.wall_width, ui)
-        {
-            model.wall_width = value;
-        }
-
-        for value in slider(model.wall_split as f32, 0.0, 1.0)
-            .down(3.0)
-            .label("wall split")
-            .set(model.ids.wall_split, ui)
-        {
-            model.wall_split = value;
-        }
-
-        for value in slider(model.wall_padding as f32, 0.2, 0.02)
-            .down(3.0)
-            .label("wall padding")
-            .set(model.ids.wall_padding, ui)
-        {
-            model.wall_padding = value;
-        }
-
-        for value in slider(model.hole_pct as f32, 0.0, 0.8)
-            .down(3.0)
-            .label("hole")
-            .set(model.ids.hole_pct, ui)
-        {
-            model.hole_pct = value;
-        }
-
-        for value in slider(model.wall_mode as f32, 1.0, 5.0)
-            .down(3.0)
-            .label("wall_mode ")
-            .set(model.ids.wall_mode, ui)
-        {
-            model.wall_mode = value as u32;
-        }
-
-        for value in slider(model.tile_count_w as f32, 1.0, 20.0)
-            .down(3.0)
-            .label("tile_count_w")
-            .set(model.ids.tile_count_w, ui)
-        {
-            model.tile_count_w = value as u32;
-        }
-
-        for _click in widget::Button::new()
-            .down(3.0)
-            //.w_h(200.0, 60.0)
-            .label("Regenerate Walls")
-            .label_font_size(15)
-            .rgb(0.3, 0.3, 0.3)
-            .label_rgb(1.0, 1.0, 1.0)
-            .border(0.0)
-            .set(model.ids.button, ui)
-        {
-            let win = _app.window_rect();
-            make_walls(
-                &mut model.walls,
-                &mut model.rays,
-                &win,
-                model.tile_count_w,
-                model.wall_split,
-                model.wall_padding,
-                model.hole_pct,
-                model.rays_prob,
-                model.wall_mode,
-            );
-        }
-
-        for value in slider(model.collision_radius as f32, 0.0, 185.0)
-            .down(3.0)
-            .label("collision radius")
-            .set(model.ids.collision_radius, ui)
-        {
-            model.collision_radius = value;
-        }
-
-        for value in slider(model.ray_width, 1.0, 10.0)
-            .down(3.0)
-            .label("ray width")
-            .set(model.ids.ray_width, ui)
-        {
-            model.ray_width = value;
-        }
-        for value in slider(model.rays_prob as f32, 0.0, 1.0)
-            .down(3.0)
-            .label("rays prob.")
-            .set(model.ids.rays_prob, ui)
-        {
-            model.rays_prob = value;
-        }
-        for value in slider(model.max_bounces as f32, 1.0, 600.0)
-            .down(3.0)
-            .label("max_bounces")
-            .set(model.ids.max_bounces, ui)
-        {
-            model.max_bounces = value as usize;
-        }
-
-        for val in slider(model.rotation, -PI, PI)
-            .down(3.0)
-            .label("Rotation")
-            .set(model.ids.rotation, ui)
-        {
-            model.rotation = val;
-        }
-
-        for value in slider(model.scheme_id as f32, 0.0, 5.0)
-            .down(3.0)
-            .label("scheme_id")
-            .set(model.ids.scheme_id, ui)
-        {
-            model.scheme_id = value as usize;
-        }
-
-        for value in slider(model.blend_id as f32, 0.0, 3.0)
-            .down(3.0)
-            .label("blend_id")
-            .set(model.ids.blend_id, ui)
-        {
-            model.blend_id = value as usize;
-        }
-
-        for value in slider(model.color_off as f32, 0.0, 4.0)
-            .down(3.0)
-            .label("color_off")
-            .set(model.ids.color_off, ui)
-        {
-            model.color_off = value as usize;
-        }
-
-        for value in slider(model.polygon_contour_weight, 1.0, 30.0)
-            .down(3.0)
-            .label("polygon cont weight")
-            .set(model.ids.polygon_contour_weight, ui)
-        {
-            model.polygon_contour_weight = value;
-        }
-
-        for v in toggle(model.draw_polygon as bool)
-            .down(3.0)
-            .label("Draw poly")
-            .set(model.ids.draw_polygon, ui)
-        {
-            model.draw_polygon = v;
-        }
-
-        for v in toggle(model.draw_arrows as bool)
-            .down(3.0)
-            .label("Draw Arrows")
-            .set(model.ids.draw_arrows, ui)
-        {
-            model.draw_arrows = v;
-        }
-
-        for v in toggle(model.draw_tex_overlay as bool)
-            .down(3.0)
-            .label("Draw Overlay")
-            .set(model.ids.draw_tex_overlay, ui)
-        {
-            model.draw_tex_overlay = v;
-        }
-
-        for v in toggle(model.animation as bool)
-            .down(3.0)
-            .label("Animation")
-            .set(model.ids.animation, ui)
-        {
-            model.animation = v;
-        }
-
-        for value in slider(model.animation_speed as f32, 0.1, 0.0001)
-            .down(3.0)
-            .label("animation speed")
-            .set(model.ids.animation_speed, ui)
-        {
-            model.animation_speed = value;
-        }
-
-        for v in toggle(model.show_walls as bool)
-            .down(3.0)
-            .label("Show wall")
-            .set(model.ids.show_walls, ui)
-        {
-            model.show_walls = v;
-        }
+fn update(_app: &App, model: &mut Model, update: Update) {
+    let egui = &mut model.egui;
+    let settings = &mut model.settings;
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+    let mut regenerate = false;
+    egui::Window::new("Settings").show(&ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            settings.show(ui);
+            regenerate = ui.button("Regenerate Walls").clicked();
+        });
+    });
+    drop(ctx);
+
+    if regenerate {
+        let win = _app.window_rect();
+        make_walls(
+            &mut model.walls,
+            &mut model.rays,
+            &win,
+            model.settings.tile_count_w,
+            model.settings.wall_split,
+            model.settings.wall_padding,
+            model.settings.hole_pct,
+            model.settings.rays_prob,
+            model.settings.wall_mode,
+        );
     }
 
     for r in model.rays.iter_mut() {
-        r.max_bounces = model.max_bounces;
+        r.max_bounces = model.settings.max_bounces;
         r.collisions.clear();
         r.reflections.clear();
         r.refl_intensity.clear();
@@ -405,9 +165,9 @@ fn update(_app: &App, model: &mut Model, _update: Update) {
         // r.refl_intensity.push(0.0);
 
         while !r.max_bounces_reached() {
-            let mut collision: Vector2 = vec2(0.0, 0.0);
+            let mut collision: Vec2 = vec2(0.0, 0.0);
             let mut distance: f32 = Float::infinity();
-            let mut surface_normal: Vector2 = vec2(0.0, 0.0);
+            let mut surface_normal: Vec2 = vec2(0.0, 0.0);
             // find the closest intersection point between the ray and the walls
             for index in (0..model.walls.len()).step_by(2) {
                 if let Some(collision_distance) = r.ray.intersect_segment(
@@ -439,29 +199,42 @@ fn update(_app: &App, model: &mut Model, _update: Update) {
             };
         }
         r.reset();
-        if model.animation {
-            model.animation_time = _app.time * model.animation_speed;
+        if model.settings.animation {
+            model.animation_time = _app.time * model.settings.animation_speed;
         }
-        r.ray.dir = r.ray.dir.rotate(model.animation_time + model.rotation);
+        r.ray.dir = r
+            .ray
+            .dir
+            .rotate(model.animation_time + model.settings.rotation);
     }
 }
 
 fn view(app: &App, model: &Model, frame: Frame) {
     let blends = [BLEND_NORMAL, BLEND_ADD, BLEND_SUBTRACT, BLEND_LIGHTEST];
-    let draw = app.draw().color_blend(blends[model.blend_id].clone());
-    frame.clear(model.palette.get_fifth(model.scheme_id, model.color_off));
+    let draw = app
+        .draw()
+        .color_blend(blends[model.settings.blend_id].clone());
+    frame.clear(
+        model
+            .palette
+            .get_fifth(model.settings.scheme_id, model.settings.color_off),
+    );
     //frame.clear(BLACK);
     //let draw = app.draw();
     // draw.background()
-    //     .color(model.palette.get_fifth(model.scheme_id, model.color_off));
+    //     .color(model.palette.get_fifth(model.settings.scheme_id, model.settings.color_off));
 
     // draw the walls
-    if model.show_walls {
+    if model.settings.show_walls {
         let size = model.walls.len();
         for index in (0..size).step_by(2) {
             draw.line()
-                .weight(model.wall_width)
-                .color(model.palette.get_second(model.scheme_id, model.color_off))
+                .weight(model.settings.wall_width)
+                .color(
+                    model
+                        .palette
+                        .get_second(model.settings.scheme_id, model.settings.color_off),
+                )
                 .start(model.walls[index])
                 .caps_round()
                 .end(model.walls[index + 1]);
@@ -469,22 +242,33 @@ fn view(app: &App, model: &Model, frame: Frame) {
     }
 
     for r in &model.rays {
-        if model.draw_arrows {
+        if model.settings.draw_arrows {
             draw.arrow()
-                .color(model.palette.get_first(model.scheme_id, model.color_off))
+                .color(
+                    model
+                        .palette
+                        .get_first(model.settings.scheme_id, model.settings.color_off),
+                )
                 .start(r.ray.orig)
-                .stroke_weight(model.ray_width)
+                .stroke_weight(model.settings.ray_width)
                 .end(r.ray.orig + r.ray.dir.normalize() * 40.0);
         }
 
-        if r.collisions.len() > 3 && model.collision_radius > 0.0 {
+        if r.collisions.len() > 3 && model.settings.collision_radius > 0.0 {
             for (&c, &i) in r.collisions.iter().zip(r.refl_intensity.iter()) {
                 draw.ellipse()
                     .no_fill()
-                    .stroke(model.palette.get_third(model.scheme_id, model.color_off))
+                    .stroke(
+                        model
+                            .palette
+                            .get_third(model.settings.scheme_id, model.settings.color_off),
+                    )
                     .stroke_weight(3.0)
                     .x_y(c.x, c.y)
-                    .w_h(model.collision_radius * i, model.collision_radius * i);
+                    .w_h(
+                        model.settings.collision_radius * i,
+                        model.settings.collision_radius * i,
+                    );
             }
         }
 
@@ -496,9 +280,13 @@ fn view(app: &App, model: &Model, frame: Frame) {
             .zip(r.reflections.iter())
             .map(|(&co, &re)| {
                 if re.x > 0.0 {
-                    col = model.palette.get_third(model.scheme_id, model.color_off)
+                    col = model
+                        .palette
+                        .get_third(model.settings.scheme_id, model.settings.color_off)
                 } else {
-                    col = model.palette.get_fourth(model.scheme_id, model.color_off)
+                    col = model
+                        .palette
+                        .get_fourth(model.settings.scheme_id, model.settings.color_off)
                 }
                 // let xc = map_range(co.x, win.left(), win.right(), 0.0, 1.0);
                 // let xy = map_range(co.y, win.bottom(), win.top(), 0.0, 1.0);
@@ -507,18 +295,22 @@ fn view(app: &App, model: &Model, frame: Frame) {
                 (pt2(co.x, co.y), col)
             });
 
-        if model.draw_polygon {
+        if model.settings.draw_polygon {
             if ppp.len() > 3 {
                 draw.polygon()
-                    .stroke(model.palette.get_second(model.scheme_id, model.color_off))
-                    .stroke_weight(model.polygon_contour_weight)
+                    .stroke(
+                        model
+                            .palette
+                            .get_second(model.settings.scheme_id, model.settings.color_off),
+                    )
+                    .stroke_weight(model.settings.polygon_contour_weight)
                     .join_round()
                     .points_colored(ppp);
                 //draw.polygon().points_textured(&model.texture, ppp);
             }
         };
 
-        // if model.draw_tex_overlay {
+        // if model.settings.draw_tex_overlay {
         //     if ppp.len() > 3 {
         //         draw.polygon().points_textured(&model.texture, ppp);
         //     }
@@ -528,22 +320,30 @@ fn view(app: &App, model: &Model, frame: Frame) {
             draw.path()
                 .stroke()
                 .caps_round()
-                .stroke_weight(model.ray_width)
+                .stroke_weight(model.settings.ray_width)
                 .points(r.collisions.iter().cloned())
                 //.points_colored(ppp.cloned());
-                .color(model.palette.get_first(model.scheme_id, model.color_off));
+                .color(
+                    model
+                        .palette
+                        .get_first(model.settings.scheme_id, model.settings.color_off),
+                );
         }
 
         for (&c, &r) in r.collisions.iter().zip(r.reflections.iter()) {
-            if model.draw_arrows {
+            if model.settings.draw_arrows {
                 draw.arrow()
                     .start(c)
                     .end(c + r.normalize() * 40.0)
-                    .stroke_weight(model.ray_width)
-                    .color(model.palette.get_first(model.scheme_id, model.color_off));
+                    .stroke_weight(model.settings.ray_width)
+                    .color(
+                        model
+                            .palette
+                            .get_first(model.settings.scheme_id, model.settings.color_off),
+                    );
             }
         }
-        if model.draw_tex_overlay {
+        if model.settings.draw_tex_overlay {
             draw.texture(&model.texture).w_h(800.0, 800.0);
         }
     }
@@ -551,12 +351,12 @@ fn view(app: &App, model: &Model, frame: Frame) {
     draw.to_frame(app, &frame).unwrap();
 
     if model.draw_gui {
-        model.ui.draw_to_frame(app, &frame).unwrap();
+        model.egui.draw_to_frame(&frame).unwrap();
     }
 }
 
 fn make_walls(
-    walls: &mut Vec<Vector2>,
+    walls: &mut Vec<Vec2>,
     rays: &mut Vec<BouncingRay2D>,
     win: &geom::Rect,
     tile_count_w: u32,
@@ -602,7 +402,7 @@ fn make_walls(
                     let hole = (step as f32 / 2.0) * hole_pct;
                     if random_range(0.0, 1.0) > rays_prob {
                         let mut r = BouncingRay2D::new();
-                        //r.primary_ray.dir = Vector2::from_angle(random_range(-PI, PI));
+                        //r.primary_ray.dir = Vec2::from_angle(random_range(-PI, PI));
                         r.primary_ray.dir = vec2(PI.cos(), PI.sin());
                         r.primary_ray.orig = vec2(
                             square.x + square.width * 0.5,
@@ -671,7 +471,7 @@ fn make_walls(
                             start_p = vec2(xpos + padding, ypos + step as f32 - padding);
                             end_p = vec2(xpos + step as f32 - padding, ypos + padding);
                             let mut r = BouncingRay2D::new();
-                            //r.primary_ray.dir = Vector2::from_angle(random_range(-PI, PI));
+                            //r.primary_ray.dir = Vec2::from_angle(random_range(-PI, PI));
                             r.primary_ray.dir = vec2(1.0.cos(), 1.0.sin());
                             // r.primary_ray.orig = start_p;
                             // r.ray.orig = start_p;
@@ -710,7 +510,7 @@ fn make_walls(
 
 fn create_wall_from_square(
     square: &Square,
-    walls: &mut Vec<Vector2>,
+    walls: &mut Vec<Vec2>,
     mode: u32,
     padding: f32,
     hole: f32,
@@ -777,6 +577,7 @@ fn key_pressed(app: &App, model: &mut Model, key: Key) {
     }
 }
 
-fn raw_window_event(app: &App, model: &mut Model, event: &ui::RawWindowEvent) {
-    model.ui.handle_raw_event(app, event);
+fn raw_window_event(_app: &App, model: &mut Model, event: &nannou::winit::event::WindowEvent) {
+    // Let egui handle things like keyboard and mouse input.
+    model.egui.handle_raw_event(event);
 }
@@ -1,9 +1,8 @@
 // A demonstration of drawing to a very large texture, capturing the texture in its original size
 // as a PNG and displaying a down-scaled version of the image within the window each frame.
 
+use edapx_postprocessing::{Output, PostProcessingEffect, Uniforms};
 use nannou::prelude::*;
-pub mod post_processing_effect;
-pub use crate::post_processing_effect::PostProcessingEffect;
 
 fn main() {
     nannou::app(model).update(update).run();
@@ -27,20 +26,20 @@ fn model(app: &App) -> Model {
         .build()
         .unwrap();
 
-    let sample_count = app.window(w_id).unwrap().msaa_samples();
+    let window = app.window(w_id).unwrap();
+    let sample_count = window.msaa_samples();
 
-    // load the sahders
-    let vs_desc = wgpu::include_wgsl!("shaders/vs.wgsl");
-    // at the moment PostProcessingEffect works only with the default sample_count value, that is 4.
-    let fs_desc = wgpu::include_wgsl!("shaders/fs_msaa4_noise.wgsl");
-
-    let effect = PostProcessingEffect::new(
+    // the bindings of the shader are generated for the sample count of the window. The shader is
+    // read from the sources and reloaded when it is saved.
+    let effect = PostProcessingEffect::from_path(
         texture_size,
         sample_count,
-        app.window(w_id).unwrap().device(),
-        vs_desc,
-        fs_desc,
-    );
+        window.device(),
+        Output::window(&window),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/shaders/noise.wgsl"),
+        Uniforms::new(),
+    )
+    .expect("the shader file is missing");
 
     Model { effect }
 }
@@ -2,18 +2,6 @@ struct FragmentOutput {
     [[location(0)]] out_color: vec4<f32>;
 };
 
-[[block]]
-struct Data {
-    time: f32;
-};
-
-[[group(0), binding(0)]]
-var tex: texture_multisampled_2d<f32>;
-[[group(0), binding(1)]]
-var tex_sampler: sampler;
-[[group(0), binding(2)]]
-var<uniform> uniforms: Data;
-
 
 fn rand(st:vec2<f32>) -> f32 {
     return fract(sin(dot(st.xy,
@@ -59,7 +47,7 @@ fn main(
     [[location(0)]] tex_coords: vec2<f32>,
 ) -> FragmentOutput {
     // Get the integer tex coordinates.
-    let tex_size: vec2<i32> = textureDimensions(tex);
+    let tex_size: vec2<i32> = source_size();
     let waved_x = tex_coords.x + sin((uniforms.time + tex_coords.y) * 19.0) * 0.02;
 
     //let tex_x: i32 = i32(f32(tex_size.x) * tex_coords.x);
@@ -67,13 +55,8 @@ fn main(
     let tex_y: i32 = i32(f32(tex_size.y) * tex_coords.y);
     let itex_coords: vec2<i32> = vec2<i32>(tex_x, tex_y);
 
-    // Average the pixels with its neighbours
-    var color: vec4<f32> = vec4<f32>(0.0, 0.0, 0.0, 0.0);
-    color = color + textureLoad(tex, itex_coords, 0);
-    color = color + textureLoad(tex, itex_coords, 1);
-    color = color + textureLoad(tex, itex_coords, 2);
-    color = color + textureLoad(tex, itex_coords, 3);
-    color = color * 0.25;
+    // the samples of the pixel averaged
+    var color: vec4<f32> = source(itex_coords);
 
     // This is wrong, to apply a pixel effect on a pixel already averaged
     // leads to aliasing. Anyway ...
@@ -1,5 +1,9 @@
 use nannou::prelude::*;
-use svg::Svg;
+use svg::{Scaling, Svg};
+
+// the flattening of the curves and the longest side of a triangle with a gradient, in points
+const TOLERANCE: f32 = 0.1;
+const RESOLUTION: f32 = 4.0;
 
 fn main() {
     nannou::app(model).run();
@@ -7,48 +11,115 @@ fn main() {
 
 struct Model {
     svg: Svg,
+    // one for each path, tessellated again only when the svg is mapped to a new rect
+    meshes: Vec<Meshes>,
+}
+
+// gradients and patterns are meshes with a color for every vertex, empty for the solid paints
+struct Meshes {
+    fill: Vec<(Point3, Rgba)>,
+    stroke: Vec<(Point3, Rgba)>,
+}
+
+fn meshes(svg: &Svg) -> Vec<Meshes> {
+    let to_points = |mesh: Vec<(nannou::lyon::math::Point, Rgba)>| {
+        mesh.into_iter()
+            .map(|(v, color)| (pt3(v.x, v.y, 0.0), color))
+            .collect()
+    };
+    svg.paths
+        .iter()
+        .map(|p| {
+            let fill = match p.fill {
+                Some(ref fill) if fill.solid().is_none() => {
+                    to_points(p.fill_mesh(TOLERANCE, RESOLUTION))
+                }
+                _ => Vec::new(),
+            };
+            let stroke = match p.stroke_style {
+                Some(ref stroke) if stroke.paint.solid().is_none() => {
+                    to_points(p.stroke_mesh(TOLERANCE, RESOLUTION))
+                }
+                _ => Vec::new(),
+            };
+            Meshes { fill, stroke }
+        })
+        .collect()
 }
 
 fn model(app: &App) -> Model {
-    app.new_window().size(1200, 800).view(view).build().unwrap();
+    app.new_window()
+        .size(1200, 800)
+        .view(view)
+        .resized(resized)
+        .build()
+        .unwrap();
 
     let assets = app.assets_path().unwrap();
     let svg_path = assets.join("svgs").join("tiger.svg");
-    let svg = Svg::load(svg_path.to_path_buf()).expect("failed to load svg");
+    let mut svg = Svg::load(&svg_path).expect("failed to load svg");
+    for warning in svg.warnings.iter() {
+        println!("{}: {}", svg_path.display(), warning);
+    }
+    svg.map_to(app.window_rect(), Scaling::Fit);
+    let meshes = meshes(&svg);
+
+    Model { svg, meshes }
+}
 
-    Model { svg }
+fn resized(_app: &App, m: &mut Model, size: Vec2) {
+    m.svg.map_to(Rect::from_wh(size), Scaling::Fit);
+    m.meshes = meshes(&m.svg);
 }
 
 fn view(app: &App, m: &Model, frame: Frame) {
     frame.clear(WHITE);
     let draw = app.draw();
 
-    m.svg.paths.iter().for_each(|p| {
-        draw.path()
-            .stroke()
-            //.x_y(app.mouse.x, app.mouse.y)
-            .stroke_weight(1.0)
-            .color(BLACK)
-            .events(p.events.iter().cloned());
-
-        if let Some(color) = p.fill_color {
-            draw.path()
-                .fill()
-                //.x_y(app.mouse.x, app.mouse.y)
-                .color(color)
-                .events(p.events.iter().cloned());
-        }
-        if let Some(ref stroke) = p.stroke_style {
+    m.svg
+        .paths
+        .iter()
+        .zip(m.meshes.iter())
+        .for_each(|(p, mesh)| {
             draw.path()
                 .stroke()
                 //.x_y(app.mouse.x, app.mouse.y)
-                .stroke_weight(stroke.weight)
-                .color(stroke.color)
-                .join(stroke.line_join)
-                .caps(stroke.line_cap)
+                .stroke_weight(1.0)
+                .color(BLACK)
                 .events(p.events.iter().cloned());
-        }
-    });
+
+            if let Some(ref fill) = p.fill {
+                match fill.solid() {
+                    Some(color) => {
+                        draw.path()
+                            .fill()
+                            //.x_y(app.mouse.x, app.mouse.y)
+                            .color(color)
+                            .events(p.events.iter().cloned());
+                    }
+                    None => {
+                        draw.mesh().points_colored(mesh.fill.iter().cloned());
+                    }
+                }
+            }
+            if let Some(ref stroke) = p.stroke_style {
+                match stroke.paint.solid() {
+                    Some(color) => {
+                        draw.path()
+                            .stroke()
+                            //.x_y(app.mouse.x, app.mouse.y)
+                            .stroke_weight(stroke.weight)
+                            .color(color)
+                            .join(stroke.line_join)
+                            .caps(stroke.line_cap)
+                            .events(p.events.iter().cloned());
+                    }
+                    None => {
+                        draw.mesh().points_colored(mesh.stroke.iter().cloned());
+                    }
+                }
+            }
+        });
 
     // Write the result of our drawing to the window's frame.
     draw.to_frame(app, &frame).unwrap();
@@ -0,0 +1,63 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum SvgError {
+    Io(io::Error),
+    // the data is not a valid svg, the string explains why
+    Parse(String),
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SvgError::Io(err) => write!(f, "cannot read the svg: {}", err),
+            SvgError::Parse(msg) => write!(f, "invalid svg: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SvgError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SvgError {
+    fn from(err: io::Error) -> Self {
+        SvgError::Io(err)
+    }
+}
+
+impl From<usvg::Error> for SvgError {
+    fn from(err: usvg::Error) -> Self {
+        SvgError::Parse(err.to_string())
+    }
+}
+
+// Something in the svg that is drawn differently than in a browser. The svg is loaded anyway, the
+// warnings are in `Svg::warnings`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgWarning {
+    // an element that is not drawn, like an image, a clip path, a mask or a filter
+    UnsupportedElement(String),
+    // an attribute that is ignored, like the dashes of a stroke or the opacity of a group
+    UnsupportedAttribute(String),
+    // the id of a paint that is neither a gradient nor a pattern, it is transparent
+    UnsupportedPaint(String),
+}
+
+impl fmt::Display for SvgWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SvgWarning::UnsupportedElement(name) => write!(f, "unsupported element: {}", name),
+            SvgWarning::UnsupportedAttribute(name) => {
+                write!(f, "unsupported attribute: {}", name)
+            }
+            SvgWarning::UnsupportedPaint(id) => write!(f, "unsupported paint: #{}", id),
+        }
+    }
+}
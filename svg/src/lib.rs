@@ -1,123 +1,525 @@
-//! A suite of common interpolation functions often referred to as "easing" and "tweening"
-//! functions. This API is provided by the [**pennereq** crate](https://docs.rs/pennereq).
-//use nannou::cgmath::{Transform, Vector3};
+//! Loads an svg as lyon path events with their paints, ready to be drawn by nannou:
+//!
+//! let mut svg = Svg::load(path)?;
+//! svg.map_to(app.window_rect(), Scaling::Fit);
+//!
+//! `Svg::from_data` and `str::parse` load an svg that is already in memory. What cannot be drawn
+//! as in a browser is listed in `svg.warnings`.
 use nannou::color::{rgba, Rgba};
+use nannou::lyon::algorithms::aabb::bounding_rect;
 use nannou::lyon::math::Point;
 use nannou::lyon::path::PathEvent;
-use nannou::lyon::tessellation::{LineCap, LineJoin};
-//use nannou::math::{Matrix3, Matrix4};
+use nannou::lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, FillVertex, LineCap, LineJoin, StrokeOptions,
+    StrokeTessellator, StrokeVertex, VertexBuffers,
+};
+use std::path::Path;
+use std::str::FromStr;
 use usvg;
 
 use nannou::prelude::*;
 
+mod error;
+mod paint;
+
+pub use error::{SvgError, SvgWarning};
+pub use paint::{Gradient, GradientStop, Paint, Pattern, SpreadMethod, Transform};
+
+#[derive(Clone)]
 pub struct StrokeStyle {
-    pub color: Rgba,
+    pub paint: Paint,
     pub weight: f32,
     pub line_cap: LineCap,
     pub line_join: LineJoin,
 }
 
+#[derive(Clone)]
 pub struct SvgPath {
     pub events: Vec<PathEvent>,
-    pub fill_color: Option<Rgba>,
+    pub fill: Option<Paint>,
     pub stroke_style: Option<StrokeStyle>,
     pub width: f32,
     pub height: f32,
 }
 
+impl SvgPath {
+    // The fill as triangles with a color for every vertex, three points for every triangle, ready
+    // for `draw.mesh().points_colored`. The triangles of a gradient or a pattern are split until
+    // their sides are shorter than `resolution`, the colors are interpolated between the vertices.
+    pub fn fill_mesh(&self, tolerance: f32, resolution: f32) -> Vec<(Point, Rgba)> {
+        let paint = match self.fill {
+            Some(ref paint) => paint,
+            None => return Vec::new(),
+        };
+        let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+        let result = FillTessellator::new().tessellate(
+            self.events.iter().cloned(),
+            &FillOptions::tolerance(tolerance),
+            &mut BuffersBuilder::new(&mut buffers, |v: FillVertex| v.position()),
+        );
+        if result.is_err() {
+            return Vec::new();
+        }
+        colored_triangles(&buffers, paint, resolution)
+    }
+
+    // the same for the stroke, see `fill_mesh`
+    pub fn stroke_mesh(&self, tolerance: f32, resolution: f32) -> Vec<(Point, Rgba)> {
+        let stroke = match self.stroke_style {
+            Some(ref stroke) => stroke,
+            None => return Vec::new(),
+        };
+        let options = StrokeOptions::tolerance(tolerance)
+            .with_line_width(stroke.weight)
+            .with_line_cap(stroke.line_cap)
+            .with_line_join(stroke.line_join);
+        let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+        let result = StrokeTessellator::new().tessellate(
+            self.events.iter().cloned(),
+            &options,
+            &mut BuffersBuilder::new(&mut buffers, |v: StrokeVertex| v.position()),
+        );
+        if result.is_err() {
+            return Vec::new();
+        }
+        colored_triangles(&buffers, &stroke.paint, resolution)
+    }
+
+    // moves the path and its paint, the stroke gets thicker with the scale
+    pub fn transform(&mut self, t: &Transform) {
+        let p = |p: Point| t.apply(p);
+        for event in self.events.iter_mut() {
+            *event = match *event {
+                PathEvent::Begin { at } => PathEvent::Begin { at: p(at) },
+                PathEvent::Line { from, to } => PathEvent::Line {
+                    from: p(from),
+                    to: p(to),
+                },
+                PathEvent::Quadratic { from, ctrl, to } => PathEvent::Quadratic {
+                    from: p(from),
+                    ctrl: p(ctrl),
+                    to: p(to),
+                },
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => PathEvent::Cubic {
+                    from: p(from),
+                    ctrl1: p(ctrl1),
+                    ctrl2: p(ctrl2),
+                    to: p(to),
+                },
+                PathEvent::End { last, first, close } => PathEvent::End {
+                    last: p(last),
+                    first: p(first),
+                    close,
+                },
+            };
+        }
+        if let Some(ref mut fill) = self.fill {
+            fill.transform(t);
+        }
+        if let Some(ref mut stroke) = self.stroke_style {
+            stroke.paint.transform(t);
+            stroke.weight *= (t.a * t.d - t.b * t.c).abs().sqrt();
+        }
+    }
+}
+
+fn colored_triangles(
+    buffers: &VertexBuffers<Point, u32>,
+    paint: &Paint,
+    resolution: f32,
+) -> Vec<(Point, Rgba)> {
+    let mut points = Vec::with_capacity(buffers.indices.len());
+    for triangle in buffers.indices.chunks_exact(3) {
+        let a = buffers.vertices[triangle[0] as usize];
+        let b = buffers.vertices[triangle[1] as usize];
+        let c = buffers.vertices[triangle[2] as usize];
+        if paint.solid().is_some() {
+            points.extend_from_slice(&[a, b, c]);
+        } else {
+            subdivide(a, b, c, resolution.max(0.1), 0, &mut points);
+        }
+    }
+    points.into_iter().map(|p| (p, paint.color_at(p))).collect()
+}
+
+// splits a triangle in four until its sides are short enough
+fn subdivide(a: Point, b: Point, c: Point, resolution: f32, depth: u32, points: &mut Vec<Point>) {
+    let longest = (b - a).length().max((c - b).length()).max((a - c).length());
+    // a huge triangle would become millions of points
+    if longest <= resolution || depth >= 8 {
+        points.extend_from_slice(&[a, b, c]);
+        return;
+    }
+    let ab = a.lerp(b, 0.5);
+    let bc = b.lerp(c, 0.5);
+    let ca = c.lerp(a, 0.5);
+    subdivide(a, ab, ca, resolution, depth + 1, points);
+    subdivide(ab, b, bc, resolution, depth + 1, points);
+    subdivide(ca, bc, c, resolution, depth + 1, points);
+    subdivide(ab, bc, ca, resolution, depth + 1, points);
+}
+
+// How the view box is placed in a rect, always in the middle of it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Scaling {
+    // the whole view box is visible, like preserveAspectRatio="xMidYMid meet"
+    Fit,
+    // the view box covers the rect and it is cut on the sides, like "xMidYMid slice"
+    Fill,
+    // one unit of the svg is one point
+    Center,
+}
+
 pub struct Svg {
     pub paths: Vec<SvgPath>,
+    // in svg units, the y axis goes down
+    pub view_box: Rect,
+    // from svg units to the coordinates of the paths, see `map_to`
+    pub transform: Transform,
+    // what is not drawn as in a browser, every warning once
+    pub warnings: Vec<SvgWarning>,
+    // the paths in svg units, every `map_to` starts from them
+    source: Vec<SvgPath>,
 }
 
 impl Svg {
-    pub fn load(asset: std::path::PathBuf) -> Result<Self, usvg::Error> {
-        let opt = usvg::Options::default();
-        let rtree = usvg::Tree::from_file(&asset, &opt)?;
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SvgError> {
+        let data = std::fs::read(path)?;
+        Svg::from_data(&data)
+    }
+
+    // An svg in memory, an embedded asset too:
+    //
+    // Svg::from_data(include_bytes!("../assets/logo.svg"))
+    //
+    // compressed svgz data works as well.
+    pub fn from_data(data: &[u8]) -> Result<Self, SvgError> {
+        let rtree = usvg::Tree::from_data(data, &usvg::Options::default())?;
+        Ok(Svg::from_tree(&rtree))
+    }
+
+    fn from_tree(rtree: &usvg::Tree) -> Self {
         let view_box = rtree.svg_node().view_box;
+        let width = view_box.rect.size().width() as f32;
+        let height = view_box.rect.size().height() as f32;
+        let (x, y) = (view_box.rect.x() as f32, view_box.rect.y() as f32);
         let mut paths = Vec::new();
+        let mut warnings = Vec::new();
 
         for node in rtree.root().descendants() {
-            dbg!(&node);
-            if let usvg::NodeKind::Path(ref p) = *node.borrow() {
-                let t = p.transform;
-                // let mut matrix: Matrix4::<f64> = Matrix3::from_cols(
-                //     Vector3::new(t.a, t.b, 0.0),
-                //     Vector3::new(t.c, t.d, 0.0),
-                //     Vector3::new(t.e, t.f, 1.0)).into();
-
-                let convert_color = |c: usvg::Color, opacity: usvg::NormalizedValue| -> Rgba {
-                    rgba(
-                        c.red as f32 / 255.0,
-                        c.green as f32 / 255.0,
-                        c.blue as f32 / 255.0,
-                        opacity.value() as f32,
-                    )
-                };
-
-                let fill_color = match &p.fill {
-                    None => None,
-                    Some(fill) => {
-                        let opacity = fill.opacity;
-                        match fill.paint {
-                            usvg::Paint::Color(c) => Some(convert_color(c, opacity)),
-                            _ => Some(rgba(0.0, 0.0, 0.0, 0.0)),
-                        }
+            // the content of gradients and patterns is only drawn through the paths that use them
+            let in_defs = node
+                .ancestors()
+                .any(|n| matches!(*n.borrow(), usvg::NodeKind::Defs));
+            if in_defs {
+                continue;
+            }
+            match *node.borrow() {
+                usvg::NodeKind::Path(ref p) => {
+                    paths.push(load_path(rtree, &node, p, width, height, &mut warnings));
+                }
+                usvg::NodeKind::Group(ref g) => {
+                    if g.clip_path.is_some() {
+                        warn(
+                            &mut warnings,
+                            SvgWarning::UnsupportedElement("clipPath".into()),
+                        );
+                    }
+                    if g.mask.is_some() {
+                        warn(&mut warnings, SvgWarning::UnsupportedElement("mask".into()));
+                    }
+                    if g.filter.is_some() {
+                        warn(
+                            &mut warnings,
+                            SvgWarning::UnsupportedElement("filter".into()),
+                        );
                     }
-                };
-
-                let stroke_style = match &p.stroke {
-                    None => None,
-                    Some(stroke) => {
-                        let opacity = stroke.opacity;
-                        let color = match stroke.paint {
-                            usvg::Paint::Color(c) => convert_color(c, opacity),
-                            _ => rgba(0.0, 0.0, 0.0, 0.0),
-                        };
-                        let line_cap = match stroke.linecap {
-                            usvg::LineCap::Butt => LineCap::Butt,
-                            usvg::LineCap::Round => LineCap::Round,
-                            usvg::LineCap::Square => LineCap::Square,
-                        };
-                        let line_join = match stroke.linejoin {
-                            usvg::LineJoin::Miter => LineJoin::Miter,
-                            usvg::LineJoin::Round => LineJoin::Round,
-                            usvg::LineJoin::Bevel => LineJoin::Bevel,
-                        };
-                        Some(StrokeStyle {
-                            color,
-                            weight: stroke.width.value() as f32,
-                            line_cap,
-                            line_join,
-                        })
+                    if g.opacity.value() < 1.0 {
+                        warn(
+                            &mut warnings,
+                            SvgWarning::UnsupportedAttribute("opacity".into()),
+                        );
                     }
-                };
+                }
+                usvg::NodeKind::Image(_) => {
+                    warn(
+                        &mut warnings,
+                        SvgWarning::UnsupportedElement("image".into()),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Svg {
+            paths: paths.clone(),
+            view_box: Rect::from_corners(pt2(x, y), pt2(x + width, y + height)),
+            transform: Transform::default(),
+            warnings,
+            source: paths,
+        }
+    }
+
+    // Moves the paths from svg units to a rect in nannou coordinates, with the y axis going up.
+    // It can be called again, when the window is resized:
+    //
+    // svg.map_to(app.window_rect(), Scaling::Fit);
+    // An empty rect, like a minimized window, leaves the paths where they are.
+    pub fn map_to(&mut self, target: Rect, scaling: Scaling) {
+        if target.w() <= 0.0 || target.h() <= 0.0 {
+            return;
+        }
+        let transform = view_box_transform(self.view_box, target, scaling);
+        self.paths = self.source.clone();
+        for path in self.paths.iter_mut() {
+            path.transform(&transform);
+        }
+        self.transform = transform;
+    }
+}
+
+fn view_box_transform(view_box: Rect, target: Rect, scaling: Scaling) -> Transform {
+    let (sx, sy) = (target.w() / view_box.w(), target.h() / view_box.h());
+    let scale = match scaling {
+        _ if !sx.is_finite() || !sy.is_finite() => 1.0,
+        Scaling::Fit => sx.min(sy),
+        Scaling::Fill => sx.max(sy),
+        Scaling::Center => 1.0,
+    };
+    // the centers meet and the y axis is flipped
+    Transform::new(
+        scale,
+        0.0,
+        0.0,
+        -scale,
+        target.x() - view_box.x() * scale,
+        target.y() + view_box.y() * scale,
+    )
+}
+
+// an svg in a string, `text.parse::<Svg>()`
+impl FromStr for Svg {
+    type Err = SvgError;
+
+    fn from_str(text: &str) -> Result<Self, SvgError> {
+        let rtree = usvg::Tree::from_str(text, &usvg::Options::default())?;
+        Ok(Svg::from_tree(&rtree))
+    }
+}
 
-                let path = convert_path(p);
-                let mut path_events = Vec::new();
-                for e in path {
-                    path_events.push(e);
+// The transform of a node and of all its groups, from its coordinates to the svg units.
+fn absolute_transform(node: &usvg::Node) -> Transform {
+    node.ancestors()
+        .fold(Transform::default(), |transform, n| match *n.borrow() {
+            usvg::NodeKind::Path(ref p) => transform.then(&convert_transform(p.transform)),
+            usvg::NodeKind::Group(ref g) => transform.then(&convert_transform(g.transform)),
+            _ => transform,
+        })
+}
+
+// The paint is read in the coordinates of the path, then the path and the paint are moved to svg
+// units together.
+fn load_path(
+    rtree: &usvg::Tree,
+    node: &usvg::Node,
+    p: &usvg::Path,
+    width: f32,
+    height: f32,
+    warnings: &mut Vec<SvgWarning>,
+) -> SvgPath {
+    let events: Vec<PathEvent> = convert_path(p).collect();
+    // the gradients and the patterns in objectBoundingBox units are relative to it
+    let bounds = bounding_rect(events.iter().cloned());
+    let bbox = Transform::from_rect(
+        bounds.origin.x,
+        bounds.origin.y,
+        bounds.size.width,
+        bounds.size.height,
+    );
+
+    let fill = p.fill.as_ref().map(|fill| {
+        let opacity = fill.opacity.value() as f32;
+        convert_paint(rtree, &fill.paint, opacity, &bbox, warnings)
+    });
+
+    let stroke_style = p.stroke.as_ref().map(|stroke| {
+        let opacity = stroke.opacity.value() as f32;
+        let paint = convert_paint(rtree, &stroke.paint, opacity, &bbox, warnings);
+        if stroke.dasharray.is_some() {
+            warn(
+                warnings,
+                SvgWarning::UnsupportedAttribute("stroke-dasharray".into()),
+            );
+        }
+        let line_cap = match stroke.linecap {
+            usvg::LineCap::Butt => LineCap::Butt,
+            usvg::LineCap::Round => LineCap::Round,
+            usvg::LineCap::Square => LineCap::Square,
+        };
+        let line_join = match stroke.linejoin {
+            usvg::LineJoin::Miter => LineJoin::Miter,
+            usvg::LineJoin::Round => LineJoin::Round,
+            usvg::LineJoin::Bevel => LineJoin::Bevel,
+        };
+        StrokeStyle {
+            paint,
+            weight: stroke.width.value() as f32,
+            line_cap,
+            line_join,
+        }
+    });
+
+    let mut path = SvgPath {
+        events,
+        fill,
+        stroke_style,
+        width,
+        height,
+    };
+    path.transform(&absolute_transform(node));
+    path
+}
+
+fn convert_color(c: usvg::Color, opacity: f32) -> Rgba {
+    rgba(
+        c.red as f32 / 255.0,
+        c.green as f32 / 255.0,
+        c.blue as f32 / 255.0,
+        opacity,
+    )
+}
+
+fn convert_transform(t: usvg::Transform) -> Transform {
+    Transform::new(
+        t.a as f32, t.b as f32, t.c as f32, t.d as f32, t.e as f32, t.f as f32,
+    )
+}
+
+// Gradients and patterns are nodes in the defs, the paint links them by id. Anything else is
+// transparent.
+fn convert_paint(
+    rtree: &usvg::Tree,
+    paint: &usvg::Paint,
+    opacity: f32,
+    bbox: &Transform,
+    warnings: &mut Vec<SvgWarning>,
+) -> Paint {
+    let id = match paint {
+        usvg::Paint::Color(c) => return Paint::Color(convert_color(*c, opacity)),
+        usvg::Paint::Link(id) => id,
+    };
+    let node = match rtree.defs_by_id(id) {
+        Some(node) => node,
+        None => {
+            warn(warnings, SvgWarning::UnsupportedPaint(id.clone()));
+            return Paint::Color(rgba(0.0, 0.0, 0.0, 0.0));
+        }
+    };
+    let kind = node.borrow();
+    match *kind {
+        usvg::NodeKind::LinearGradient(ref g) => Paint::LinearGradient {
+            start: point(&g.x1, &g.y1),
+            end: point(&g.x2, &g.y2),
+            gradient: convert_gradient(&g.base, opacity, bbox),
+        },
+        usvg::NodeKind::RadialGradient(ref g) => Paint::RadialGradient {
+            center: point(&g.cx, &g.cy),
+            radius: g.r.value() as f32,
+            focal: point(&g.fx, &g.fy),
+            gradient: convert_gradient(&g.base, opacity, bbox),
+        },
+        usvg::NodeKind::Pattern(ref pattern) => {
+            let rect = pattern.rect;
+            let (mut x, mut y) = (rect.x() as f32, rect.y() as f32);
+            let (mut width, mut height) = (rect.width() as f32, rect.height() as f32);
+            if matches!(pattern.units, usvg::Units::ObjectBoundingBox) {
+                x = bbox.e + x * bbox.a;
+                y = bbox.f + y * bbox.d;
+                width *= bbox.a;
+                height *= bbox.d;
+            }
+            // the content starts in the corner of the tile
+            let content = match pattern.view_box {
+                Some(ref view_box) => {
+                    let vb = view_box.rect;
+                    let scale = (width / vb.width() as f32).min(height / vb.height() as f32);
+                    Transform::new(
+                        scale,
+                        0.0,
+                        0.0,
+                        scale,
+                        (width - vb.width() as f32 * scale) / 2.0 - vb.x() as f32 * scale,
+                        (height - vb.height() as f32 * scale) / 2.0 - vb.y() as f32 * scale,
+                    )
                 }
-                let width = view_box.rect.size().width() as f32;
-                let height = view_box.rect.size().height() as f32;
-
-                paths.push(SvgPath {
-                    events: path_events,
-                    fill_color,
-                    stroke_style,
-                    width,
-                    height,
-                })
+                None if matches!(pattern.content_units, usvg::Units::ObjectBoundingBox) => {
+                    Transform::new(bbox.a, 0.0, 0.0, bbox.d, 0.0, 0.0)
+                }
+                None => Transform::default(),
             }
+            .then(&Transform::new(1.0, 0.0, 0.0, 1.0, x, y));
+
+            let paths = node
+                .descendants()
+                .filter_map(|n| match *n.borrow() {
+                    usvg::NodeKind::Path(ref p) => {
+                        let mut path = load_path(rtree, &n, p, width, height, warnings);
+                        path.transform(&content);
+                        Some(path)
+                    }
+                    _ => None,
+                })
+                .collect();
+            Paint::Pattern(Pattern {
+                x,
+                y,
+                width,
+                height,
+                transform: convert_transform(pattern.transform),
+                opacity,
+                paths,
+            })
+        }
+        _ => {
+            warn(warnings, SvgWarning::UnsupportedPaint(id.clone()));
+            Paint::Color(rgba(0.0, 0.0, 0.0, 0.0))
         }
+    }
+}
+
+fn warn(warnings: &mut Vec<SvgWarning>, warning: SvgWarning) {
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+}
 
-        Ok(Svg { paths })
-        // Ok(Svg {
-        //     events: Vec::new(),
-        //     fill_color: None,
-        //     stroke_style: None,
-        //     width: 0.0,
-        //     height: 0.0,
-        // })
+fn convert_gradient(base: &usvg::BaseGradient, opacity: f32, bbox: &Transform) -> Gradient {
+    let mut transform = convert_transform(base.transform);
+    if matches!(base.units, usvg::Units::ObjectBoundingBox) {
+        transform = transform.then(bbox);
+    }
+    let stops = base
+        .stops
+        .iter()
+        .map(|stop| GradientStop {
+            offset: stop.offset.value() as f32,
+            color: convert_color(stop.color, opacity * stop.opacity.value() as f32),
+        })
+        .collect();
+    let spread = match base.spread_method {
+        usvg::SpreadMethod::Pad => SpreadMethod::Pad,
+        usvg::SpreadMethod::Reflect => SpreadMethod::Reflect,
+        usvg::SpreadMethod::Repeat => SpreadMethod::Repeat,
+    };
+    Gradient {
+        stops,
+        spread,
+        transform,
     }
 }
 
@@ -148,15 +550,9 @@ impl<'l> Iterator for PathConvIter<'l> {
                 self.segment_iter = Some(self.sub_path_iter.next()?.0.iter());
             }
 
-            //let mut matrix = Matrix4::<f64>::from_scale(1.0);
-            //let flip = Matrix4::<f64>::from_nonuniform_scale(1.0, -1.0, 1.0);
-            //matrix = matrix * flip;
-            //dbg!(matrix.transform_point(cgmath::Point3::new(10.0,50.0,2.0)));
-
             let next = self.segment_iter.as_mut().and_then(|it| it.next());
             match next {
                 Some(usvg::PathSegment::MoveTo { x, y }) => {
-                    //let (x, y) = matrix.transform_point(point(x, y)).into();
                     if self.needs_end {
                         let last = self.prev;
                         let first = self.first;
@@ -238,3 +634,109 @@ pub fn convert_path<'a>(p: &'a usvg::Path) -> PathConvIter<'a> {
         needs_end: false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapped(t: &Transform, x: f32, y: f32) -> (f32, f32) {
+        let p = t.apply(Point::new(x, y));
+        (p.x, p.y)
+    }
+
+    #[test]
+    fn view_box_in_a_rect() {
+        // a view box of 200 x 100 units, its corner on (10, 20)
+        let view_box = Rect::from_corners(pt2(10.0, 20.0), pt2(210.0, 120.0));
+        let target = Rect::from_x_y_w_h(0.0, 0.0, 400.0, 400.0);
+
+        // the top left corner goes up, the y axis is flipped
+        let fit = view_box_transform(view_box, target, Scaling::Fit);
+        assert_eq!(mapped(&fit, 10.0, 20.0), (-200.0, 100.0));
+        assert_eq!(mapped(&fit, 210.0, 120.0), (200.0, -100.0));
+
+        let fill = view_box_transform(view_box, target, Scaling::Fill);
+        assert_eq!(mapped(&fill, 10.0, 20.0), (-400.0, 200.0));
+        assert_eq!(mapped(&fill, 110.0, 70.0), (0.0, 0.0));
+
+        let center = view_box_transform(view_box, target, Scaling::Center);
+        assert_eq!(mapped(&center, 10.0, 20.0), (-100.0, 50.0));
+    }
+
+    #[test]
+    fn loads_from_a_string() {
+        let svg: Svg = r##"
+            <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+                <linearGradient id="fade">
+                    <stop offset="0" stop-color="#ff0000"/>
+                    <stop offset="1" stop-color="#0000ff"/>
+                </linearGradient>
+                <g transform="translate(10 0)">
+                    <path d="M 0 0 L 50 0 L 50 50 Z" fill="url(#fade)"/>
+                </g>
+                <path d="M 0 90 L 100 90" stroke="black" stroke-dasharray="4 2"/>
+            </svg>
+        "##
+        .parse()
+        .unwrap();
+
+        assert_eq!(svg.paths.len(), 2);
+        let gradient = &svg.paths[0];
+        match gradient.events[0] {
+            PathEvent::Begin { at } => assert_eq!(at, Point::new(10.0, 0.0)),
+            _ => panic!("the path does not begin with a move"),
+        }
+        // the gradient follows the bounding box of the path, from 10 to 60
+        let fill = gradient.fill.as_ref().unwrap();
+        let left = fill.color_at(Point::new(10.0, 25.0));
+        let right = fill.color_at(Point::new(60.0, 25.0));
+        assert!(left.red > 0.99 && left.blue < 0.01);
+        assert!(right.blue > 0.99 && right.red < 0.01);
+
+        assert_eq!(
+            svg.warnings,
+            vec![SvgWarning::UnsupportedAttribute("stroke-dasharray".into())]
+        );
+    }
+
+    #[test]
+    fn mapped_again_from_svg_units() {
+        let mut svg: Svg = r##"
+            <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+                <path d="M 0 0 L 100 100" stroke="black"/>
+            </svg>
+        "##
+        .parse()
+        .unwrap();
+        let first_point = |svg: &Svg| match svg.paths[0].events[0] {
+            PathEvent::Begin { at } => (at.x, at.y),
+            _ => panic!("the path does not begin with a move"),
+        };
+
+        let target = Rect::from_x_y_w_h(0.0, 0.0, 200.0, 200.0);
+        svg.map_to(target, Scaling::Fit);
+        assert_eq!(first_point(&svg), (-100.0, 100.0));
+        // a minimized window does not collapse the paths
+        svg.map_to(Rect::from_x_y_w_h(0.0, 0.0, 0.0, 0.0), Scaling::Fit);
+        assert_eq!(first_point(&svg), (-100.0, 100.0));
+        svg.map_to(Rect::from_x_y_w_h(0.0, 0.0, 50.0, 0.0), Scaling::Fit);
+        assert_eq!(first_point(&svg), (-100.0, 100.0));
+
+        for i in 0..100 {
+            let side = 100.0 + i as f32 * 7.3;
+            svg.map_to(Rect::from_x_y_w_h(0.0, 0.0, side, side), Scaling::Fit);
+        }
+        svg.map_to(target, Scaling::Fit);
+        assert_eq!(first_point(&svg), (-100.0, 100.0));
+        assert_eq!(svg.paths[0].stroke_style.as_ref().unwrap().weight, 2.0);
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert!(matches!(
+            Svg::from_data(b"not an svg"),
+            Err(SvgError::Parse(_))
+        ));
+        assert!(matches!(Svg::load("missing.svg"), Err(SvgError::Io(_))));
+    }
+}
@@ -0,0 +1,392 @@
+// How a path is filled or stroked. A solid color is drawn as it is, gradients and patterns have a
+// color for every point of the path, see `Paint::color_at`, the renderer samples it at the
+// vertices of a fine tessellation, see `SvgPath::fill_mesh`.
+use crate::SvgPath;
+use nannou::color::{rgba, Rgba};
+use nannou::lyon::algorithms::hit_test::hit_test_path;
+use nannou::lyon::math::Point;
+use nannou::lyon::tessellation::FillRule;
+
+// An affine transformation, the same six numbers of the svg `matrix(a b c d e f)`:
+// x' = a * x + c * y + e
+// y' = b * x + d * y + f
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+}
+
+impl Transform {
+    pub fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Transform { a, b, c, d, e, f }
+    }
+
+    // maps the unit square to the rectangle
+    pub fn from_rect(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Transform::new(width, 0.0, 0.0, height, x, y)
+    }
+
+    pub fn apply(&self, p: Point) -> Point {
+        Point::new(
+            self.a * p.x + self.c * p.y + self.e,
+            self.b * p.x + self.d * p.y + self.f,
+        )
+    }
+
+    // this transformation first, then `other`
+    pub fn then(&self, other: &Transform) -> Transform {
+        Transform {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    // None when the transformation squashes everything on a line
+    pub fn invert(&self) -> Option<Transform> {
+        let det = self.a * self.d - self.b * self.c;
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        Some(Transform {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+    }
+}
+
+// what happens before the first stop and after the last one
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SpreadMethod {
+    // the colors of the first and of the last stops
+    Pad,
+    // the gradient goes back and forth
+    Reflect,
+    // the gradient starts again from the beginning
+    Repeat,
+}
+
+impl SpreadMethod {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            SpreadMethod::Pad => t.clamp(0.0, 1.0),
+            SpreadMethod::Repeat => t.rem_euclid(1.0),
+            SpreadMethod::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GradientStop {
+    // between 0 and 1, sorted
+    pub offset: f32,
+    // the opacity of the stop and of the paint are in the alpha
+    pub color: Rgba,
+}
+
+// The part shared by linear and radial gradients. The transform maps the space of the gradient to
+// the space of the path, the bounding box of the path is already in it when the gradient units
+// are objectBoundingBox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub stops: Vec<GradientStop>,
+    pub spread: SpreadMethod,
+    pub transform: Transform,
+}
+
+impl Gradient {
+    // the color at a position along the gradient, 0 is the start and 1 the end
+    pub fn color(&self, t: f32) -> Rgba {
+        let t = self.spread.apply(t);
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return rgba(0.0, 0.0, 0.0, 0.0),
+        };
+        if t <= first.offset {
+            return first.color;
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if t <= b.offset {
+                // two stops on the same offset make a hard edge
+                let span = b.offset - a.offset;
+                let k = if span > 0.0 {
+                    (t - a.offset) / span
+                } else {
+                    1.0
+                };
+                return rgba(
+                    a.color.red + (b.color.red - a.color.red) * k,
+                    a.color.green + (b.color.green - a.color.green) * k,
+                    a.color.blue + (b.color.blue - a.color.blue) * k,
+                    a.color.alpha + (b.color.alpha - a.color.alpha) * k,
+                );
+            }
+        }
+        last.color
+    }
+}
+
+// The content of one tile, repeated over the whole plane. The rect and the paths are in the space
+// of the pattern, the transform maps it to the space of the path.
+#[derive(Clone)]
+pub struct Pattern {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub transform: Transform,
+    pub opacity: f32,
+    pub paths: Vec<SvgPath>,
+}
+
+#[derive(Clone)]
+pub enum Paint {
+    Color(Rgba),
+    LinearGradient {
+        start: Point,
+        end: Point,
+        gradient: Gradient,
+    },
+    // the focal point is where the gradient starts, it is the center in most of the svgs
+    RadialGradient {
+        center: Point,
+        radius: f32,
+        focal: Point,
+        gradient: Gradient,
+    },
+    Pattern(Pattern),
+}
+
+impl Paint {
+    // the same color everywhere, it does not need a colored mesh
+    pub fn solid(&self) -> Option<Rgba> {
+        match self {
+            Paint::Color(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    // follows a path that is moved by `t`, see `SvgPath::transform`
+    pub fn transform(&mut self, t: &Transform) {
+        match self {
+            Paint::Color(_) => {}
+            Paint::LinearGradient { gradient, .. } | Paint::RadialGradient { gradient, .. } => {
+                gradient.transform = gradient.transform.then(t);
+            }
+            Paint::Pattern(pattern) => pattern.transform = pattern.transform.then(t),
+        }
+    }
+
+    // the color at a point, in the coordinates of the path
+    pub fn color_at(&self, p: Point) -> Rgba {
+        match self {
+            Paint::Color(color) => *color,
+            Paint::LinearGradient {
+                start,
+                end,
+                gradient,
+            } => {
+                let p = match gradient.transform.invert() {
+                    Some(inverse) => inverse.apply(p),
+                    None => return gradient.color(0.0),
+                };
+                let direction = *end - *start;
+                let length = direction.square_length();
+                if length <= 0.0 {
+                    // SVG paints the last stop when the start and the end are the same
+                    return gradient.color(1.0);
+                }
+                gradient.color((p - *start).dot(direction) / length)
+            }
+            Paint::RadialGradient {
+                center,
+                radius,
+                focal,
+                gradient,
+            } => {
+                let p = match gradient.transform.invert() {
+                    Some(inverse) => inverse.apply(p),
+                    None => return gradient.color(0.0),
+                };
+                gradient.color(radial_offset(p, *center, *radius, *focal))
+            }
+            Paint::Pattern(pattern) => {
+                let p = match pattern.transform.invert() {
+                    Some(inverse) => inverse.apply(p),
+                    None => return rgba(0.0, 0.0, 0.0, 0.0),
+                };
+                if pattern.width <= 0.0 || pattern.height <= 0.0 {
+                    return rgba(0.0, 0.0, 0.0, 0.0);
+                }
+                let p = Point::new(
+                    pattern.x + (p.x - pattern.x).rem_euclid(pattern.width),
+                    pattern.y + (p.y - pattern.y).rem_euclid(pattern.height),
+                );
+                // the last path is the one on top
+                pattern
+                    .paths
+                    .iter()
+                    .rev()
+                    .filter_map(|path| path.fill.as_ref().map(|fill| (path, fill)))
+                    .find(|(path, _)| {
+                        hit_test_path(&p, path.events.iter().cloned(), FillRule::NonZero, 0.1)
+                    })
+                    .map(|(_, fill)| {
+                        let color = fill.color_at(p);
+                        rgba(
+                            color.red,
+                            color.green,
+                            color.blue,
+                            color.alpha * pattern.opacity,
+                        )
+                    })
+                    .unwrap_or_else(|| rgba(0.0, 0.0, 0.0, 0.0))
+            }
+        }
+    }
+}
+
+// The position along a radial gradient: 0 on the focal point, 1 on the circle, on the line that
+// goes from the focal point through `p`.
+fn radial_offset(p: Point, center: Point, radius: f32, focal: Point) -> f32 {
+    if radius <= 0.0 {
+        return 1.0;
+    }
+    let to_p = p - focal;
+    let distance = to_p.length();
+    if distance <= f32::EPSILON {
+        return 0.0;
+    }
+    // where the ray from the focal point meets the circle
+    let direction = to_p / distance;
+    let from_center = focal - center;
+    let b = direction.dot(from_center);
+    let c = from_center.square_length() - radius * radius;
+    let to_circle = -b + (b * b - c).max(0.0).sqrt();
+    if to_circle <= f32::EPSILON {
+        return 1.0;
+    }
+    distance / to_circle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Rgba, b: Rgba) -> bool {
+        (a.red - b.red).abs() < 1e-3
+            && (a.green - b.green).abs() < 1e-3
+            && (a.blue - b.blue).abs() < 1e-3
+            && (a.alpha - b.alpha).abs() < 1e-3
+    }
+
+    fn black_to_white(spread: SpreadMethod) -> Gradient {
+        Gradient {
+            stops: vec![
+                GradientStop {
+                    offset: 0.0,
+                    color: rgba(0.0, 0.0, 0.0, 1.0),
+                },
+                GradientStop {
+                    offset: 1.0,
+                    color: rgba(1.0, 1.0, 1.0, 1.0),
+                },
+            ],
+            spread,
+            transform: Transform::default(),
+        }
+    }
+
+    fn gray(v: f32) -> Rgba {
+        rgba(v, v, v, 1.0)
+    }
+
+    #[test]
+    fn spread_methods() {
+        let pad = black_to_white(SpreadMethod::Pad);
+        assert!(close(pad.color(-0.5), gray(0.0)));
+        assert!(close(pad.color(0.25), gray(0.25)));
+        assert!(close(pad.color(1.5), gray(1.0)));
+
+        let repeat = black_to_white(SpreadMethod::Repeat);
+        assert!(close(repeat.color(1.25), gray(0.25)));
+        assert!(close(repeat.color(-0.25), gray(0.75)));
+
+        let reflect = black_to_white(SpreadMethod::Reflect);
+        assert!(close(reflect.color(1.25), gray(0.75)));
+        assert!(close(reflect.color(-0.25), gray(0.25)));
+    }
+
+    #[test]
+    fn gradients_follow_their_transform() {
+        let mut gradient = black_to_white(SpreadMethod::Pad);
+        // the bounding box of a path from (10, 20) to (110, 70)
+        gradient.transform = Transform::from_rect(10.0, 20.0, 100.0, 50.0);
+        let linear = Paint::LinearGradient {
+            start: Point::new(0.0, 0.0),
+            end: Point::new(1.0, 0.0),
+            gradient: gradient.clone(),
+        };
+        assert!(close(linear.color_at(Point::new(10.0, 40.0)), gray(0.0)));
+        assert!(close(linear.color_at(Point::new(35.0, 20.0)), gray(0.25)));
+        assert!(close(linear.color_at(Point::new(110.0, 70.0)), gray(1.0)));
+
+        let radial = Paint::RadialGradient {
+            center: Point::new(0.5, 0.5),
+            radius: 0.5,
+            focal: Point::new(0.5, 0.5),
+            gradient,
+        };
+        assert!(close(radial.color_at(Point::new(60.0, 45.0)), gray(0.0)));
+        assert!(close(radial.color_at(Point::new(85.0, 45.0)), gray(0.5)));
+        assert!(close(radial.color_at(Point::new(60.0, 57.5)), gray(0.5)));
+    }
+
+    #[test]
+    fn focal_point() {
+        let center = Point::new(0.0, 0.0);
+        let focal = Point::new(-5.0, 0.0);
+        assert_eq!(radial_offset(focal, center, 10.0, focal), 0.0);
+        // halfway from the focal point to the circle, in both directions
+        assert!((radial_offset(Point::new(2.5, 0.0), center, 10.0, focal) - 0.5).abs() < 1e-4);
+        assert!((radial_offset(Point::new(-7.5, 0.0), center, 10.0, focal) - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn transforms() {
+        let t = Transform::new(2.0, 0.0, 0.0, 3.0, 1.0, 1.0);
+        let r = Transform::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0);
+        let p = Point::new(1.0, 2.0);
+        assert_eq!(t.then(&r).apply(p), r.apply(t.apply(p)));
+        let back = t.invert().unwrap().apply(t.apply(p));
+        assert!((back - p).length() < 1e-5);
+        assert!(Transform::new(1.0, 2.0, 2.0, 4.0, 0.0, 0.0)
+            .invert()
+            .is_none());
+    }
+}